@@ -0,0 +1 @@
+from .meta_stats import *
@@ -0,0 +1,21 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from . import artist as artist
+from . import auth as auth
+from . import bulk as bulk
+from . import cache as cache
+from . import cassette as cassette
+from . import config as config
+from . import crawl as crawl
+from . import dataset as dataset
+from . import engagement as engagement
+from . import export as export
+from . import facebook as facebook
+from . import growth as growth
+from . import handle as handle
+from . import identity as identity
+from . import instagram as instagram
+from . import meta as meta
+from . import mock as mock
+from . import snapshot as snapshot
+from . import version as version
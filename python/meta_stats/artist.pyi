@@ -0,0 +1,111 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.config import MetaConfig
+from meta_stats.facebook import PageEngagement
+from meta_stats.handle import FacebookPageRef, InstagramHandle
+from meta_stats.instagram import InstagramPageInfo
+
+
+class ArtistStats:
+    """An artist's audience across Facebook and Instagram. Platforms that could not be fetched are
+    listed in `failures` instead of failing the whole profile.
+    """
+    def __init__(self, *, facebook_page_id: str | None = None, instagram_username: str | None = None, instagram_linked: bool, name: str | None = None, facebook: PageEngagement | None = None, instagram: InstagramPageInfo | None = None, platforms: list[PlatformReach], failures: list[ArtistFetchFailure], fetched_at: float) -> None: ...
+    @property
+    def facebook_page_id(self) -> str | None: ...
+    @property
+    def instagram_username(self) -> str | None: ...
+    @property
+    def instagram_linked(self) -> bool:
+        """Whether the Instagram account was found through the page's `instagram_business_account`."""
+    @property
+    def name(self) -> str | None:
+        """Instagram display name, when the profile was fetched."""
+    @property
+    def facebook(self) -> PageEngagement | None: ...
+    @property
+    def instagram(self) -> InstagramPageInfo | None: ...
+    @property
+    def platforms(self) -> list[PlatformReach]: ...
+    @property
+    def failures(self) -> list[ArtistFetchFailure]: ...
+    @property
+    def fetched_at(self) -> float:
+        """When the fetch started, in Unix seconds."""
+    def __hash__(self) -> int: ...
+    @property
+    def total_reach(self) -> int: ...
+    @property
+    def is_complete(self) -> bool: ...
+    @property
+    def is_partial(self) -> bool:
+        """Some platforms were fetched and some failed."""
+    def followers(self, platform: str) -> int | None:
+        """Followers on `platform`, or `None` when it was not fetched."""
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> ArtistStats: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> ArtistStats: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PlatformReach:
+    """Followers on one platform, as of `fetched_at` (Unix seconds)."""
+    def __init__(self, *, platform: str, account: str, followers: int, fetched_at: float) -> None: ...
+    @property
+    def platform(self) -> str:
+        """`facebook` or `instagram`."""
+    @property
+    def account(self) -> str:
+        """Page id or username."""
+    @property
+    def followers(self) -> int: ...
+    @property
+    def fetched_at(self) -> float: ...
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PlatformReach: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PlatformReach: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class ArtistFetchFailure:
+    """Why one platform is missing from an `ArtistStats`."""
+    def __init__(self, *, platform: str, category: str, message: str) -> None: ...
+    @property
+    def platform(self) -> str: ...
+    @property
+    def category(self) -> str:
+        """`auth`, `rate_limit`, ... for Meta errors, or `request`, `parse`, ... for local failures."""
+    @property
+    def message(self) -> str: ...
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> ArtistFetchFailure: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> ArtistFetchFailure: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def get_artist_stats(facebook_page_id: FacebookPageRef | str | None = None, instagram_username: InstagramHandle | str | None = None, access_token: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> ArtistStats: ...
@@ -0,0 +1,15 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from meta_stats.config import MetaConfig
+
+
+def get_meta_client_id(config: MetaConfig | None = None) -> str: ...
+
+
+def get_meta_client_secret(config: MetaConfig | None = None) -> str: ...
+
+
+def get_meta_version(config: MetaConfig | None = None) -> str: ...
+
+
+def get_meta_access_token(endpoint_url: str | None = None, client_id: str | None = None, client_secret: str | None = None, grant_type: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> str: ...
@@ -0,0 +1,98 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.config import MetaConfig
+from meta_stats.dataset import DatasetPart, DatasetWriter
+from meta_stats.facebook import PageEngagementResult
+from meta_stats.handle import FacebookPageRef, InstagramHandle
+from meta_stats.instagram import InstagramPageInfoResult
+
+
+class BulkFetchIterator:
+    """Iterator over `BulkPageResult`s or `BulkProfileResult`s as they finish. Use each item's
+    `index` to match it back to the input list. `to_arrow` and the other exports wait for the
+    remaining results and include only those not yet taken from the iterator, ordered by `index`.
+    """
+    @property
+    def total(self) -> int: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    def __iter__(self) -> BulkFetchIterator: ...
+    def __next__(self) -> BulkPageResult | BulkProfileResult: ...
+    def write_dataset(self, writer: DatasetWriter, captured_at: float | None = None) -> tuple[list[DatasetPart], list[BulkPageResult | BulkProfileResult]]:
+        """Waits for the remaining results and writes the successful ones to a `DatasetWriter`.
+        Returns the part files written and the results that failed.
+        """
+
+
+class BulkPageResult:
+    """Outcome for one Facebook page. `result` holds the page or the Meta error; `failure` is set
+    instead when the request itself could not be made or parsed.
+    """
+    def __init__(self, *, index: int, page_id: str, result: PageEngagementResult | None = None, failure: dict[str, str] | None = None) -> None: ...
+    @property
+    def index(self) -> int: ...
+    @property
+    def page_id(self) -> str: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def result(self) -> PageEngagementResult | None: ...
+    @property
+    def error_category(self) -> str | None:
+        """`auth`, `rate_limit`, ... for Meta errors, or `request`, `parse`, ... for local failures."""
+    @property
+    def error_message(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BulkPageResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BulkPageResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BulkProfileResult:
+    """Outcome for one Instagram profile, shaped like `BulkPageResult`."""
+    def __init__(self, *, index: int, username: str, result: InstagramPageInfoResult | None = None, failure: dict[str, str] | None = None) -> None: ...
+    @property
+    def index(self) -> int: ...
+    @property
+    def username(self) -> str: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def result(self) -> InstagramPageInfoResult | None: ...
+    @property
+    def error_category(self) -> str | None: ...
+    @property
+    def error_message(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BulkProfileResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BulkProfileResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def bulk_fetch_facebook_pages(ids: list[FacebookPageRef | str], fields: list[str] | None = None, concurrency: int | None = None, access_token: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> BulkFetchIterator: ...
+
+
+def bulk_fetch_instagram_profiles(usernames: list[InstagramHandle | str], concurrency: int | None = None, config: MetaConfig | None = None) -> BulkFetchIterator: ...
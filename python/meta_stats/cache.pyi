@@ -0,0 +1,10 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+
+def configure_cache(directory: str, default_ttl_seconds: int | None = None, endpoint_ttls: dict[str, int] | None = None, stale_while_error: bool | None = None) -> None: ...
+
+
+def disable_cache() -> None: ...
+
+
+def clear_cache() -> int: ...
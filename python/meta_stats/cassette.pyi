@@ -0,0 +1,7 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+
+def use_cassette(path: str, mode: str | None = None) -> None: ...
+
+
+def eject_cassette() -> None: ...
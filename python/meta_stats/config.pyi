@@ -0,0 +1,37 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+
+class MetaConfig:
+    """Immutable settings for one Meta app: credentials, API version, hosts and HTTP behaviour.
+
+    Every fetch function takes an optional `config`; without one the settings are read from
+    `META_*` environment variables (and `.env`) as before.
+    """
+    @property
+    def profile(self) -> str: ...
+    @property
+    def client_id(self) -> str | None: ...
+    @property
+    def has_client_secret(self) -> bool: ...
+    @property
+    def version(self) -> str | None: ...
+    @property
+    def graph_base_url(self) -> str | None: ...
+    @property
+    def instagram_base_url(self) -> str | None: ...
+    @property
+    def proxy(self) -> str | None: ...
+    @property
+    def timeout_seconds(self) -> int: ...
+    @property
+    def connect_timeout_seconds(self) -> int: ...
+    @property
+    def max_retries(self) -> int: ...
+    @property
+    def retry_backoff_ms(self) -> int: ...
+    @property
+    def max_concurrency_per_host(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+def load_config(path: str | None = None, profile: str | None = None, env_prefix: str | None = None, client_id: str | None = None, client_secret: str | None = None, version: str | None = None, graph_base_url: str | None = None, instagram_base_url: str | None = None, proxy: str | None = None, timeout_seconds: int | None = None, connect_timeout_seconds: int | None = None, max_retries: int | None = None, retry_backoff_ms: int | None = None, max_concurrency_per_host: int | None = None) -> MetaConfig: ...
@@ -0,0 +1,76 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.config import MetaConfig
+from meta_stats.facebook import PostInfo
+from meta_stats.handle import FacebookPageRef, InstagramHandle
+from meta_stats.instagram import FeedMedia
+
+
+class CrawlJob:
+    """A crawl of full post history for Facebook pages and media history for Instagram users,
+    checkpointed to SQLite after every page so an interrupted run resumes from its last cursor.
+
+    Only cursors are stored, never `paging.next` URLs, so no access token is written to disk.
+    """
+    @property
+    def path(self) -> str: ...
+    @staticmethod
+    def open(path: str) -> CrawlJob:
+        """Opens the job stored at `path`, creating it if needed."""
+    def add_facebook_pages(self, page_ids: list[FacebookPageRef | str]) -> int: ...
+    def add_instagram_users(self, usernames: list[InstagramHandle | str]) -> int: ...
+    def retry_failed(self) -> int: ...
+    def progress(self) -> CrawlProgress: ...
+    def run(self, access_token: str | None = None, meta_version: str | None = None, max_pages: int | None = None, progress_callback: Any | None = None, config: MetaConfig | None = None) -> CrawlProgress:
+        """Runs the job with the GIL released. `progress_callback(progress)` is called after every
+        page; returning `False` stops the run, and an exception stops it and is re-raised.
+        """
+    def facebook_posts(self, page_id: FacebookPageRef | str) -> list[PostInfo]: ...
+    def instagram_media(self, username: InstagramHandle | str) -> list[FeedMedia]: ...
+    def __repr__(self) -> str: ...
+
+
+class CrawlProgress:
+    """Snapshot of a crawl job. `eta_seconds` extrapolates the item rate of the current (or most
+    recent) run over the items still expected, using the average size of finished targets
+    where a target's total is not known up front.
+    """
+    def __init__(self, *, targets_total: int, targets_done: int, targets_failed: int, targets_pending: int, pages_fetched: int, items_fetched: int, elapsed_seconds: float, eta_seconds: float | None = None, stopped_reason: str | None = None, last_error: str | None = None) -> None: ...
+    @property
+    def targets_total(self) -> int: ...
+    @property
+    def targets_done(self) -> int: ...
+    @property
+    def targets_failed(self) -> int: ...
+    @property
+    def targets_pending(self) -> int: ...
+    @property
+    def pages_fetched(self) -> int: ...
+    @property
+    def items_fetched(self) -> int: ...
+    @property
+    def elapsed_seconds(self) -> float: ...
+    @property
+    def eta_seconds(self) -> float | None: ...
+    @property
+    def stopped_reason(self) -> str | None:
+        """Why the last run stopped early: `auth`, `rate_limit`, `transient`, `request`, `max_pages`,
+        `cancelled`, or `error` when it raised.
+        """
+    @property
+    def last_error(self) -> str | None: ...
+    @property
+    def is_complete(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CrawlProgress: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CrawlProgress: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
@@ -0,0 +1,51 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.facebook import CommentNode, PageEngagement, PostInfo
+from meta_stats.instagram import InstagramPageInfo
+
+
+class DatasetWriter:
+    """Writes results as Hive-style partitioned files, `<root>/<dataset>/platform=<platform>/date=<YYYY-MM-DD>/part-*.parquet`,
+    partitioned by the UTC date of capture. Every write adds a new part file, so a dataset is
+    appended to by writing again; files are written under a hidden temporary name and renamed
+    into place, so readers never see a partial file.
+    """
+    @staticmethod
+    def open(root: str, format: str | None = None) -> DatasetWriter:
+        """`format` is `parquet` (the default) or `csv`."""
+    @property
+    def root(self) -> str: ...
+    @property
+    def format(self) -> str: ...
+    def write_posts(self, page_id: str, posts: list[PostInfo], captured_at: float | None = None) -> DatasetPart | None: ...
+    def write_comments(self, post_id: str, comments: list[CommentNode], captured_at: float | None = None) -> DatasetPart | None: ...
+    def write_page_engagement(self, pages: list[PageEngagement], captured_at: float | None = None) -> DatasetPart | None: ...
+    def write_instagram_profiles(self, profiles: list[InstagramPageInfo], captured_at: float | None = None) -> DatasetPart | None: ...
+    def __repr__(self) -> str: ...
+
+
+class DatasetPart:
+    """One file added to a dataset by a single write."""
+    def __init__(self, *, dataset: str, platform: str, path: str, rows: int) -> None: ...
+    @property
+    def dataset(self) -> str: ...
+    @property
+    def platform(self) -> str: ...
+    @property
+    def path(self) -> str: ...
+    @property
+    def rows(self) -> int: ...
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> DatasetPart: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> DatasetPart: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
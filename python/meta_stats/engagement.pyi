@@ -0,0 +1,95 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.facebook import PostInfo
+from meta_stats.instagram import FeedMedia, InstagramPageInfo
+
+
+class EngagementSummary:
+    """Engagement across an account's recent posts."""
+    def __init__(self, *, platform: str, account: str | None = None, followers: int, posts: list[PostEngagement], total_interactions: int, mean_rate: float | None = None, median_rate: float | None = None) -> None: ...
+    @property
+    def platform(self) -> str:
+        """`facebook` or `instagram`."""
+    @property
+    def account(self) -> str | None: ...
+    @property
+    def followers(self) -> int:
+        """Follower count the rates are relative to, as of when the posts were fetched."""
+    @property
+    def posts(self) -> list[PostEngagement]:
+        """The posts considered, newest first."""
+    @property
+    def total_interactions(self) -> int: ...
+    @property
+    def mean_rate(self) -> float | None: ...
+    @property
+    def median_rate(self) -> float | None: ...
+    def __hash__(self) -> int: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    @property
+    def post_count(self) -> int: ...
+    @property
+    def outliers(self) -> list[PostEngagement]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> EngagementSummary: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> EngagementSummary: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PostEngagement:
+    """Interactions on one post relative to the account's follower count."""
+    def __init__(self, *, post_id: str, likes: int, comments: int, shares: int | None = None, reactions: int | None = None, interactions: int, engagement_rate: float | None = None, is_outlier: bool) -> None: ...
+    @property
+    def post_id(self) -> str: ...
+    @property
+    def likes(self) -> int: ...
+    @property
+    def comments(self) -> int: ...
+    @property
+    def shares(self) -> int | None:
+        """Facebook only; `None` when the post has no share count."""
+    @property
+    def reactions(self) -> int | None:
+        """Total Facebook reactions, likes included, when supplied."""
+    @property
+    def interactions(self) -> int: ...
+    @property
+    def engagement_rate(self) -> float | None:
+        """Interactions as a percentage of followers; `None` when the follower count is zero."""
+    @property
+    def is_outlier(self) -> bool:
+        """Whether the post's rate is far above the account's usual rate."""
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PostEngagement: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PostEngagement: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def facebook_engagement(posts: list[PostInfo], followers: int, last_n: int | None = None, reactions: dict[str, int] | None = None, account: str | None = None) -> EngagementSummary: ...
+
+
+def instagram_engagement(media: list[FeedMedia], followers: int, last_n: int | None = None, account: str | None = None) -> EngagementSummary: ...
+
+
+def instagram_profile_engagement(page_info: InstagramPageInfo, last_n: int | None = None, username: str | None = None) -> EngagementSummary: ...
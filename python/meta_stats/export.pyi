@@ -0,0 +1,27 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.engagement import PostEngagement
+from meta_stats.facebook import CommentNode, PageEngagement, PageEvent, PageVideo, PostInfo
+from meta_stats.instagram import FeedMedia, InstagramPageInfo, MediaNode
+from meta_stats.snapshot import Snapshot
+
+
+def to_arrow(rows: list[PostInfo] | list[CommentNode] | list[PageEvent] | list[PageVideo] | list[MediaNode] | list[FeedMedia] | list[PostEngagement] | list[Snapshot] | list[PageEngagement] | list[InstagramPageInfo], kind: str | None = None) -> Any:
+    """Exports a list of posts, comments, events, videos, media, post engagement, snapshots, pages or
+    profiles, such as those returned by `CrawlJob.facebook_posts` or `SnapshotStore.history`.
+    `kind` names what the list holds (`posts`, `comments`, `events`, `videos`, `timeline_media`,
+    `feed_media`, `engagement`, `snapshots`, `pages` or `profiles`); it is required for an empty
+    list, whose columns could otherwise be any of these.
+    """
+
+
+def to_pandas(rows: list[PostInfo] | list[CommentNode] | list[PageEvent] | list[PageVideo] | list[MediaNode] | list[FeedMedia] | list[PostEngagement] | list[Snapshot] | list[PageEngagement] | list[InstagramPageInfo], kind: str | None = None) -> Any: ...
+
+
+def to_polars(rows: list[PostInfo] | list[CommentNode] | list[PageEvent] | list[PageVideo] | list[MediaNode] | list[FeedMedia] | list[PostEngagement] | list[Snapshot] | list[PageEngagement] | list[InstagramPageInfo], kind: str | None = None) -> Any: ...
+
+
+def columns(rows: list[PostInfo] | list[CommentNode] | list[PageEvent] | list[PageVideo] | list[MediaNode] | list[FeedMedia] | list[PostEngagement] | list[Snapshot] | list[PageEngagement] | list[InstagramPageInfo], kind: str | None = None) -> list[str]:
+    """Names of the columns a list would be exported with."""
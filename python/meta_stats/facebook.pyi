@@ -0,0 +1,849 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from datetime import datetime
+from typing import Any, Iterator, Literal, overload
+
+from meta_stats.config import MetaConfig
+from meta_stats.handle import FacebookPageRef
+from meta_stats.meta import MetaError, Paging
+from meta_stats.snapshot import SnapshotStore
+
+
+class BasicPageInfo:
+    def __init__(self, *, id: str = ..., name: str = ...) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BasicPageInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BasicPageInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageCategory:
+    def __init__(self, *, id: str = ..., name: str = ...) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageCategory: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageCategory: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageEngagement:
+    def __init__(self, *, category: str = ..., category_list: list[PageCategory] = ..., followers_count: int = ..., fan_count: int = ..., overall_star_rating: float = ..., rating_count: int = ..., talking_about_count: int = ..., id: str = ...) -> None: ...
+    @property
+    def category(self) -> str: ...
+    @property
+    def category_list(self) -> list[PageCategory]: ...
+    @property
+    def followers_count(self) -> int: ...
+    @property
+    def fan_count(self) -> int: ...
+    @property
+    def overall_star_rating(self) -> float: ...
+    @property
+    def rating_count(self) -> int: ...
+    @property
+    def talking_about_count(self) -> int: ...
+    @property
+    def id(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageEngagement: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageEngagement: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CommentParent:
+    def __init__(self, *, id: str) -> None: ...
+    @property
+    def id(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CommentParent: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CommentParent: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CommentInfo:
+    def __init__(self, *, message: str | None = None, created_time: datetime | None = None, id: str | None = None, like_count: int | None = None, comment_count: int | None = None, parent: CommentParent | None = None) -> None: ...
+    @property
+    def message(self) -> str | None: ...
+    @property
+    def created_time(self) -> datetime | None: ...
+    @property
+    def id(self) -> str | None: ...
+    @property
+    def like_count(self) -> int | None: ...
+    @property
+    def comment_count(self) -> int | None: ...
+    @property
+    def parent(self) -> CommentParent | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CommentInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CommentInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CommentNode:
+    def __init__(self, *, comment: CommentInfo, replies: list[CommentNode]) -> None: ...
+    @property
+    def comment(self) -> CommentInfo: ...
+    @property
+    def replies(self) -> list[CommentNode]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CommentNode: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CommentNode: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class LikeInfo:
+    def __init__(self, *, id: str | None = None) -> None: ...
+    @property
+    def id(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> LikeInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> LikeInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class LikeSummary:
+    def __init__(self, *, total_count: int = ..., can_like: bool = ..., has_liked: bool = ...) -> None: ...
+    @property
+    def total_count(self) -> int: ...
+    @property
+    def can_like(self) -> bool: ...
+    @property
+    def has_liked(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> LikeSummary: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> LikeSummary: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CommentSummary:
+    def __init__(self, *, order: str = ..., total_count: int = ..., can_comment: bool = ...) -> None: ...
+    @property
+    def order(self) -> str: ...
+    @property
+    def total_count(self) -> int: ...
+    @property
+    def can_comment(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CommentSummary: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CommentSummary: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BasicCommentsInfo:
+    def __init__(self, *, data: list[CommentInfo] = ..., paging: Paging | None = None, summary: CommentSummary = ...) -> None: ...
+    @property
+    def data(self) -> list[CommentInfo]: ...
+    @property
+    def paging(self) -> Paging | None: ...
+    @property
+    def summary(self) -> CommentSummary: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[CommentInfo]: ...
+    @overload
+    def __getitem__(self, index: int) -> CommentInfo:
+        """An item by position, or a list of items for a slice."""
+    @overload
+    def __getitem__(self, index: slice) -> list[CommentInfo]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BasicCommentsInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BasicCommentsInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BasicLikesInfo:
+    def __init__(self, *, data: list[LikeInfo] = ..., paging: Paging | None = None, summary: LikeSummary = ...) -> None: ...
+    @property
+    def data(self) -> list[LikeInfo]: ...
+    @property
+    def paging(self) -> Paging | None: ...
+    @property
+    def summary(self) -> LikeSummary: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[LikeInfo]: ...
+    @overload
+    def __getitem__(self, index: int) -> LikeInfo:
+        """An item by position, or a list of items for a slice."""
+    @overload
+    def __getitem__(self, index: slice) -> list[LikeInfo]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BasicLikesInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BasicLikesInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PostShares:
+    def __init__(self, *, count: int = ...) -> None: ...
+    @property
+    def count(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PostShares: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PostShares: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PostAttachment:
+    def __init__(self, *, media_type: str | None = None, url: str | None = None, title: str | None = None, subattachments: PostAttachments | None = None) -> None: ...
+    @property
+    def media_type(self) -> str | None: ...
+    @property
+    def url(self) -> str | None: ...
+    @property
+    def title(self) -> str | None: ...
+    @property
+    def subattachments(self) -> PostAttachments | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PostAttachment: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PostAttachment: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PostAttachments:
+    def __init__(self, *, data: list[PostAttachment] = ...) -> None: ...
+    @property
+    def data(self) -> list[PostAttachment]: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[PostAttachment]: ...
+    @overload
+    def __getitem__(self, index: int) -> PostAttachment:
+        """An item by position, or a list of items for a slice."""
+    @overload
+    def __getitem__(self, index: slice) -> list[PostAttachment]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PostAttachments: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PostAttachments: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MessageTag:
+    def __init__(self, *, id: str = ..., name: str = ..., type: str | None = None, offset: int = ..., length: int = ...) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def type(self) -> str | None: ...
+    @property
+    def offset(self) -> int: ...
+    @property
+    def length(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MessageTag: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MessageTag: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PostInfo:
+    def __init__(self, *, id: str = ..., message: str | None = None, created_time: datetime | None = None, story: str | None = None, likes: BasicLikesInfo | None = None, comments: BasicCommentsInfo | None = None, permalink_url: str | None = None, shares: PostShares | None = None, status_type: str | None = None, full_picture: str | None = None, attachments: PostAttachments | None = None, is_published: bool | None = None, place: Place | None = None, message_tags: list[MessageTag] | None = None) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def message(self) -> str | None: ...
+    @property
+    def created_time(self) -> datetime | None: ...
+    @property
+    def story(self) -> str | None: ...
+    @property
+    def likes(self) -> BasicLikesInfo | None: ...
+    @property
+    def comments(self) -> BasicCommentsInfo | None: ...
+    @property
+    def permalink_url(self) -> str | None: ...
+    @property
+    def shares(self) -> PostShares | None: ...
+    @property
+    def status_type(self) -> str | None: ...
+    @property
+    def full_picture(self) -> str | None: ...
+    @property
+    def attachments(self) -> PostAttachments | None: ...
+    @property
+    def is_published(self) -> bool | None: ...
+    @property
+    def place(self) -> Place | None: ...
+    @property
+    def message_tags(self) -> list[MessageTag] | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PostInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PostInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BasicPostsInfo:
+    def __init__(self, *, data: list[PostInfo], paging: Paging) -> None: ...
+    @property
+    def data(self) -> list[PostInfo]: ...
+    @property
+    def paging(self) -> Paging: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[PostInfo]: ...
+    @overload
+    def __getitem__(self, index: int) -> PostInfo:
+        """An item by position, or a list of items for a slice."""
+    @overload
+    def __getitem__(self, index: slice) -> list[PostInfo]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BasicPostsInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BasicPostsInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PlaceLocation:
+    def __init__(self, *, city: str | None = None, country: str | None = None, latitude: float | None = None, longitude: float | None = None) -> None: ...
+    @property
+    def city(self) -> str | None: ...
+    @property
+    def country(self) -> str | None: ...
+    @property
+    def latitude(self) -> float | None: ...
+    @property
+    def longitude(self) -> float | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PlaceLocation: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PlaceLocation: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class Place:
+    def __init__(self, *, id: str | None = None, name: str | None = None, location: PlaceLocation | None = None) -> None: ...
+    @property
+    def id(self) -> str | None: ...
+    @property
+    def name(self) -> str | None: ...
+    @property
+    def location(self) -> PlaceLocation | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> Place: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> Place: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageEvent:
+    def __init__(self, *, id: str = ..., name: str = ..., description: str | None = None, start_time: datetime | None = None, end_time: datetime | None = None, timezone: str | None = None, place: Place | None = None, ticket_uri: str | None = None, attending_count: int = ..., interested_count: int = ..., maybe_count: int = ..., is_canceled: bool = ...) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def description(self) -> str | None: ...
+    @property
+    def start_time(self) -> datetime | None: ...
+    @property
+    def end_time(self) -> datetime | None: ...
+    @property
+    def timezone(self) -> str | None: ...
+    @property
+    def place(self) -> Place | None: ...
+    @property
+    def ticket_uri(self) -> str | None: ...
+    @property
+    def attending_count(self) -> int: ...
+    @property
+    def interested_count(self) -> int: ...
+    @property
+    def maybe_count(self) -> int: ...
+    @property
+    def is_canceled(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageEvent: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageEvent: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class VideoInsights:
+    def __init__(self, *, total_video_views: int | None = None, total_video_impressions: int | None = None, total_video_complete_views: int | None = None, total_video_avg_time_watched: int | None = None, total_video_view_total_time: int | None = None) -> None: ...
+    @property
+    def total_video_views(self) -> int | None: ...
+    @property
+    def total_video_impressions(self) -> int | None: ...
+    @property
+    def total_video_complete_views(self) -> int | None: ...
+    @property
+    def total_video_avg_time_watched(self) -> int | None: ...
+    @property
+    def total_video_view_total_time(self) -> int | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> VideoInsights: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> VideoInsights: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageVideo:
+    def __init__(self, *, id: str = ..., title: str | None = None, description: str | None = None, length: float | None = None, created_time: datetime | None = None, permalink_url: str | None = None, views: int | None = None, insights: VideoInsights | None = None) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def title(self) -> str | None: ...
+    @property
+    def description(self) -> str | None: ...
+    @property
+    def length(self) -> float | None: ...
+    @property
+    def created_time(self) -> datetime | None: ...
+    @property
+    def permalink_url(self) -> str | None: ...
+    @property
+    def views(self) -> int | None: ...
+    @property
+    def insights(self) -> VideoInsights | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageVideo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageVideo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BasicPageInfoResult:
+    def __init__(self, *, is_success: bool, page_info: BasicPageInfo | None = None, error: MetaError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    @staticmethod
+    def success(info: BasicPageInfo) -> BasicPageInfoResult: ...
+    @staticmethod
+    def error(err: MetaError) -> BasicPageInfoResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def page_info(self) -> BasicPageInfo | None: ...
+    @property
+    def error_info(self) -> MetaError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BasicPageInfoResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BasicPageInfoResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageEngagementResult:
+    def __init__(self, *, is_success: bool, page_engagement: PageEngagement | None = None, error: MetaError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    @staticmethod
+    def success(info: PageEngagement) -> PageEngagementResult: ...
+    @staticmethod
+    def error(err: MetaError) -> PageEngagementResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def page_engagement(self) -> PageEngagement | None: ...
+    @property
+    def error_info(self) -> MetaError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageEngagementResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageEngagementResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BasicPostsInfoResult:
+    def __init__(self, *, is_success: bool, posts_info: BasicPostsInfo | None = None, error: MetaError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    @staticmethod
+    def success(info: BasicPostsInfo) -> BasicPostsInfoResult: ...
+    @staticmethod
+    def error(err: MetaError) -> BasicPostsInfoResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def posts_info(self) -> BasicPostsInfo | None: ...
+    @property
+    def error_info(self) -> MetaError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BasicPostsInfoResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BasicPostsInfoResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageEventsResult:
+    def __init__(self, *, is_success: bool, events: list[PageEvent] | None = None, error: MetaError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    @staticmethod
+    def success(events: list[PageEvent]) -> PageEventsResult: ...
+    @staticmethod
+    def error(err: MetaError) -> PageEventsResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def events(self) -> list[PageEvent] | None: ...
+    @property
+    def error_info(self) -> MetaError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageEventsResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageEventsResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageVideosResult:
+    def __init__(self, *, is_success: bool, videos: list[PageVideo] | None = None, error: MetaError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    @staticmethod
+    def success(videos: list[PageVideo]) -> PageVideosResult: ...
+    @staticmethod
+    def error(err: MetaError) -> PageVideosResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def videos(self) -> list[PageVideo] | None: ...
+    @property
+    def error_info(self) -> MetaError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageVideosResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageVideosResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PostCommentsResult:
+    def __init__(self, *, is_success: bool, comments: list[CommentNode] | None = None, error: MetaError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    @staticmethod
+    def success(comments: list[CommentNode]) -> PostCommentsResult: ...
+    @staticmethod
+    def error(err: MetaError) -> PostCommentsResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def comments(self) -> list[CommentNode] | None: ...
+    @property
+    def error_info(self) -> MetaError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PostCommentsResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PostCommentsResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class InstagramBusinessAccount:
+    """An Instagram account linked to a Facebook page."""
+    def __init__(self, *, id: str = ..., username: str | None = None) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def username(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> InstagramBusinessAccount: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> InstagramBusinessAccount: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class PageLinks:
+    """A page's name, vanity username, website and linked Instagram accounts: what is needed to
+    match it with an Instagram profile.
+    """
+    def __init__(self, *, id: str = ..., name: str = ..., username: str | None = None, website: str | None = None, instagram_business_account: InstagramBusinessAccount | None = None, connected_instagram_account: InstagramBusinessAccount | None = None) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def username(self) -> str | None: ...
+    @property
+    def website(self) -> str | None: ...
+    @property
+    def instagram_business_account(self) -> InstagramBusinessAccount | None:
+        """The Instagram professional account the page manages."""
+    @property
+    def connected_instagram_account(self) -> InstagramBusinessAccount | None:
+        """The Instagram account shown on the page, which need not be a professional account."""
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PageLinks: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> PageLinks: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def get_facebook_page_info(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> BasicPageInfoResult: ...
+
+
+def get_facebook_page_followers(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> PageEngagementResult: ...
+
+
+def get_facebook_page_posts(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, extended_fields: bool | None = None, config: MetaConfig | None = None, *, since: datetime | None = None, until: datetime | None = None) -> BasicPostsInfoResult:
+    """`since` and `until` are timezone-aware datetimes; only posts created between them, both
+    inclusive, are returned.
+    """
+
+
+def get_facebook_page_posts_with_summary(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, extended_fields: bool | None = None, config: MetaConfig | None = None, *, since: datetime | None = None, until: datetime | None = None) -> BasicPostsInfoResult:
+    """`since` and `until` are timezone-aware datetimes; only posts created between them, both
+    inclusive, are returned.
+    """
+
+
+def get_facebook_next_results(next: str, config: MetaConfig | None = None) -> str: ...
+
+
+def get_facebook_post_interactions(access_token: str | None = None, post_id: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> str: ...
+
+
+def parse_next_results_to_basic_posts_info(raw_text: str) -> BasicPostsInfoResult: ...
+
+
+def get_facebook_page_events(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, time_filter: str | None = None, config: MetaConfig | None = None) -> PageEventsResult: ...
+
+
+def get_facebook_page_videos(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, include_insights: bool | None = None, config: MetaConfig | None = None) -> PageVideosResult: ...
+
+
+def get_facebook_page_reels(access_token: str | None = None, page_id: FacebookPageRef | str | None = None, meta_version: str | None = None, include_insights: bool | None = None, config: MetaConfig | None = None) -> PageVideosResult: ...
+
+
+def get_facebook_post_comments(access_token: str | None = None, post_id: str | None = None, meta_version: str | None = None, order: str | None = None, filter: str | None = None, config: MetaConfig | None = None) -> PostCommentsResult: ...
+
+
+def resolve_page_id(page_id: FacebookPageRef | str, access_token: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None, store: SnapshotStore | None = None, refresh: bool | None = None) -> str:
+    """Raises `ValueError` with Meta's message when the page cannot be found. `refresh` asks the
+    API even when `store` already knows the name.
+    """
@@ -0,0 +1,90 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.snapshot import Snapshot
+
+
+class GrowthReport:
+    """Growth and trend figures for one metric of one entity, computed from its snapshot history."""
+    def __init__(self, *, entity: str, metric: str, as_of: float, current: float, windows: list[GrowthWindow], rolling_average: list[tuple[float, float]], acceleration: float | None = None, normalized_growth_30d: float | None = None) -> None: ...
+    @property
+    def entity(self) -> str: ...
+    @property
+    def metric(self) -> str: ...
+    @property
+    def as_of(self) -> float:
+        """Time the report is computed for; defaults to the newest snapshot."""
+    @property
+    def current(self) -> float: ...
+    @property
+    def windows(self) -> list[GrowthWindow]:
+        """One entry per window in `GROWTH_WINDOWS` that the history covers."""
+    @property
+    def rolling_average(self) -> list[tuple[float, float]]:
+        """`(captured_at, mean of the values in the 7 days up to it)` for every snapshot."""
+    @property
+    def acceleration(self) -> float | None:
+        """Daily growth over the last 7 days minus daily growth over the 7 days before, so
+        positive values mean growth is speeding up.
+        """
+    @property
+    def normalized_growth_30d(self) -> float | None:
+        """30-day absolute growth per million monthly users of the platform."""
+    def __hash__(self) -> int: ...
+    @property
+    def growth_7d(self) -> GrowthWindow | None: ...
+    @property
+    def growth_30d(self) -> GrowthWindow | None: ...
+    @property
+    def growth_90d(self) -> GrowthWindow | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> GrowthReport: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> GrowthReport: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class GrowthWindow:
+    """Change of a metric over the `days` before the report's `as_of` time."""
+    def __init__(self, *, days: int, start_value: float, start_at: float, end_value: float, absolute: float, percent: float | None = None, per_day: float) -> None: ...
+    @property
+    def days(self) -> int: ...
+    @property
+    def start_value(self) -> float: ...
+    @property
+    def start_at(self) -> float: ...
+    @property
+    def end_value(self) -> float: ...
+    @property
+    def absolute(self) -> float: ...
+    @property
+    def percent(self) -> float | None:
+        """`None` when the window starts at zero."""
+    @property
+    def per_day(self) -> float:
+        """Average change per day between the two snapshots actually compared."""
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> GrowthWindow: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> GrowthWindow: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def growth_report(history: list[Snapshot], as_of: float | None = None, platform_size: float | None = None) -> GrowthReport | None:
+    """Computes a `GrowthReport` from snapshots such as those returned by `SnapshotStore.history`.
+    `platform_size` overrides the monthly users used for `normalized_growth_30d`.
+    """
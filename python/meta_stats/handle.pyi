@@ -0,0 +1,49 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+
+class FacebookPageRef:
+    """A Facebook page, either by numeric id or by vanity username. Its string form is what goes
+    in the Graph API path.
+    """
+    def __init__(self, input: str) -> None:
+        """Same as `parse`."""
+    @staticmethod
+    def parse(input: str) -> FacebookPageRef: ...
+    @property
+    def id(self) -> str | None:
+        """Numeric page id, when the page was given by id."""
+    @property
+    def vanity(self) -> str | None:
+        """Vanity username, when the page was given by name."""
+    @property
+    def is_numeric(self) -> bool: ...
+    @property
+    def url(self) -> str: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]:
+        """Pickles as a call to `parse` with the string form."""
+
+
+class InstagramHandle:
+    """A validated Instagram username, lower-cased."""
+    def __init__(self, input: str) -> None:
+        """Same as `parse`."""
+    @staticmethod
+    def parse(input: str) -> InstagramHandle: ...
+    @property
+    def username(self) -> str: ...
+    @property
+    def url(self) -> str: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]:
+        """Pickles as a call to `parse` with the string form."""
@@ -0,0 +1,100 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.artist import ArtistFetchFailure
+from meta_stats.config import MetaConfig
+from meta_stats.handle import FacebookPageRef, InstagramHandle
+
+
+class IdentityResolution:
+    """Candidates for the artist's account on the other platform, most likely first, and the
+    lookups that failed along the way.
+    """
+    def __init__(self, *, platform: str, account: str, candidates: list[IdentityCandidate], failures: list[ArtistFetchFailure]) -> None: ...
+    @property
+    def platform(self) -> str: ...
+    @property
+    def account(self) -> str: ...
+    @property
+    def candidates(self) -> list[IdentityCandidate]: ...
+    @property
+    def failures(self) -> list[ArtistFetchFailure]: ...
+    def __hash__(self) -> int: ...
+    @property
+    def best(self) -> IdentityCandidate | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> IdentityResolution: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> IdentityResolution: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class IdentityCandidate:
+    """A possible account for the artist on the other platform."""
+    def __init__(self, *, platform: str, account: str, name: str | None = None, evidence: list[MatchEvidence]) -> None: ...
+    @property
+    def platform(self) -> str:
+        """`facebook` or `instagram`."""
+    @property
+    def account(self) -> str:
+        """Page id or vanity name for Facebook, username for Instagram."""
+    @property
+    def name(self) -> str | None: ...
+    @property
+    def evidence(self) -> list[MatchEvidence]: ...
+    def __hash__(self) -> int: ...
+    @property
+    def confidence(self) -> float: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> IdentityCandidate: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> IdentityCandidate: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MatchEvidence:
+    """One reason to believe a candidate is the same artist."""
+    def __init__(self, *, kind: str, detail: str, weight: float) -> None: ...
+    @property
+    def kind(self) -> str:
+        """`instagram_business_account`, `connected_instagram_account`, `profile_link`,
+        `page_website` or `name_match`.
+        """
+    @property
+    def detail(self) -> str: ...
+    @property
+    def weight(self) -> float: ...
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MatchEvidence: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MatchEvidence: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def resolve_instagram_for_page(page_id: FacebookPageRef | str, access_token: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> IdentityResolution: ...
+
+
+def resolve_facebook_for_instagram(username: InstagramHandle | str, access_token: str | None = None, meta_version: str | None = None, config: MetaConfig | None = None) -> IdentityResolution: ...
+
+
+def name_similarity(a: str, b: str) -> float: ...
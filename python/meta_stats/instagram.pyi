@@ -0,0 +1,384 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from datetime import datetime
+from typing import Any, Iterator, Literal, overload
+
+from meta_stats.config import MetaConfig
+from meta_stats.handle import InstagramHandle
+from meta_stats.meta import MetaInstagramError
+from meta_stats.snapshot import SnapshotStore
+
+
+class FollowedBy:
+    def __init__(self, *, count: int = ...) -> None: ...
+    @property
+    def count(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> FollowedBy: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> FollowedBy: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class EdgeCount:
+    def __init__(self, *, count: int = ...) -> None: ...
+    @property
+    def count(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> EdgeCount: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> EdgeCount: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MediaPageInfo:
+    def __init__(self, *, has_next_page: bool = ..., end_cursor: str | None = None) -> None: ...
+    @property
+    def has_next_page(self) -> bool: ...
+    @property
+    def end_cursor(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MediaPageInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MediaPageInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CaptionNode:
+    def __init__(self, *, text: str = ...) -> None: ...
+    @property
+    def text(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CaptionNode: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CaptionNode: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CaptionEdge:
+    def __init__(self, *, node: CaptionNode = ...) -> None: ...
+    @property
+    def node(self) -> CaptionNode: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CaptionEdge: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CaptionEdge: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class CaptionEdges:
+    def __init__(self, *, edges: list[CaptionEdge] = ...) -> None: ...
+    @property
+    def edges(self) -> list[CaptionEdge]: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[CaptionEdge]: ...
+    @overload
+    def __getitem__(self, index: int) -> CaptionEdge:
+        """An item by position, or a list of items for a slice."""
+    @overload
+    def __getitem__(self, index: slice) -> list[CaptionEdge]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CaptionEdges: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> CaptionEdges: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MediaNode:
+    def __init__(self, *, id: str = ..., shortcode: str = ..., taken_at_timestamp: datetime | None = None, is_video: bool = ..., display_url: str | None = None, video_view_count: int | None = None, edge_liked_by: EdgeCount = ..., edge_media_to_comment: EdgeCount = ..., edge_media_to_caption: CaptionEdges = ...) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def shortcode(self) -> str: ...
+    @property
+    def taken_at_timestamp(self) -> datetime | None: ...
+    @property
+    def is_video(self) -> bool: ...
+    @property
+    def display_url(self) -> str | None: ...
+    @property
+    def video_view_count(self) -> int | None: ...
+    @property
+    def edge_liked_by(self) -> EdgeCount: ...
+    @property
+    def edge_media_to_comment(self) -> EdgeCount: ...
+    @property
+    def edge_media_to_caption(self) -> CaptionEdges: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MediaNode: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MediaNode: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MediaEdge:
+    def __init__(self, *, node: MediaNode = ...) -> None: ...
+    @property
+    def node(self) -> MediaNode: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MediaEdge: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MediaEdge: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class TimelineMedia:
+    def __init__(self, *, count: int = ..., page_info: MediaPageInfo = ..., edges: list[MediaEdge] = ...) -> None: ...
+    @property
+    def count(self) -> int: ...
+    @property
+    def page_info(self) -> MediaPageInfo: ...
+    @property
+    def edges(self) -> list[MediaEdge]: ...
+    def to_arrow(self) -> Any:
+        """The rows as a `pyarrow.RecordBatch`."""
+    def to_pandas(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_array__(self, requested_schema: Any | None = None) -> tuple[Any, Any]: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[MediaEdge]: ...
+    @overload
+    def __getitem__(self, index: int) -> MediaEdge:
+        """An item by position, or a list of items for a slice."""
+    @overload
+    def __getitem__(self, index: slice) -> list[MediaEdge]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> TimelineMedia: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> TimelineMedia: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class FeedCaption:
+    def __init__(self, *, text: str = ...) -> None: ...
+    @property
+    def text(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> FeedCaption: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> FeedCaption: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class FeedMedia:
+    """One post from the `api/v1/feed/user/<id>/` endpoint, which pages through a profile's full
+    media history rather than the first twelve posts in `web_profile_info`.
+    """
+    def __init__(self, *, id: str = ..., code: str = ..., taken_at: datetime | None = None, media_type: int = ..., like_count: int = ..., comment_count: int = ..., play_count: int | None = None, caption: FeedCaption | None = None) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def code(self) -> str: ...
+    @property
+    def taken_at(self) -> datetime | None: ...
+    @property
+    def media_type(self) -> int: ...
+    @property
+    def like_count(self) -> int: ...
+    @property
+    def comment_count(self) -> int: ...
+    @property
+    def play_count(self) -> int | None: ...
+    @property
+    def caption(self) -> FeedCaption | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> FeedMedia: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> FeedMedia: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class BioLink:
+    def __init__(self, *, title: str = ..., url: str = ...) -> None: ...
+    @property
+    def title(self) -> str: ...
+    @property
+    def url(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> BioLink: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> BioLink: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class UserInfo:
+    def __init__(self, *, id: str = ..., eimu_id: str = ..., username: str = ..., biography: str = ..., edge_followed_by: FollowedBy = ..., full_name: str = ..., highlight_reel_count: int = ..., category_name: str | None = None, edge_owner_to_timeline_media: TimelineMedia = ..., external_url: str | None = None, bio_links: list[BioLink] = ...) -> None: ...
+    @property
+    def id(self) -> str: ...
+    @property
+    def eimu_id(self) -> str: ...
+    @property
+    def username(self) -> str: ...
+    @property
+    def biography(self) -> str: ...
+    @property
+    def edge_followed_by(self) -> FollowedBy: ...
+    @property
+    def full_name(self) -> str: ...
+    @property
+    def highlight_reel_count(self) -> int: ...
+    @property
+    def category_name(self) -> str | None: ...
+    @property
+    def edge_owner_to_timeline_media(self) -> TimelineMedia: ...
+    @property
+    def external_url(self) -> str | None: ...
+    @property
+    def bio_links(self) -> list[BioLink]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> UserInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> UserInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class InstagramPageData:
+    def __init__(self, *, user: UserInfo = ...) -> None: ...
+    @property
+    def user(self) -> UserInfo: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> InstagramPageData: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> InstagramPageData: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class InstagramPageInfo:
+    def __init__(self, *, data: InstagramPageData = ..., status: str = ...) -> None: ...
+    @property
+    def data(self) -> InstagramPageData: ...
+    @property
+    def status(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> InstagramPageInfo: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> InstagramPageInfo: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class InstagramPageInfoResult:
+    def __init__(self, *, is_success: bool, page_info: InstagramPageInfo | None = None, error: MetaInstagramError | None = None, cache_status: Literal["hit", "miss", "stale"] | None = None) -> None: ...
+    @staticmethod
+    def success(info: InstagramPageInfo) -> InstagramPageInfoResult: ...
+    @staticmethod
+    def error(err: MetaInstagramError) -> InstagramPageInfoResult: ...
+    @property
+    def is_success(self) -> bool: ...
+    @property
+    def is_error(self) -> bool: ...
+    @property
+    def page_info(self) -> InstagramPageInfo | None: ...
+    @property
+    def error_info(self) -> MetaInstagramError | None: ...
+    @property
+    def cache_status(self) -> str | None: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> InstagramPageInfoResult: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> InstagramPageInfoResult: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def get_instagram_page_info(username: InstagramHandle | str | None = None, config: MetaConfig | None = None) -> InstagramPageInfoResult: ...
+
+
+def resolve_user_id(username: InstagramHandle | str, config: MetaConfig | None = None, store: SnapshotStore | None = None, refresh: bool | None = None) -> str:
+    """Raises `ValueError` with Instagram's message when the profile cannot be fetched. `refresh`
+    asks Instagram even when `store` already knows the username.
+    """
@@ -0,0 +1,115 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+
+class Cursor:
+    def __init__(self, *, before: str, after: str) -> None: ...
+    @property
+    def before(self) -> str: ...
+    @property
+    def after(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> Cursor: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> Cursor: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class Paging:
+    def __init__(self, *, next: str | None = None, cursors: Cursor) -> None: ...
+    @property
+    def next(self) -> str | None: ...
+    @property
+    def cursors(self) -> Cursor: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> Paging: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> Paging: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MetaErrorData:
+    def __init__(self, *, message: str, type: str, code: int, error_subcode: int | None = None, fbtrace_id: str = ..., is_transient: bool = ...) -> None: ...
+    @property
+    def message(self) -> str: ...
+    @property
+    def type(self) -> str: ...
+    @property
+    def code(self) -> int: ...
+    @property
+    def error_subcode(self) -> int | None: ...
+    @property
+    def fbtrace_id(self) -> str: ...
+    @property
+    def is_transient(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MetaErrorData: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MetaErrorData: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MetaError:
+    def __init__(self, *, error: MetaErrorData) -> None: ...
+    @property
+    def error(self) -> MetaErrorData: ...
+    @property
+    def category(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MetaError: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MetaError: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class MetaInstagramError:
+    def __init__(self, *, message: str, require_login: bool = ..., igweb_rollout: bool = ..., status: str) -> None: ...
+    @property
+    def message(self) -> str: ...
+    @property
+    def require_login(self) -> bool: ...
+    @property
+    def igweb_rollout(self) -> bool: ...
+    @property
+    def status(self) -> str: ...
+    @property
+    def category(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> MetaInstagramError: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> MetaInstagramError: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def set_base_urls(graph_base_url: str | None = None, instagram_base_url: str | None = None) -> None: ...
@@ -0,0 +1,49 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+
+class MockServer:
+    """A local stand-in for the Graph API and Instagram's `web_profile_info`, for testing code
+    built on this library. Pages, posts and profiles are scripted with the `add_*` methods,
+    and Meta error bodies, 5xx responses and latency can be injected.
+    """
+    @staticmethod
+    def start(port: int | None = None) -> MockServer: ...
+    @property
+    def url(self) -> str: ...
+    @property
+    def requests(self) -> list[str]:
+        """Paths requested so far, with access tokens and secrets scrubbed."""
+    def add_page(self, page_id: str, name: str, followers_count: int | None = None, fan_count: int | None = None, post_count: int | None = None) -> None: ...
+    def link_instagram_account(self, page_id: str, username: str, connected_only: bool | None = None) -> None:
+        """Links a page to an Instagram account, reported as the page's `instagram_business_account`
+        or, with `connected_only`, its `connected_instagram_account`.
+        """
+    def set_page_website(self, page_id: str, website: str) -> None: ...
+    def set_page_username(self, page_id: str, username: str) -> None:
+        """Gives the page a vanity name it can be fetched by instead of its id. Setting it again
+        renames the page; the old name stops resolving.
+        """
+    def rename_instagram_user(self, username: str, new_username: str) -> None:
+        """Changes an Instagram user's username, keeping their id."""
+    def set_instagram_links(self, username: str, external_url: str | None = None, bio_links: list[str] | None = None) -> None:
+        """Sets the profile's `external_url` and `bio_links`."""
+    def add_instagram_user(self, username: str, full_name: str, followers_count: int | None = None, biography: str | None = None, media_count: int | None = None) -> None: ...
+    def inject_error(self, kind: str, path_contains: str | None = None, times: int | None = None, status: int | None = None) -> None:
+        """Makes the next `times` requests whose path contains `path_contains` (any request when
+        unset) fail with the given kind: `invalid_token` (190), `app_rate_limit` (4),
+        `throttled` (613), `permission` (10), `invalid_client` (101, the app token request
+        rejecting the client secret), `require_login` or `server_error` (5xx, `status` defaults
+        to 500).
+        """
+    def set_served_version(self, version: str | None = None) -> None:
+        """Reports `version` in the `facebook-api-version` header of every Graph response, as Meta
+        does after silently upgrading a retired version. `None` echoes the requested version.
+        """
+    def set_latency(self, milliseconds: int) -> None: ...
+    def use_for_requests(self) -> None:
+        """Sends every Graph and Instagram request made by this library to the mock server."""
+    def stop(self) -> None: ...
+    def __enter__(self) -> MockServer: ...
+    def __exit__(self, _exc_type: Any, _exc_value: Any, _traceback: Any) -> bool: ...
@@ -0,0 +1,91 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+from meta_stats.facebook import PageEngagement, PostInfo
+from meta_stats.growth import GrowthReport
+from meta_stats.instagram import FeedMedia, InstagramPageInfo
+
+
+class SnapshotStore:
+    """Timestamped metric history in a local SQLite file, so follower and engagement counts that
+    Meta only reports as current values can be tracked over time.
+    """
+    @property
+    def path(self) -> str: ...
+    @staticmethod
+    def open(path: str) -> SnapshotStore:
+        """Opens the store at `path`, creating it or migrating it to the current schema as needed."""
+    def record(self, entity: str, metric: str, value: float, captured_at: float | None = None) -> int: ...
+    def record_page_engagement(self, engagement: PageEngagement, page_id: str | None = None, captured_at: float | None = None) -> int: ...
+    def record_instagram_profile(self, username: str, page_info: InstagramPageInfo, captured_at: float | None = None) -> int: ...
+    def record_posts(self, posts: list[PostInfo], captured_at: float | None = None) -> int: ...
+    def record_instagram_media(self, media: list[FeedMedia], captured_at: float | None = None) -> int: ...
+    def record_name(self, platform: str, account_id: str, name: str, seen_at: float | None = None) -> None: ...
+    def account_id(self, platform: str, name: str) -> str | None: ...
+    def names(self, platform: str, account_id: str) -> list[AccountName]: ...
+    def resolve_entity(self, entity: str) -> str: ...
+    def history(self, entity: str, metric: str, since: float | None = None, until: float | None = None) -> list[Snapshot]: ...
+    def growth_report(self, entity: str, metric: str, as_of: float | None = None, platform_size: float | None = None) -> GrowthReport | None: ...
+    def latest(self, entity: str) -> list[Snapshot]: ...
+    def entities(self, kind: str | None = None) -> list[str]: ...
+    @property
+    def schema_version(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class Snapshot:
+    """One metric value of one entity at one point in time. `captured_at` is in Unix seconds."""
+    def __init__(self, *, entity: str, metric: str, captured_at: float, value: float) -> None: ...
+    @property
+    def entity(self) -> str: ...
+    @property
+    def metric(self) -> str: ...
+    @property
+    def captured_at(self) -> float: ...
+    @property
+    def value(self) -> float: ...
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> Snapshot: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> Snapshot: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+class AccountName:
+    """A vanity name or username an account was seen with, and when."""
+    def __init__(self, *, platform: str, account_id: str, name: str, first_seen: float, last_seen: float) -> None: ...
+    @property
+    def platform(self) -> str:
+        """`facebook` or `instagram`."""
+    @property
+    def account_id(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def first_seen(self) -> float: ...
+    @property
+    def last_seen(self) -> float: ...
+    def __hash__(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def to_json(self, indent: int | None = None) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> AccountName: ...
+    @staticmethod
+    def from_dict(dict: dict[str, Any]) -> AccountName: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __repr__(self) -> str: ...
+    def _repr_html_(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+
+def entity(kind: str, id: str) -> str:
+    """`facebook_page:<id>` style key for use with `history` and `latest`."""
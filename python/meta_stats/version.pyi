@@ -0,0 +1,41 @@
+# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.
+
+from typing import Any
+
+
+class GraphVersion:
+    """A validated Graph API version such as `v22.0`."""
+    def __init__(self, version: str) -> None:
+        """Same as `parse`."""
+    @staticmethod
+    def parse(version: str) -> GraphVersion: ...
+    @property
+    def major(self) -> int: ...
+    @property
+    def minor(self) -> int: ...
+    @property
+    def released(self) -> str | None:
+        """Release date as `YYYY-MM-DD`, if the version is in the release table."""
+    @property
+    def expires(self) -> str | None:
+        """Announced sunset date as `YYYY-MM-DD`."""
+    @property
+    def days_until_expiry(self) -> int | None: ...
+    @property
+    def is_retired(self) -> bool: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool:
+        """Versions order by major, then minor number."""
+    def __ne__(self, other: object) -> bool: ...
+    def __lt__(self, other: GraphVersion) -> bool: ...
+    def __le__(self, other: GraphVersion) -> bool: ...
+    def __gt__(self, other: GraphVersion) -> bool: ...
+    def __ge__(self, other: GraphVersion) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[str]]:
+        """Pickles as a call to `parse` with the string form."""
+
+
+def supported_graph_versions() -> list[GraphVersion]:
+    """Every version in the release table that has not been retired, oldest first."""
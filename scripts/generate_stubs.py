@@ -0,0 +1,580 @@
+#!/usr/bin/env python3
+"""Generates the `python/meta_stats/*.pyi` type stubs from the pyo3 bindings in `src/`.
+
+Run it after changing a `#[pyclass]`, a `#[pymethods]` block, a `#[pyfunction]` or the module
+registrations in `src/lib.rs`. With `--check` it writes nothing and exits with 1 when the stubs
+are out of date.
+"""
+
+import re
+import sys
+from dataclasses import dataclass, field, replace
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+SRC = ROOT / "src"
+STUBS = ROOT / "python" / "meta_stats"
+HEADER = "# Generated by scripts/generate_stubs.py from the bindings in src/. Do not edit.\n"
+
+# Rust types with no pyclass or enum to read the Python type from.
+PRIMITIVES = {
+    "String": "str",
+    "str": "str",
+    "bool": "bool",
+    "f32": "float",
+    "f64": "float",
+    "PyObject": "Any",
+    "PyAny": "Any",
+    "PyDict": "dict[str, Any]",
+    "MetaStatsError": "dict[str, str]",
+    "DateTime": "datetime",
+    "TimeArg": "datetime",
+}
+INTEGERS = {"i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "usize", "isize"}
+
+# Methods returning a bare `PyObject` whose Python type is known.
+RETURNS = {
+    ("BulkFetchIterator", "__next__"): "BulkPageResult | BulkProfileResult",
+    ("BulkFetchIterator", "write_dataset"): "tuple[list[DatasetPart], list[BulkPageResult | BulkProfileResult]]",
+}
+
+# Methods added to each model by the `model_methods!` macro in `src/protocol.rs`.
+MODEL_METHODS = """\
+    #[new]
+    #[pyo3(signature = (**fields))]
+    fn py_new(py: Python<'_>, fields: Option<&PyDict>) -> PyResult<Self> {
+    }
+
+    fn to_dict(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
+    }
+
+    #[pyo3(signature = (indent=None))]
+    fn to_json(&self, indent: Option<usize>) -> PyResult<String> {
+    }
+
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+    }
+
+    #[staticmethod]
+    fn from_dict(py: Python<'_>, dict: &PyDict) -> PyResult<Self> {
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (String,))> {
+    }
+
+    fn __repr__(&self) -> String {
+    }
+
+    fn _repr_html_(&self) -> PyResult<String> {
+    }
+
+    fn __richcmp__(&self, other: &Self, op: pyo3::pyclass::CompareOp, py: Python<'_>) -> PyObject {
+    }
+"""
+
+# Added by `model_methods!(frozen X)` on top of `MODEL_METHODS`.
+FROZEN_METHODS = """\
+    fn __hash__(&self) -> u64 {
+    }
+"""
+
+# Methods added to each `Export` type by the `export_methods!` macro in `src/export.rs`.
+EXPORT_METHODS = """\
+    /// The rows as a `pyarrow.RecordBatch`.
+    fn to_arrow(&self, py: Python<'_>) -> PyResult<PyObject> {
+    }
+
+    fn to_pandas(&self, py: Python<'_>) -> PyResult<PyObject> {
+    }
+
+    fn to_polars(&self, py: Python<'_>) -> PyResult<PyObject> {
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__(&self, py: Python<'_>, requested_schema: Option<PyObject>) -> PyResult<(PyObject, PyObject)> {
+    }
+"""
+
+
+@dataclass
+class Param:
+    name: str
+    rust: str
+    default: str | None = None
+    kind: str = ""
+
+
+@dataclass
+class Function:
+    name: str
+    params: list[Param]
+    rust_return: str
+    docs: list[str]
+    decorator: str = ""
+    body: str = ""
+
+
+@dataclass
+class Field:
+    name: str
+    rust: str
+    docs: list[str]
+    readable: bool
+    required: bool
+
+
+@dataclass
+class Class:
+    name: str
+    docs: list[str]
+    fields: list[Field] = field(default_factory=list)
+    methods: list[Function] = field(default_factory=list)
+
+
+@dataclass
+class Source:
+    classes: dict[str, Class] = field(default_factory=dict)
+    functions: dict[str, Function] = field(default_factory=dict)
+    # Non-pyclass types Python sees: `FromPyObject` enums and serde string enums.
+    aliases: dict[str, str] = field(default_factory=dict)
+
+
+def split_top_level(text):
+    parts, depth, current = [], 0, ""
+    for char in text:
+        if char in "<([":
+            depth += 1
+        elif char in ">)]":
+            depth -= 1
+        if char == "," and depth == 0:
+            parts.append(current.strip())
+            current = ""
+        else:
+            current += char
+    if current.strip():
+        parts.append(current.strip())
+    return parts
+
+
+def attribute(attrs, name):
+    """The argument of `#[name(...)]` or `#[pyo3(name ...)]`, `""` when bare, `None` when absent."""
+    for attr in attrs:
+        match = re.fullmatch(rf"#\[{name}(?:\((.*)\))?\]", attr)
+        if match:
+            return match.group(1) or ""
+        match = re.fullmatch(r"#\[pyo3\((.*)\)\]", attr)
+        if match:
+            for option in split_top_level(match.group(1)):
+                key, _, value = option.partition("=")
+                if key.strip() == name:
+                    return value.strip()
+    return None
+
+
+def parse_signature(text, attrs, docs, class_name=None):
+    match = re.search(r"fn (\w+)(?:<[^(]*>)?\((.*)\)\s*(?:->\s*(.*?))?\s*(?:where .*)?\{$", text, re.S)
+    rust_name, params_text, rust_return = match.group(1), match.group(2), match.group(3) or "()"
+
+    params = []
+    for param in split_top_level(params_text):
+        if param in ("&self", "&mut self", "self") or param.startswith(("slf:", "mut slf:")):
+            continue
+        name, _, rust = param.partition(":")
+        name, rust = name.strip().removeprefix("mut "), rust.strip()
+        if rust.startswith("Python"):
+            continue
+        params.append(Param(name, rust))
+
+    signature = attribute(attrs, "signature")
+    if signature is not None:
+        by_name = {param.name: param for param in params}
+        keyword_only = False
+        for option in split_top_level(signature.strip()[1:-1]):
+            name, _, default = option.partition("=")
+            name = name.strip()
+            if name.startswith("**"):
+                by_name[name[2:]].kind = "**"
+            elif name.startswith("*"):
+                if name[1:] in by_name:
+                    by_name[name[1:]].kind = "*"
+                keyword_only = True
+            else:
+                if keyword_only:
+                    by_name[name].kind = "keyword"
+                if default:
+                    by_name[name].default = default.strip()
+    else:
+        for param in reversed(params):
+            if not param.rust.startswith("Option<"):
+                break
+            param.default = "None"
+
+    name = attribute(attrs, "name")
+    getter = attribute(attrs, "getter")
+    decorator = ""
+    if getter is not None:
+        decorator = "property"
+        name = getter or (rust_name.removeprefix("get_"))
+    elif attribute(attrs, "staticmethod") is not None:
+        decorator = "staticmethod"
+    elif attribute(attrs, "new") is not None:
+        name = "__init__"
+    name = (name or rust_name).strip('"')
+    if class_name and (class_name, name) in RETURNS:
+        rust_return = "!" + RETURNS[(class_name, name)]
+    return Function(name, params, rust_return, docs, decorator)
+
+
+def read_block(lines, start, indent):
+    """Lines after `start` up to the closing brace at `indent`."""
+    end = start + 1
+    while not lines[end].startswith(" " * indent + "}") or lines[end].startswith(" " * (indent + 1)):
+        end += 1
+    return lines[start + 1:end], end
+
+
+def read_signature(lines, start):
+    end = start
+    while not lines[end].rstrip().endswith("{"):
+        end += 1
+    return " ".join(line.strip() for line in lines[start:end + 1]), end
+
+
+def parse_methods(lines, class_name):
+    methods, attrs, docs = [], [], []
+    i = 0
+    while i < len(lines):
+        line = lines[i]
+        stripped = line.strip()
+        if line.startswith("    ") and not line.startswith("     "):
+            if stripped.startswith("///"):
+                docs.append(stripped[3:].strip())
+            elif stripped.startswith("#["):
+                attrs.append(stripped)
+            elif re.match(r"(pub(\(crate\))? )?fn ", stripped):
+                text, i = read_signature(lines, i)
+                body, i = read_block(lines, i, 4)
+                methods.append(replace(parse_signature(text, attrs, docs, class_name), body="\n".join(body)))
+                attrs, docs = [], []
+            else:
+                attrs, docs = [], []
+        i += 1
+    return methods
+
+
+def parse_fields(lines):
+    fields, attrs, docs = [], [], []
+    for line in lines:
+        stripped = line.strip()
+        if stripped.startswith("///"):
+            docs.append(stripped[3:].strip())
+        elif stripped.startswith("#["):
+            attrs.append(stripped)
+        elif ":" in stripped:
+            name, _, rust = stripped.partition(":")
+            name = name.strip().removeprefix("pub ").removeprefix("pub(crate) ").removeprefix("r#")
+            serde = [option for attr in attrs if attr.startswith("#[serde(") for option in split_top_level(attr[8:-2])]
+            if "skip" not in serde:
+                rust = rust.strip().rstrip(",")
+                required = not rust.startswith("Option<") and not any(option.startswith("default") for option in serde)
+                fields.append(Field(name, rust, docs, "#[pyo3(get)]" in attrs, required))
+            attrs, docs = [], []
+    return fields
+
+
+def parse_enum(lines, attrs):
+    """The Rust types a `FromPyObject` enum accepts, or the Python type of a serde enum of unit
+    variants."""
+    variants = [line.strip().rstrip(",") for line in lines if line.strip() and not line.strip().startswith(("//", "#["))]
+    if "#[derive(FromPyObject)]" in attrs:
+        return [re.fullmatch(r"\w+\((.*)\)", variant).group(1) for variant in variants]
+    derive = next((attr for attr in attrs if attr.startswith("#[derive(")), "")
+    if "Serialize" in derive and all(re.fullmatch(r"\w+", variant) for variant in variants):
+        rename = next((re.search(r'rename_all = "(\w+)"', attr) for attr in attrs if "rename_all" in attr), None)
+        case = rename.group(1) if rename else ""
+        names = [re.sub(r"(?<!^)([A-Z])", r"_\1", variant).lower() if case == "snake_case" else variant.lower() if case == "lowercase" else variant for variant in variants]
+        return "Literal[" + ", ".join(f'"{name}"' for name in names) + "]"
+    return None
+
+
+def parse_source(path):
+    source = Source()
+    lines = path.read_text().splitlines()
+    attrs, docs, models = [], [], []
+    i = 0
+    while i < len(lines):
+        line = lines[i]
+        if line.startswith("///"):
+            docs.append(line[3:].strip())
+        elif line.startswith("#["):
+            attrs.append(line.strip())
+        elif match := re.match(r"pub struct (\w+) \{", line):
+            body, i = read_block(lines, i, 0)
+            if any(attr.startswith("#[pyclass") for attr in attrs):
+                source.classes[match.group(1)] = Class(match.group(1), docs, parse_fields(body))
+            attrs, docs = [], []
+        elif match := re.match(r"pub struct (\w+);", line):
+            if any(attr.startswith("#[pyclass") for attr in attrs):
+                source.classes[match.group(1)] = Class(match.group(1), docs)
+            attrs, docs = [], []
+        elif match := re.match(r"pub enum (\w+) \{", line):
+            body, i = read_block(lines, i, 0)
+            alias = parse_enum(body, attrs)
+            if alias:
+                source.aliases[match.group(1)] = alias
+            attrs, docs = [], []
+        elif match := re.match(r"impl (\w+) \{", line):
+            body, i = read_block(lines, i, 0)
+            if "#[pymethods]" in attrs:
+                source.classes[match.group(1)].methods += parse_methods(body, match.group(1))
+            attrs, docs = [], []
+        elif match := re.match(r"model_methods!\((frozen )?(\w+) \{", line):
+            body, i = read_block(lines, i, 0)
+            frozen = FROZEN_METHODS.splitlines() if match.group(1) else []
+            source.classes[match.group(2)].methods += parse_methods(frozen + body, match.group(2))
+            models.append(match.group(2))
+        elif match := re.match(r"export_methods!\((frozen )?(model )?(\w+) \{", line):
+            body, i = read_block(lines, i, 0)
+            frozen = FROZEN_METHODS.splitlines() if match.group(1) else []
+            source.classes[match.group(3)].methods += parse_methods(frozen + EXPORT_METHODS.splitlines() + body, match.group(3))
+            if match.group(2):
+                models.append(match.group(3))
+        elif line.startswith("model_methods!("):
+            end = i
+            while not lines[end].rstrip().endswith(");"):
+                end += 1
+            text = " ".join(lines[i:end + 1])
+            names = split_top_level(text[text.index("(") + 1:text.rindex(")")])
+            if names[0].startswith("frozen "):
+                names[0] = names[0].removeprefix("frozen ")
+                for name in names:
+                    source.classes[name].methods += parse_methods(FROZEN_METHODS.splitlines(), name)
+            models += names
+            i = end
+        elif re.match(r"pub fn ", line) and "#[pyfunction]" in attrs:
+            text, i = read_signature(lines, i)
+            function = parse_signature(text, attrs, docs)
+            source.functions[re.search(r"fn (\w+)", text).group(1)] = function
+            attrs, docs = [], []
+        else:
+            attrs, docs = [], []
+        i += 1
+    for name in models:
+        source.classes[name].methods += parse_methods(MODEL_METHODS.splitlines(), name)
+    return source
+
+
+class Module:
+    def __init__(self, name, owners, aliases):
+        self.name = name
+        self.owners = owners
+        self.aliases = aliases
+        self.typing = set()
+        self.datetime = False
+        self.imports = {}
+
+    def python_type(self, rust, class_name):
+        rust = re.sub(r"'\w+\s*,?\s*", "", rust).replace("&mut ", "").replace("&", "").strip()
+        if rust.startswith("!"):
+            return self.names(rust[1:])
+        if rust == "()":
+            return "None"
+        if rust.startswith("(") and rust.endswith(")"):
+            items = [self.python_type(item, class_name) for item in split_top_level(rust[1:-1])]
+            return f"tuple[{', '.join(items)}]"
+        match = re.fullmatch(r"([\w:]+)(?:<(.*)>)?", rust)
+        if not match:
+            raise SystemExit(f"Cannot map Rust type {rust!r}")
+        name, args = match.group(1).split("::")[-1], split_top_level(match.group(2) or "")
+        if name in ("PyResult", "Result", "Py", "PyRef", "PyRefMut"):
+            return self.python_type(args[0], class_name)
+        if name == "Option":
+            return f"{self.python_type(args[0], class_name)} | None"
+        if name == "Iterator":
+            return self.names(f"Iterator[{self.python_type(args[0], class_name)}]")
+        if name == "Vec":
+            return f"list[{self.python_type(args[0], class_name)}]"
+        if name in ("HashMap", "BTreeMap"):
+            return f"dict[{self.python_type(args[0], class_name)}, {self.python_type(args[1], class_name)}]"
+        if name == "Self":
+            return class_name
+        if name in INTEGERS:
+            return "int"
+        if name in PRIMITIVES:
+            return self.names(PRIMITIVES[name])
+        if name in self.aliases:
+            alias = self.aliases[name]
+            if isinstance(alias, list):
+                return " | ".join(self.python_type(variant, class_name) for variant in alias)
+            return self.names(alias)
+        if name in self.owners:
+            return self.names(name)
+        raise SystemExit(f"Cannot map Rust type {rust!r}: not a registered pyclass")
+
+    def names(self, python):
+        """Records the imports a Python type expression needs."""
+        self.datetime = self.datetime or re.search(r"\bdatetime\b", python) is not None
+        for word in set(re.findall(r"\b[A-Z]\w*", python)):
+            if word in ("Any", "Iterator", "Literal"):
+                self.typing.add(word)
+            elif word in self.owners and self.owners[word] != self.name:
+                self.imports.setdefault(self.owners[word], set()).add(word)
+            elif word not in self.owners:
+                raise SystemExit(f"Cannot map type {word!r}: not a registered pyclass")
+        return python
+
+
+def docstring(docs, indent):
+    if not docs:
+        return []
+    if len(docs) == 1:
+        return [f'{indent}"""{docs[0]}"""']
+    return [f'{indent}"""{docs[0]}'] + [f"{indent}{line}" if line else "" for line in docs[1:]] + [f'{indent}"""']
+
+
+def render_parameter(module, param, class_name):
+    python = module.python_type(param.rust, class_name)
+    if param.kind == "**":
+        return f"**{param.name}: Any"
+    if param.kind == "*":
+        return f"*{param.name}: Any"
+    if param.default is None:
+        return f"{param.name}: {python}"
+    default = param.default if re.fullmatch(r"None|True|False|-?\d+(\.\d+)?|\"[^\"]*\"", param.default) else "..."
+    return f"{param.name}: {python} = {default}"
+
+
+def render_function(module, function, indent, class_name=None):
+    lines = []
+    params = [render_parameter(module, param, class_name) for param in function.params]
+    keyword = next((i for i, param in enumerate(function.params) if param.kind == "keyword"), None)
+    if keyword is not None:
+        params.insert(keyword, "*")
+    if class_name:
+        if function.decorator:
+            lines.append(f"{indent}@{function.decorator}")
+        if function.decorator == "overload":
+            module.typing.add("overload")
+        if function.decorator != "staticmethod":
+            params.insert(0, "self")
+    returns = "None" if function.name == "__init__" else module.python_type(function.rust_return, class_name)
+    if function.name == "__next__":
+        returns = returns.removesuffix(" | None")
+    head = f"{indent}def {function.name}({', '.join(params)}) -> {returns}:"
+    body = docstring(function.docs, indent + "    ")
+    return lines + ([head] + body if body else [head + " ..."])
+
+
+def is_model_constructor(method):
+    return method.name == "__init__" and [param.kind for param in method.params] == ["**"]
+
+
+def protocol_methods(cls, method):
+    """The Python signatures of a protocol method whose Rust signature is untyped or not the one
+    Python calls: `__iter__` and `__getitem__` over a list field return `PyObject`, and
+    `__richcmp__` stands in for the comparison operators."""
+    item = None
+    if match := re.search(r"protocol::(?:iter|get_item)\(py, &self\.(\w+)", method.body):
+        rust = next(f.rust for f in cls.fields if f.name == match.group(1))
+        item = re.fullmatch(r"Vec<(.*)>", rust).group(1)
+    if method.name == "__iter__" and item:
+        return [replace(method, rust_return=f"Iterator<{item}>")]
+    if method.name == "__getitem__" and item:
+        return [
+            Function("__getitem__", [Param("index", "isize")], item, method.docs, "overload"),
+            Function("__getitem__", [Param("index", "!slice")], f"Vec<{item}>", [], "overload"),
+        ]
+    if method.name == "__richcmp__":
+        operators = ["__eq__", "__ne__"]
+        if "op.matches" in method.body:
+            operators += ["__lt__", "__le__", "__gt__", "__ge__"]
+        return [
+            Function(name, [Param("other", "!object" if name in ("__eq__", "__ne__") else "Self")], "bool", [] if i else method.docs)
+            for i, name in enumerate(operators)
+        ]
+    return [method]
+
+
+def render_class(module, cls):
+    lines = [f"class {cls.name}:"] + docstring(cls.docs, "    ")
+    members = []
+    for method in cls.methods:
+        if is_model_constructor(method):
+            # Models are built from keyword arguments named after their serialized fields.
+            params = [Param(f.name, f.rust, None if f.required else "None" if f.rust.startswith("Option<") else "...") for f in cls.fields]
+            method = Function("__init__", params, "()", method.docs)
+            rendered = render_function(module, method, "    ", cls.name)
+            rendered[-1] = rendered[-1].replace("(self, ", "(self, *, ", 1)
+            members.append(rendered)
+    for f in cls.fields:
+        if f.readable:
+            members.append(render_function(module, Function(f.name, [], f.rust, f.docs, "property"), "    ", cls.name))
+    for method in cls.methods:
+        if not is_model_constructor(method):
+            members += [render_function(module, expanded, "    ", cls.name) for expanded in protocol_methods(cls, method)]
+    if not members and not cls.docs:
+        return [lines[0] + " ..."]
+    for member in members:
+        lines += member
+    return lines
+
+
+def parse_registrations():
+    """`{module: [("class" | "function", source file, rust name)]}` from `src/lib.rs`, in order."""
+    lib = (SRC / "lib.rs").read_text()
+    variables = dict(re.findall(r"let (\w+) = PyModule::new\(py, \"(\w+)\"\)", lib))
+    modules = {name: [] for name in variables.values()}
+    for variable, kind, file, name in re.findall(r"(\w+)\.add_(class|function)(?:::<|\(wrap_pyfunction!\()(\w+)::(\w+)", lib):
+        modules[variables[variable]].append((kind, file, name))
+    return modules
+
+
+def generate():
+    registrations = parse_registrations()
+    sources = {path.stem: parse_source(path) for path in sorted(SRC.glob("*.rs"))}
+    owners = {name: module for module, items in registrations.items() for kind, _, name in items if kind == "class"}
+    aliases = {name: alias for source in sources.values() for name, alias in source.aliases.items()}
+
+    stubs = {"__init__.pyi": HEADER + "\n" + "".join(f"from . import {name} as {name}\n" for name in sorted(registrations))}
+    for name, items in registrations.items():
+        module = Module(name, owners, aliases)
+        blocks = []
+        for kind, file, rust_name in items:
+            if kind == "class":
+                blocks.append(render_class(module, sources[file].classes[rust_name]))
+            else:
+                blocks.append(render_function(module, sources[file].functions[rust_name], ""))
+
+        head = [HEADER.rstrip()]
+        stdlib = (["from datetime import datetime"] if module.datetime else []) + ([f"from typing import {', '.join(sorted(module.typing))}"] if module.typing else [])
+        if stdlib:
+            head += [""] + stdlib
+        if module.imports:
+            head += [""] + [f"from meta_stats.{other} import {', '.join(sorted(names))}" for other, names in sorted(module.imports.items())]
+        stubs[f"{name}.pyi"] = "\n\n\n".join(["\n".join(head)] + ["\n".join(block) for block in blocks]) + "\n"
+    stubs["py.typed"] = ""
+    return stubs
+
+
+def main():
+    stubs = generate()
+    if "--check" in sys.argv[1:]:
+        stale = [name for name, text in stubs.items() if not (STUBS / name).exists() or (STUBS / name).read_text() != text]
+        stale += [path.name for path in STUBS.glob("*.pyi") if path.name not in stubs]
+        if stale:
+            print(f"Stubs are out of date: {', '.join(sorted(stale))}. Run scripts/generate_stubs.py.", file=sys.stderr)
+            return 1
+        return 0
+
+    STUBS.mkdir(exist_ok=True)
+    for path in STUBS.glob("*.pyi"):
+        if path.name not in stubs:
+            path.unlink()
+    for name, text in stubs.items():
+        (STUBS / name).write_text(text)
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
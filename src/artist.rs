@@ -0,0 +1,280 @@
+use crate::auth;
+use crate::config::MetaConfig;
+use crate::db::unix_now;
+use crate::error::MetaStatsError;
+use crate::facebook::{self, PageEngagement};
+use crate::handle::{FacebookPageRef, HandleArg, InstagramHandle, PageArg};
+use crate::instagram::{self, InstagramPageInfo};
+use crate::meta::ErrorCategory;
+use crate::protocol::{model_methods, Repr};
+use crate::version::GraphVersion;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::thread;
+
+/// Followers on one platform, as of `fetched_at` (Unix seconds).
+#[pyclass(frozen, module = "meta_stats.artist")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlatformReach {
+    /// `facebook` or `instagram`.
+    #[pyo3(get)]
+    platform: String,
+    /// Page id or username.
+    #[pyo3(get)]
+    account: String,
+    #[pyo3(get)]
+    followers: i64,
+    #[pyo3(get)]
+    fetched_at: f64,
+}
+
+model_methods!(frozen PlatformReach);
+
+impl Repr for PlatformReach {
+    fn repr(&self) -> String {
+        format!("PlatformReach({} {}, {} followers)", self.platform, self.account, self.followers)
+    }
+}
+
+/// Why one platform is missing from an `ArtistStats`.
+#[pyclass(frozen, module = "meta_stats.artist")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArtistFetchFailure {
+    #[pyo3(get)]
+    platform: String,
+    /// `auth`, `rate_limit`, ... for Meta errors, or `request`, `parse`, ... for local failures.
+    #[pyo3(get)]
+    category: String,
+    #[pyo3(get)]
+    message: String,
+}
+
+impl ArtistFetchFailure {
+    pub(crate) fn meta(platform: &str, category: ErrorCategory, message: &str) -> ArtistFetchFailure {
+        ArtistFetchFailure { platform: platform.to_string(), category: category.as_str().to_string(), message: message.to_string() }
+    }
+
+    pub(crate) fn local(platform: &str, error: &MetaStatsError) -> ArtistFetchFailure {
+        ArtistFetchFailure { platform: platform.to_string(), category: error.kind().to_string(), message: error.to_string() }
+    }
+
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+}
+
+model_methods!(frozen ArtistFetchFailure);
+
+impl Repr for ArtistFetchFailure {
+    fn repr(&self) -> String {
+        format!("ArtistFetchFailure({}, {}: {})", self.platform, self.category, self.message)
+    }
+}
+
+/// An artist's audience across Facebook and Instagram. Platforms that could not be fetched are
+/// listed in `failures` instead of failing the whole profile.
+#[pyclass(frozen, module = "meta_stats.artist")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtistStats {
+    #[pyo3(get)]
+    facebook_page_id: Option<String>,
+    #[pyo3(get)]
+    instagram_username: Option<String>,
+    /// Whether the Instagram account was found through the page's `instagram_business_account`.
+    #[pyo3(get)]
+    instagram_linked: bool,
+    /// Instagram display name, when the profile was fetched.
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    facebook: Option<PageEngagement>,
+    #[pyo3(get)]
+    instagram: Option<InstagramPageInfo>,
+    #[pyo3(get)]
+    platforms: Vec<PlatformReach>,
+    #[pyo3(get)]
+    failures: Vec<ArtistFetchFailure>,
+    /// When the fetch started, in Unix seconds.
+    #[pyo3(get)]
+    fetched_at: f64,
+}
+
+impl ArtistStats {
+    pub fn platforms(&self) -> &[PlatformReach] {
+        &self.platforms
+    }
+
+    pub fn failures(&self) -> &[ArtistFetchFailure] {
+        &self.failures
+    }
+
+    /// Followers summed over every platform that was fetched. Audiences overlap, so this is
+    /// an upper bound on unique reach.
+    pub fn total_reach(&self) -> i64 {
+        self.platforms.iter().map(|platform| platform.followers).sum()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+model_methods!(frozen ArtistStats {
+    #[getter(total_reach)]
+    fn py_total_reach(&self) -> i64 {
+        self.total_reach()
+    }
+
+    #[getter(is_complete)]
+    fn py_is_complete(&self) -> bool {
+        self.is_complete()
+    }
+
+    /// Some platforms were fetched and some failed.
+    #[getter]
+    fn is_partial(&self) -> bool {
+        !self.platforms.is_empty() && !self.failures.is_empty()
+    }
+
+    /// Followers on `platform`, or `None` when it was not fetched.
+    fn followers(&self, platform: &str) -> Option<i64> {
+        self.platforms.iter().find(|reach| reach.platform == platform).map(|reach| reach.followers)
+    }
+});
+
+impl Repr for ArtistStats {
+    fn repr(&self) -> String {
+        let name = self.name.as_deref().or(self.facebook_page_id.as_deref()).or(self.instagram_username.as_deref()).unwrap_or("?");
+        format!("ArtistStats({}, reach={}, {} failures)", name, self.total_reach(), self.failures.len())
+    }
+}
+
+fn fetch_facebook(access_token: &str, page_id: &str, meta_version: &GraphVersion, config: &MetaConfig) -> Result<(PageEngagement, PlatformReach), ArtistFetchFailure> {
+    let result = facebook::fetch_facebook_page_followers(Some(access_token.to_string()), Some(page_id.to_string()), Some(meta_version.to_string()), config)
+        .map_err(|e| ArtistFetchFailure::local("facebook", &e))?;
+
+    match result.into_result() {
+        Ok(engagement) => {
+            let reach = PlatformReach { platform: "facebook".to_string(), account: page_id.to_string(), followers: engagement.followers_count(), fetched_at: unix_now() };
+            Ok((engagement, reach))
+        },
+        Err(error) => Err(ArtistFetchFailure::meta("facebook", error.category(), error.message())),
+    }
+}
+
+fn fetch_instagram(username: &str, config: &MetaConfig) -> Result<(InstagramPageInfo, PlatformReach), ArtistFetchFailure> {
+    let result = instagram::fetch_instagram_page_info(Some(username.to_string()), config)
+        .map_err(|e| ArtistFetchFailure::local("instagram", &e))?;
+
+    match result.into_result() {
+        Ok(page_info) => {
+            let reach = PlatformReach { platform: "instagram".to_string(), account: username.to_string(), followers: page_info.user().followers_count(), fetched_at: unix_now() };
+            Ok((page_info, reach))
+        },
+        Err(error) => Err(ArtistFetchFailure::meta("instagram", error.category(), error.message())),
+    }
+}
+
+/// Fetches an artist's Facebook page and Instagram profile in parallel. With only a page id,
+/// the Instagram account linked to the page is looked up and fetched too.
+pub fn fetch_artist_stats(facebook_page_id: Option<String>, instagram_username: Option<String>, access_token: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<ArtistStats, MetaStatsError> {
+    if facebook_page_id.is_none() && instagram_username.is_none() {
+        return Err(MetaStatsError::InvalidArgument("at least one of facebook_page_id and instagram_username must be set".to_string()));
+    }
+    let facebook_page_id = facebook_page_id.map(|page_id| FacebookPageRef::parse(&page_id).map(|page| page.to_string())).transpose()?;
+    let instagram_username = instagram_username.map(|username| InstagramHandle::parse(&username).map(|handle| handle.to_string())).transpose()?;
+
+    let fetched_at = unix_now();
+    let mut failures = Vec::new();
+    let mut instagram_username = instagram_username;
+    let mut instagram_linked = false;
+
+    // The token and version are only needed for Facebook; a failure to get a token is
+    // reported against Facebook rather than failing the Instagram half as well.
+    let facebook = match &facebook_page_id {
+        Some(page_id) => {
+            let meta_version = GraphVersion::resolve(meta_version, config)?;
+            let access_token = match access_token {
+                Some(access_token) => Ok(Ok(access_token)),
+                None => auth::fetch_meta_access_token(None, None, None, None, Some(meta_version.to_string()), config),
+            };
+            match access_token {
+                Ok(Ok(access_token)) => Some((page_id.clone(), access_token, meta_version)),
+                Ok(Err(error)) => {
+                    failures.push(ArtistFetchFailure::meta("facebook", error.category(), error.message()));
+                    None
+                },
+                Err(e) => {
+                    failures.push(ArtistFetchFailure::local("facebook", &e));
+                    None
+                },
+            }
+        },
+        None => None,
+    };
+
+    if let (None, Some((page_id, access_token, meta_version))) = (&instagram_username, &facebook) {
+        match facebook::fetch_facebook_page_links(access_token, page_id, meta_version, config) {
+            Ok(Ok(links)) => {
+                instagram_username = links.instagram_business_account().and_then(|account| account.username()).map(str::to_string);
+                instagram_linked = instagram_username.is_some();
+            },
+            Ok(Err(error)) => failures.push(ArtistFetchFailure::meta("instagram", error.category(), &format!("Could not look up the page's Instagram account: {}", error.message()))),
+            Err(e) => failures.push(ArtistFetchFailure::local("instagram", &e)),
+        }
+    }
+
+    let (facebook_outcome, instagram_outcome) = thread::scope(|scope| {
+        let facebook_handle = facebook
+            .as_ref()
+            .map(|(page_id, access_token, meta_version)| scope.spawn(move || fetch_facebook(access_token, page_id, meta_version, config)));
+        let instagram_outcome = instagram_username.as_deref().map(|username| fetch_instagram(username, config));
+        let facebook_outcome = facebook_handle.map(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|_| Err(ArtistFetchFailure { platform: "facebook".to_string(), category: "request".to_string(), message: "Facebook fetch panicked".to_string() }))
+        });
+        (facebook_outcome, instagram_outcome)
+    });
+
+    let mut platforms = Vec::new();
+    let facebook = match facebook_outcome {
+        Some(Ok((engagement, reach))) => {
+            platforms.push(reach);
+            Some(engagement)
+        },
+        Some(Err(failure)) => {
+            failures.push(failure);
+            None
+        },
+        None => None,
+    };
+    let instagram = match instagram_outcome {
+        Some(Ok((page_info, reach))) => {
+            platforms.push(reach);
+            Some(page_info)
+        },
+        Some(Err(failure)) => {
+            failures.push(failure);
+            None
+        },
+        None => None,
+    };
+
+    Ok(ArtistStats {
+        facebook_page_id,
+        instagram_username,
+        instagram_linked,
+        name: instagram.as_ref().map(|page_info| page_info.user().full_name().to_string()).filter(|name| !name.is_empty()),
+        facebook,
+        instagram,
+        platforms,
+        failures,
+        fetched_at,
+    })
+}
+
+#[pyfunction]
+pub fn get_artist_stats(facebook_page_id: Option<PageArg>, instagram_username: Option<HandleArg>, access_token: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<ArtistStats> {
+    Ok(fetch_artist_stats(facebook_page_id.map(String::from), instagram_username.map(String::from), access_token, meta_version, &MetaConfig::or_from_env(config)?)?)
+}
@@ -1,56 +1,40 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
-use std::env;
-use dotenv::dotenv;
-use pyo3::exceptions::PyValueError;
+use crate::config::MetaConfig;
+use crate::error::MetaStatsError;
+use crate::http::{self, HttpClient};
+use crate::meta::MetaError;
+use crate::version::GraphVersion;
 
 #[derive(Serialize, Deserialize)]
 pub struct AccessTokenResponse {
     access_token: String,
 }
 
-#[pyfunction]
-pub fn get_meta_client_id() -> PyResult<String> {
-    dotenv().ok();
-
-    match env::var("META_CLIENT_ID") {
-        Ok(client_id) => Ok(client_id),
-        Err(_) => Err(PyValueError::new_err("META_CLIENT_ID not set in environment")),
-    }
+pub fn meta_client_id(config: &MetaConfig) -> Result<String, MetaStatsError> {
+    config.client_id()
 }
 
-#[pyfunction]
-pub fn get_meta_client_secret() -> PyResult<String> {
-    dotenv().ok();
-
-    match env::var("META_CLIENT_SECRET") {
-        Ok(client_secret) => Ok(client_secret),
-        Err(_) => Err(PyValueError::new_err("META_CLIENT_SECRET not set in environment")),
-    }
+pub fn meta_client_secret(config: &MetaConfig) -> Result<String, MetaStatsError> {
+    config.client_secret()
 }
 
-#[pyfunction]
-pub fn get_meta_version() -> PyResult<String> {
-    dotenv().ok();
-
-    match env::var("META_VERSION") {
-        Ok(version) => Ok(version),
-        Err(_) => Err(PyValueError::new_err("META_VERSION not set in environment")),
-    }
+pub fn meta_version(config: &MetaConfig) -> Result<String, MetaStatsError> {
+    config.version()
 }
 
-#[pyfunction]
-pub fn get_meta_access_token(endpoint_url: Option<String>, client_id: Option<String>, client_secret: Option<String>, grant_type: Option<String>, meta_version: Option<String>) -> PyResult<String> {
-    dotenv().ok();
+/// Requests an app token. Meta rejecting the credentials, such as a wrong client secret, comes
+/// back as the `MetaError` it sent rather than a parse failure.
+pub fn fetch_meta_access_token(endpoint_url: Option<String>, client_id: Option<String>, client_secret: Option<String>, grant_type: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<Result<String, MetaError>, MetaStatsError> {
     let client_id = match client_id {
         Some(client_id) => client_id,
-        None => get_meta_client_id()?,
+        None => meta_client_id(config)?,
     };
 
     let client_secret = match client_secret {
         Some(client_secret) => client_secret,
-        None => get_meta_client_secret()?,
+        None => meta_client_secret(config)?,
     };
 
     let grant_type = match grant_type {
@@ -58,20 +42,11 @@ pub fn get_meta_access_token(endpoint_url: Option<String>, client_id: Option<Str
         None => "client_credentials".to_string(),
     };
 
-    let meta_version = match meta_version {
-        Some(meta_version) => meta_version,
-        None => get_meta_version()?,
-    };
-
-    let formatted_version = if meta_version.starts_with("v") {
-        meta_version
-    } else {
-        format!("v{}", meta_version)
-    };
+    let formatted_version = GraphVersion::resolve(meta_version, config)?;
 
     let endpoint_url = match endpoint_url {
         Some(endpoint_url) => endpoint_url,
-        None => format!("https://graph.facebook.com/{}/oauth/access_token", formatted_version),
+        None => format!("{}/{}/oauth/access_token", config.graph_base_url(), formatted_version),
     };
 
     let url = format!(
@@ -79,16 +54,63 @@ pub fn get_meta_access_token(endpoint_url: Option<String>, client_id: Option<Str
         endpoint_url, client_id, client_secret, grant_type
     );
 
-    let client = Client::new();
-    let res = client.get(&url).send().map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-    let raw_text = res.text().map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let client = HttpClient::new(config)?;
+    let raw_text = http::get_uncached(&client, &url, "access token")?.text;
 
+    if let Ok(error) = serde_json::from_str::<MetaError>(&raw_text) {
+        return Ok(Err(error));
+    }
 
     let response: Result<AccessTokenResponse, serde_json::Error> = serde_json::from_str(&raw_text);
 
     match response {
-        Ok(response) => Ok(response.access_token),
-        Err(e) => Err(PyValueError::new_err(e.to_string())),
+        Ok(response) => Ok(Ok(response.access_token)),
+        Err(e) => Err(MetaStatsError::Parse(e.to_string())),
     }
-}
\ No newline at end of file
+}
+
+#[pyfunction]
+pub fn get_meta_client_id(config: Option<MetaConfig>) -> PyResult<String> {
+    Ok(meta_client_id(&MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+pub fn get_meta_client_secret(config: Option<MetaConfig>) -> PyResult<String> {
+    Ok(meta_client_secret(&MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+pub fn get_meta_version(config: Option<MetaConfig>) -> PyResult<String> {
+    Ok(meta_version(&MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+pub fn get_meta_access_token(endpoint_url: Option<String>, client_id: Option<String>, client_secret: Option<String>, grant_type: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<String> {
+    match fetch_meta_access_token(endpoint_url, client_id, client_secret, grant_type, meta_version, &MetaConfig::or_from_env(config)?)? {
+        Ok(access_token) => Ok(access_token),
+        Err(error) => Err(PyValueError::new_err(format!("Could not get an access token: {}", error.message()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::TestCassette;
+    use crate::config::ConfigValues;
+
+    #[test]
+    fn fetches_an_app_token() {
+        let _cassette = TestCassette::replay("auth");
+        let config = MetaConfig::load(None, None, Some("META_STATS_TEST_".to_string()), ConfigValues::default()).unwrap();
+
+        let token = fetch_meta_access_token(None, Some("client-id".to_string()), Some("client-secret".to_string()), Some("client_credentials".to_string()), Some("22.0".to_string()), &config).unwrap().ok().unwrap();
+        assert_eq!(token, "REDACTED");
+    }
+
+    #[test]
+    fn needs_credentials() {
+        let config = MetaConfig::load(None, None, Some("META_STATS_TEST_".to_string()), ConfigValues::default()).unwrap();
+
+        assert!(matches!(fetch_meta_access_token(None, None, None, None, Some("22.0".to_string()), &config), Err(MetaStatsError::Config(_))));
+    }
+}
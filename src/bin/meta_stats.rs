@@ -0,0 +1,484 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use chrono::{NaiveDate, NaiveTime};
+use meta_stats::error::MetaStatsError;
+use meta_stats::config::{ConfigValues, MetaConfig};
+use meta_stats::meta::{ErrorCategory, MetaError, MetaInstagramError};
+use meta_stats::dataset::{DatasetFormat, DatasetWriter};
+use meta_stats::{auth, bulk, facebook, instagram};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const EXIT_FAILURE: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+const EXIT_CONFIG: u8 = 3;
+const EXIT_AUTH: u8 = 4;
+const EXIT_PERMISSION: u8 = 5;
+const EXIT_RATE_LIMIT: u8 = 6;
+const EXIT_NOT_FOUND: u8 = 7;
+const EXIT_INVALID_REQUEST: u8 = 8;
+const EXIT_TRANSIENT: u8 = 9;
+const EXIT_NETWORK: u8 = 10;
+
+const MAX_TABLE_CELL_WIDTH: usize = 60;
+
+/// Query Facebook pages and Instagram profiles from the command line.
+///
+/// Credentials are read from a profile in the --config file, META_* variables in the
+/// environment or a .env file, and the flags below, with later sources taking precedence.
+///
+/// The exit code reflects the category of any Meta error: 3 configuration, 4 auth,
+/// 5 permission, 6 rate limit, 7 not found, 8 invalid request, 9 transient, 10 network.
+#[derive(Parser)]
+#[command(name = "meta-stats", version)]
+struct Cli {
+    /// TOML config file with named profiles. Defaults to META_CONFIG.
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Profile to use from the config file. Defaults to META_PROFILE, then "default".
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Access token to use instead of requesting an app token.
+    #[arg(long, global = true)]
+    token: Option<String>,
+
+    /// Graph API version, e.g. v22.0. Defaults to META_VERSION.
+    #[arg(long = "api-version", global = true)]
+    api_version: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+
+    /// Send Graph API requests to this host instead, e.g. a proxy or mock server.
+    #[arg(long, global = true)]
+    graph_base_url: Option<String>,
+
+    /// Send Instagram requests to this host instead.
+    #[arg(long, global = true)]
+    instagram_base_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
+    Table,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print an app access token.
+    Token,
+    /// Facebook page lookups.
+    Page {
+        #[command(subcommand)]
+        command: PageCommand,
+    },
+    /// Instagram profile lookups.
+    Ig {
+        #[command(subcommand)]
+        command: IgCommand,
+    },
+    /// Fetch pages and profiles in parallel and append them to a partitioned dataset.
+    Export(ExportArgs),
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// Dataset root directory, e.g. a mounted data lake path.
+    dir: String,
+    /// Facebook page to export. Repeat for several pages.
+    #[arg(long = "page")]
+    pages: Vec<String>,
+    /// Instagram username to export. Repeat for several profiles.
+    #[arg(long = "ig")]
+    profiles: Vec<String>,
+    /// Also export the latest posts of every page.
+    #[arg(long)]
+    posts: bool,
+    /// parquet or csv.
+    #[arg(long, default_value = "parquet")]
+    dataset_format: String,
+    /// Requests in flight at once.
+    #[arg(long)]
+    concurrency: Option<usize>,
+}
+
+#[derive(Subcommand)]
+enum PageCommand {
+    /// Page id and name.
+    Info { page_id: String },
+    /// Follower, fan and engagement counts.
+    Followers { page_id: String },
+    /// Posts with like and comment totals.
+    Posts {
+        page_id: String,
+        /// Follow pagination until every post (or every post since --since) is fetched.
+        #[arg(long)]
+        all: bool,
+        /// Only include posts created on or after this date (YYYY-MM-DD).
+        #[arg(long)]
+        since: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum IgCommand {
+    /// Profile details and follower count.
+    Profile { username: String },
+    /// Recent media with like and comment counts.
+    Media { username: String },
+}
+
+enum Failure {
+    Local(MetaStatsError),
+    Meta { category: ErrorCategory, message: String },
+    /// Some items of an export failed; the rest were written and are listed in `records`.
+    Partial { records: Vec<Value>, message: String },
+}
+
+impl From<MetaStatsError> for Failure {
+    fn from(error: MetaStatsError) -> Self {
+        Failure::Local(error)
+    }
+}
+
+impl From<MetaError> for Failure {
+    fn from(error: MetaError) -> Self {
+        Failure::Meta {
+            category: error.category(),
+            message: format!("Meta error {}: {}", error.code(), error.message()),
+        }
+    }
+}
+
+impl From<MetaInstagramError> for Failure {
+    fn from(error: MetaInstagramError) -> Self {
+        Failure::Meta {
+            category: error.category(),
+            message: format!("Instagram error: {}", error.message()),
+        }
+    }
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Local(MetaStatsError::Config(_)) => EXIT_CONFIG,
+            Failure::Local(MetaStatsError::InvalidArgument(_)) => EXIT_USAGE,
+            Failure::Local(MetaStatsError::Request(_)) => EXIT_NETWORK,
+            Failure::Local(MetaStatsError::Parse(_) | MetaStatsError::Io(_)) => EXIT_FAILURE,
+            Failure::Partial { .. } => EXIT_FAILURE,
+            Failure::Meta { category, .. } => match category {
+                ErrorCategory::Auth => EXIT_AUTH,
+                ErrorCategory::Permission => EXIT_PERMISSION,
+                ErrorCategory::RateLimit => EXIT_RATE_LIMIT,
+                ErrorCategory::NotFound => EXIT_NOT_FOUND,
+                ErrorCategory::InvalidRequest => EXIT_INVALID_REQUEST,
+                ErrorCategory::Transient => EXIT_TRANSIENT,
+                ErrorCategory::Unknown => EXIT_FAILURE,
+            },
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Failure::Local(error) => error.to_string(),
+            Failure::Meta { category, message } => format!("{} ({})", message, category.as_str()),
+            Failure::Partial { message, .. } => message.clone(),
+        }
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn token(cli: &Cli, config: &MetaConfig) -> Result<String, Failure> {
+    match &cli.token {
+        Some(token) => Ok(token.clone()),
+        None => Ok(auth::fetch_meta_access_token(None, None, None, None, cli.api_version.clone(), config)??),
+    }
+}
+
+fn page_posts(cli: &Cli, config: &MetaConfig, page_id: &str, all: bool, since: Option<&str>) -> Result<Vec<Value>, Failure> {
+    let since = since
+        .map(|since| {
+            NaiveDate::parse_from_str(since, "%Y-%m-%d")
+                .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+                .map_err(|_| MetaStatsError::InvalidArgument(format!("--since must be a date as YYYY-MM-DD, got '{}'", since)))
+        })
+        .transpose()?;
+    let access_token = token(cli, config)?;
+    let mut posts_info = facebook::fetch_facebook_page_posts_with_summary(Some(access_token), Some(page_id.to_string()), cli.api_version.clone(), None, since, None, config)?.into_result()?;
+    let mut posts = Vec::new();
+
+    loop {
+        let next = posts_info.next_url().map(str::to_string);
+        posts.extend(posts_info.into_data());
+
+        match next {
+            Some(next) if all => {
+                let raw_text = facebook::fetch_facebook_next_results(next, config)?;
+                posts_info = facebook::parse_basic_posts_info(raw_text)?.into_result()?;
+            },
+            _ => break,
+        }
+    }
+
+    Ok(posts
+        .iter()
+        .map(|post| {
+            let post = to_value(post);
+            json!({
+                "id": post["id"],
+                "created_time": post["created_time"],
+                "message": post["message"],
+                "story": post["story"],
+                "likes": post["likes"]["summary"]["total_count"],
+                "comments": post["comments"]["summary"]["total_count"],
+            })
+        })
+        .collect())
+}
+
+/// Bulk-fetches the pages and profiles into the dataset, plus each page's first page of posts
+/// with `--posts`. Items that fail are reported on stderr and the rest are still written.
+fn export(cli: &Cli, config: &MetaConfig, args: &ExportArgs) -> Result<Vec<Value>, Failure> {
+    let ExportArgs { dir, pages, profiles, posts, dataset_format, concurrency } = args;
+    let writer = DatasetWriter::open(dir, DatasetFormat::parse(dataset_format)?)?;
+    let access_token = if pages.is_empty() { None } else { Some(token(cli, config)?) };
+
+    let mut items: Vec<bulk::BulkItem> = Vec::new();
+    if !pages.is_empty() {
+        items.extend(bulk::start_facebook_pages(pages.clone(), None, *concurrency, access_token.clone(), cli.api_version.clone(), config)?);
+    }
+    if !profiles.is_empty() {
+        items.extend(bulk::start_instagram_profiles(profiles.clone(), *concurrency, config)?);
+    }
+
+    let total = items.len();
+    let exported: Vec<String> = items.iter().filter(|item| item.error().is_none()).map(|item| item.key().to_string()).collect();
+    let (mut parts, failures) = bulk::write_dataset(items, &writer, None)?;
+    let mut errors: Vec<String> = failures
+        .iter()
+        .filter_map(|item| item.error().map(|(category, message)| format!("{}: {} ({})", item.key(), message, category)))
+        .collect();
+
+    if *posts {
+        for page_id in exported.iter().filter(|key| pages.contains(key)) {
+            let outcome = facebook::fetch_facebook_page_posts_with_summary(access_token.clone(), Some(page_id.clone()), cli.api_version.clone(), None, None, None, config)?;
+            match outcome.into_result() {
+                Ok(posts_info) => parts.extend(writer.write_posts(page_id, posts_info.data(), None)?),
+                Err(error) => errors.push(format!("{} posts: {} ({})", page_id, error.message(), error.category().as_str())),
+            }
+        }
+    }
+
+    let records: Vec<Value> = parts.iter().map(to_value).collect();
+    if errors.is_empty() {
+        return Ok(records);
+    }
+    for error in &errors {
+        eprintln!("meta-stats: {}", error);
+    }
+    Err(Failure::Partial { records, message: format!("{} of {} items failed", errors.len(), total) })
+}
+
+fn run(cli: &Cli) -> Result<Vec<Value>, Failure> {
+    let explicit = ConfigValues {
+        version: cli.api_version.clone(),
+        graph_base_url: cli.graph_base_url.clone(),
+        instagram_base_url: cli.instagram_base_url.clone(),
+        ..ConfigValues::default()
+    };
+    let config = &MetaConfig::load(cli.config.clone(), cli.profile.clone(), None, explicit)?;
+
+    match &cli.command {
+        Command::Token => Ok(vec![json!({ "access_token": token(cli, config)? })]),
+        Command::Page { command } => match command {
+            PageCommand::Info { page_id } => {
+                let page_info = facebook::fetch_facebook_page_info(Some(token(cli, config)?), Some(page_id.clone()), cli.api_version.clone(), config)?.into_result()?;
+                Ok(vec![to_value(&page_info)])
+            },
+            PageCommand::Followers { page_id } => {
+                let engagement = facebook::fetch_facebook_page_followers(Some(token(cli, config)?), Some(page_id.clone()), cli.api_version.clone(), config)?.into_result()?;
+                Ok(vec![to_value(&engagement)])
+            },
+            PageCommand::Posts { page_id, all, since } => page_posts(cli, config, page_id, *all, since.as_deref()),
+        },
+        Command::Ig { command } => match command {
+            IgCommand::Profile { username } => {
+                let page_info = instagram::fetch_instagram_page_info(Some(username.clone()), config)?.into_result()?;
+                let user = to_value(page_info.user());
+                Ok(vec![json!({
+                    "username": username,
+                    "full_name": user["full_name"],
+                    "biography": user["biography"],
+                    "followers": user["edge_followed_by"]["count"],
+                    "media_count": user["edge_owner_to_timeline_media"]["count"],
+                    "highlight_reel_count": user["highlight_reel_count"],
+                    "category_name": user["category_name"],
+                })])
+            },
+            IgCommand::Media { username } => {
+                let page_info = instagram::fetch_instagram_page_info(Some(username.clone()), config)?.into_result()?;
+                Ok(page_info
+                    .user()
+                    .timeline_media()
+                    .nodes()
+                    .map(|node| {
+                        let node = to_value(node);
+                        json!({
+                            "id": node["id"],
+                            "shortcode": node["shortcode"],
+                            "taken_at": node["taken_at_timestamp"],
+                            "is_video": node["is_video"],
+                            "likes": node["edge_liked_by"]["count"],
+                            "comments": node["edge_media_to_comment"]["count"],
+                            "video_views": node["video_view_count"],
+                            "caption": node["edge_media_to_caption"]["edges"][0]["node"]["text"],
+                        })
+                    })
+                    .collect())
+            },
+        },
+        Command::Export(args) => export(cli, config, args),
+    }
+}
+
+/// Flattens nested objects into dotted column names; arrays are kept as compact JSON.
+fn flatten(prefix: &str, value: &Value, columns: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&name, value, columns);
+            }
+        },
+        Value::Null => columns.push((prefix.to_string(), String::new())),
+        Value::String(text) => columns.push((prefix.to_string(), text.clone())),
+        other => columns.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+fn rows(records: &[Value]) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut headers: Vec<String> = Vec::new();
+    let flattened: Vec<Vec<(String, String)>> = records
+        .iter()
+        .map(|record| {
+            let mut columns = Vec::new();
+            flatten("", record, &mut columns);
+            for (name, _) in &columns {
+                if !headers.contains(name) {
+                    headers.push(name.clone());
+                }
+            }
+            columns
+        })
+        .collect();
+
+    let rows = flattened
+        .into_iter()
+        .map(|columns| {
+            headers
+                .iter()
+                .map(|header| {
+                    columns
+                        .iter()
+                        .find(|(name, _)| name == header)
+                        .map(|(_, value)| value.clone())
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+
+    (headers, rows)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn table_cell(field: &str) -> String {
+    let field = field.replace(['\n', '\r'], " ");
+    if field.chars().count() > MAX_TABLE_CELL_WIDTH {
+        format!("{}…", field.chars().take(MAX_TABLE_CELL_WIDTH - 1).collect::<String>())
+    } else {
+        field
+    }
+}
+
+fn print_records(records: &[Value], format: Format) {
+    match format {
+        Format::Json => {
+            let output = match records {
+                [record] => serde_json::to_string_pretty(record),
+                records => serde_json::to_string_pretty(records),
+            };
+            println!("{}", output.unwrap_or_default());
+        },
+        Format::Csv => {
+            let (headers, rows) = rows(records);
+            println!("{}", headers.iter().map(|h| csv_field(h)).collect::<Vec<_>>().join(","));
+            for row in rows {
+                println!("{}", row.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(","));
+            }
+        },
+        Format::Table => {
+            let (headers, rows) = rows(records);
+            let rows: Vec<Vec<String>> = rows.iter().map(|row| row.iter().map(|f| table_cell(f)).collect()).collect();
+            let widths: Vec<usize> = headers
+                .iter()
+                .enumerate()
+                .map(|(i, header)| rows.iter().map(|row| row[i].chars().count()).chain([header.chars().count()]).max().unwrap_or(0))
+                .collect();
+
+            let line = |cells: &[String]| {
+                cells
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            };
+
+            println!("{}", line(&headers));
+            println!("{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("  "));
+            for row in &rows {
+                println!("{}", line(row));
+            }
+        },
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(&cli) {
+        Ok(records) => {
+            print_records(&records, cli.format);
+            ExitCode::SUCCESS
+        },
+        Err(failure) => {
+            if let Failure::Partial { records, .. } = &failure {
+                print_records(records, cli.format);
+            }
+            eprintln!("meta-stats: {}", failure.message());
+            ExitCode::from(failure.exit_code())
+        },
+    }
+}
@@ -0,0 +1,528 @@
+use crate::auth;
+use crate::config::MetaConfig;
+use crate::dataset::{DatasetPart, DatasetWriter};
+use crate::error::MetaStatsError;
+use crate::export::{export_methods, Export, Table, Tabular};
+use crate::facebook::{self, PageEngagement, PageEngagementResult};
+use crate::handle::{HandleArg, PageArg};
+use crate::instagram::{self, InstagramPageInfoResult, UserInfo};
+use crate::meta::ErrorCategory;
+use crate::protocol::{model_methods, Repr};
+use crate::version::GraphVersion;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_CONCURRENCY: usize = 8;
+/// Longest pause imposed on a host after repeated rate-limit errors.
+const MAX_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(300);
+/// How often a blocked `__next__` wakes up to let Python handle Ctrl-C.
+const SIGNAL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Default)]
+struct HostState {
+    in_flight: usize,
+    blocked_until: Option<Instant>,
+    consecutive_rate_limits: u32,
+}
+
+/// In-flight counts and rate-limit pauses per host, shared by every bulk fetch in the process
+/// so that two concurrent refreshes against the same app cannot double the load.
+#[derive(Default)]
+struct HostGate {
+    hosts: Mutex<HashMap<String, HostState>>,
+    changed: Condvar,
+}
+
+static HOST_GATE: OnceLock<HostGate> = OnceLock::new();
+
+fn host_gate() -> &'static HostGate {
+    HOST_GATE.get_or_init(HostGate::default)
+}
+
+struct HostPermit {
+    host: String,
+}
+
+impl HostGate {
+    /// Waits until `host` has fewer than `limit` requests in flight and is not paused.
+    fn acquire(&self, host: &str, limit: usize) -> HostPermit {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+
+        loop {
+            let state = hosts.entry(host.to_string()).or_default();
+            let now = Instant::now();
+
+            match state.blocked_until {
+                Some(until) if until > now => {
+                    hosts = self
+                        .changed
+                        .wait_timeout(hosts, until - now)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0;
+                },
+                _ if state.in_flight >= limit => {
+                    hosts = self.changed.wait(hosts).unwrap_or_else(|e| e.into_inner());
+                },
+                _ => {
+                    state.in_flight += 1;
+                    return HostPermit { host: host.to_string() };
+                },
+            }
+        }
+    }
+
+    /// Pauses every request to `host`, doubling the pause for each consecutive rate limit.
+    fn report_rate_limited(&self, host: &str, base_backoff: Duration) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let state = hosts.entry(host.to_string()).or_default();
+
+        let backoff = base_backoff
+            .saturating_mul(2u32.saturating_pow(state.consecutive_rate_limits))
+            .min(MAX_RATE_LIMIT_BACKOFF);
+        let until = Instant::now() + backoff;
+        state.blocked_until = Some(state.blocked_until.map_or(until, |current| current.max(until)));
+        state.consecutive_rate_limits = state.consecutive_rate_limits.saturating_add(1);
+    }
+
+    fn report_ok(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = hosts.get_mut(host) {
+            state.consecutive_rate_limits = 0;
+        }
+    }
+}
+
+impl Drop for HostPermit {
+    fn drop(&mut self) {
+        let gate = host_gate();
+        let mut hosts = gate.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = hosts.get_mut(&self.host) {
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+        gate.changed.notify_all();
+    }
+}
+
+/// Outcome for one Facebook page. `result` holds the page or the Meta error; `failure` is set
+/// instead when the request itself could not be made or parsed.
+#[pyclass(module = "meta_stats.bulk")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkPageResult {
+    #[pyo3(get)]
+    index: usize,
+    #[pyo3(get)]
+    page_id: String,
+    result: Option<PageEngagementResult>,
+    failure: Option<MetaStatsError>,
+}
+
+model_methods!(BulkPageResult {
+    #[getter]
+    fn is_success(&self) -> bool {
+        self.result.as_ref().map(|result| result.meta_error().is_none()).unwrap_or(false)
+    }
+
+    #[getter]
+    fn is_error(&self) -> bool {
+        !self.is_success()
+    }
+
+    #[getter]
+    fn result(&self) -> Option<PageEngagementResult> {
+        self.result.clone()
+    }
+
+    /// `auth`, `rate_limit`, ... for Meta errors, or `request`, `parse`, ... for local failures.
+    #[getter]
+    fn error_category(&self) -> Option<&'static str> {
+        match (&self.result, &self.failure) {
+            (_, Some(failure)) => Some(failure.kind()),
+            (Some(result), None) => result.meta_error().map(|error| error.category().as_str()),
+            (None, None) => None,
+        }
+    }
+
+    #[getter]
+    fn error_message(&self) -> Option<String> {
+        match (&self.result, &self.failure) {
+            (_, Some(failure)) => Some(failure.to_string()),
+            (Some(result), None) => result.meta_error().map(|error| error.message().to_string()),
+            (None, None) => None,
+        }
+    }
+});
+
+impl Repr for BulkPageResult {
+    fn repr(&self) -> String {
+        let outcome = if self.is_success() { "Success" } else { "Error" };
+        format!("BulkPageResult({}, {})", self.page_id, outcome)
+    }
+}
+
+/// Outcome for one Instagram profile, shaped like `BulkPageResult`.
+#[pyclass(module = "meta_stats.bulk")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkProfileResult {
+    #[pyo3(get)]
+    index: usize,
+    #[pyo3(get)]
+    username: String,
+    result: Option<InstagramPageInfoResult>,
+    failure: Option<MetaStatsError>,
+}
+
+model_methods!(BulkProfileResult {
+    #[getter]
+    fn is_success(&self) -> bool {
+        self.result.as_ref().map(|result| result.meta_error().is_none()).unwrap_or(false)
+    }
+
+    #[getter]
+    fn is_error(&self) -> bool {
+        !self.is_success()
+    }
+
+    #[getter]
+    fn result(&self) -> Option<InstagramPageInfoResult> {
+        self.result.clone()
+    }
+
+    #[getter]
+    fn error_category(&self) -> Option<&'static str> {
+        match (&self.result, &self.failure) {
+            (_, Some(failure)) => Some(failure.kind()),
+            (Some(result), None) => result.meta_error().map(|error| error.category().as_str()),
+            (None, None) => None,
+        }
+    }
+
+    #[getter]
+    fn error_message(&self) -> Option<String> {
+        match (&self.result, &self.failure) {
+            (_, Some(failure)) => Some(failure.to_string()),
+            (Some(result), None) => result.meta_error().map(|error| error.message().to_string()),
+            (None, None) => None,
+        }
+    }
+});
+
+impl Repr for BulkProfileResult {
+    fn repr(&self) -> String {
+        let outcome = if self.is_success() { "Success" } else { "Error" };
+        format!("BulkProfileResult({}, {})", self.username, outcome)
+    }
+}
+
+/// One row per page, with the engagement metrics null for pages that failed.
+impl Tabular for BulkPageResult {
+    fn table(rows: &[BulkPageResult]) -> Table {
+        let pages: Vec<Option<&PageEngagement>> = rows.iter().map(|row| row.result.as_ref().and_then(PageEngagementResult::page)).collect();
+        let metrics: Vec<Option<Vec<(&str, f64)>>> = pages.iter().map(|page| page.map(PageEngagement::metrics)).collect();
+
+        let mut table = Table::new()
+            .int64("index", rows.iter().map(|row| Some(row.index as i64)))
+            .utf8("page_id", rows.iter().map(|row| Some(&row.page_id)))
+            .boolean("is_success", rows.iter().map(|row| Some(row.is_success())))
+            .utf8("error_category", rows.iter().map(BulkPageResult::error_category))
+            .utf8("error_message", rows.iter().map(BulkPageResult::error_message))
+            .utf8("id", pages.iter().map(|page| page.map(PageEngagement::id)))
+            .utf8("category", pages.iter().map(|page| page.map(PageEngagement::category)));
+        for (position, (metric, _)) in PageEngagement::default().metrics().into_iter().enumerate() {
+            table = table.float64(metric, metrics.iter().map(|metrics| metrics.as_ref().map(|metrics| metrics[position].1)));
+        }
+        table
+    }
+}
+
+/// One row per profile, with the profile columns null for profiles that failed.
+impl Tabular for BulkProfileResult {
+    fn table(rows: &[BulkProfileResult]) -> Table {
+        let users: Vec<Option<&UserInfo>> = rows.iter().map(|row| row.result.as_ref().and_then(InstagramPageInfoResult::user)).collect();
+        let metrics: Vec<Option<Vec<(&str, f64)>>> = users.iter().map(|user| user.map(UserInfo::metrics)).collect();
+
+        let mut table = Table::new()
+            .int64("index", rows.iter().map(|row| Some(row.index as i64)))
+            .utf8("username", rows.iter().map(|row| Some(&row.username)))
+            .boolean("is_success", rows.iter().map(|row| Some(row.is_success())))
+            .utf8("error_category", rows.iter().map(BulkProfileResult::error_category))
+            .utf8("error_message", rows.iter().map(BulkProfileResult::error_message))
+            .utf8("user_id", users.iter().map(|user| user.map(UserInfo::id)))
+            .utf8("full_name", users.iter().map(|user| user.map(UserInfo::full_name)));
+        for (position, (metric, _)) in UserInfo::default().metrics().into_iter().enumerate() {
+            table = table.float64(metric, metrics.iter().map(|metrics| metrics.as_ref().map(|metrics| metrics[position].1)));
+        }
+        table
+    }
+}
+
+pub enum BulkItem {
+    Page(BulkPageResult),
+    Profile(BulkProfileResult),
+}
+
+impl BulkItem {
+    /// The page id or username the item was fetched for.
+    pub fn key(&self) -> &str {
+        match self {
+            BulkItem::Page(item) => &item.page_id,
+            BulkItem::Profile(item) => &item.username,
+        }
+    }
+
+    /// `(category, message)` when the item failed.
+    pub fn error(&self) -> Option<(&'static str, String)> {
+        match self {
+            BulkItem::Page(item) => item.error_category().zip(item.error_message()),
+            BulkItem::Profile(item) => item.error_category().zip(item.error_message()),
+        }
+    }
+}
+
+/// Writes the successful items to `writer`, one part file per dataset, and returns the parts
+/// written along with the items that failed.
+pub fn write_dataset(items: Vec<BulkItem>, writer: &DatasetWriter, captured_at: Option<f64>) -> Result<(Vec<DatasetPart>, Vec<BulkItem>), MetaStatsError> {
+    let (mut pages, mut profiles, mut failures) = (Vec::new(), Vec::new(), Vec::new());
+    for item in items {
+        match item {
+            BulkItem::Page(BulkPageResult { result: Some(result), failure: None, .. }) if result.meta_error().is_none() => pages.extend(result.into_result().ok()),
+            BulkItem::Profile(BulkProfileResult { result: Some(result), failure: None, .. }) if result.meta_error().is_none() => profiles.extend(result.into_result().ok()),
+            failed => failures.push(failed),
+        }
+    }
+
+    let parts = [writer.write_page_engagement(&pages, captured_at)?, writer.write_instagram_profiles(&profiles, captured_at)?];
+    Ok((parts.into_iter().flatten().collect(), failures))
+}
+
+impl IntoPy<PyObject> for BulkItem {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            BulkItem::Page(item) => item.into_py(py),
+            BulkItem::Profile(item) => item.into_py(py),
+        }
+    }
+}
+
+/// Results of a bulk fetch in completion order. Dropping it stops the workers after their
+/// current request.
+pub struct BulkFetch {
+    receiver: Receiver<BulkItem>,
+    total: usize,
+}
+
+impl Iterator for BulkFetch {
+    type Item = BulkItem;
+
+    fn next(&mut self) -> Option<BulkItem> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Runs `fetch` for every key on `concurrency` worker threads, going through the shared gate
+/// for `host`. Rate-limited items pause the host and are retried up to `max_retries` times.
+fn spawn_workers<F>(keys: Vec<String>, concurrency: Option<usize>, host: String, config: &MetaConfig, fetch: F) -> BulkFetch
+where
+    F: Fn(usize, String) -> (BulkItem, bool) + Send + Sync + 'static,
+{
+    let total = keys.len();
+    let queue: Arc<Mutex<VecDeque<(usize, String)>>> = Arc::new(Mutex::new(keys.into_iter().enumerate().collect()));
+    let fetch = Arc::new(fetch);
+    let (sender, receiver) = mpsc::channel();
+
+    let workers = concurrency.unwrap_or(DEFAULT_CONCURRENCY).clamp(1, total.max(1));
+    let limit = config.max_concurrency_per_host();
+    let max_retries = config.max_retries();
+    let backoff = config.retry_backoff();
+
+    for _ in 0..workers {
+        let queue = Arc::clone(&queue);
+        let fetch = Arc::clone(&fetch);
+        let sender = sender.clone();
+        let host = host.clone();
+
+        thread::spawn(move || loop {
+            let next = queue.lock().ok().and_then(|mut queue| queue.pop_front());
+            let (index, key) = match next {
+                Some(next) => next,
+                None => break,
+            };
+
+            let mut attempt = 0;
+            let item = loop {
+                let permit = host_gate().acquire(&host, limit);
+                let (item, rate_limited) = fetch(index, key.clone());
+                drop(permit);
+
+                if !rate_limited {
+                    host_gate().report_ok(&host);
+                    break item;
+                }
+
+                host_gate().report_rate_limited(&host, backoff);
+                if attempt >= max_retries {
+                    break item;
+                }
+                attempt += 1;
+            };
+
+            if sender.send(item).is_err() {
+                break;
+            }
+        });
+    }
+
+    BulkFetch { receiver, total }
+}
+
+/// Fetches engagement fields for many pages in parallel. The access token and version are
+/// resolved once up front rather than per page.
+pub fn start_facebook_pages(ids: Vec<String>, fields: Option<Vec<String>>, concurrency: Option<usize>, access_token: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<BulkFetch, MetaStatsError> {
+    let meta_version = GraphVersion::resolve(meta_version, config)?.to_string();
+    let access_token = match access_token {
+        Some(access_token) => Ok(access_token),
+        None => auth::fetch_meta_access_token(None, None, None, None, Some(meta_version.clone()), config)?,
+    };
+
+    if let Some(fields) = &fields {
+        facebook::page_engagement_fields(fields)?;
+    }
+
+    let host = config.graph_base_url();
+    let worker_config = config.clone();
+
+    Ok(spawn_workers(ids, concurrency, host, config, move |index, page_id| {
+        let outcome = match &access_token {
+            Ok(access_token) => facebook::fetch_facebook_page_engagement(Some(access_token.clone()), Some(page_id.clone()), Some(meta_version.clone()), fields.as_deref(), &worker_config),
+            // Meta rejected the app credentials, so every page fails with that error.
+            Err(error) => Ok(PageEngagementResult::error(error.clone())),
+        };
+
+        let rate_limited = matches!(&outcome, Ok(result) if result.meta_error().map(|e| e.category()) == Some(ErrorCategory::RateLimit));
+        let (result, failure) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(failure) => (None, Some(failure)),
+        };
+
+        (BulkItem::Page(BulkPageResult { index, page_id, result, failure }), rate_limited)
+    }))
+}
+
+/// Fetches many Instagram profiles in parallel.
+pub fn start_instagram_profiles(usernames: Vec<String>, concurrency: Option<usize>, config: &MetaConfig) -> Result<BulkFetch, MetaStatsError> {
+    let host = config.instagram_base_url();
+    let worker_config = config.clone();
+
+    Ok(spawn_workers(usernames, concurrency, host, config, move |index, username| {
+        let outcome = instagram::fetch_instagram_page_info(Some(username.clone()), &worker_config);
+
+        let rate_limited = matches!(&outcome, Ok(result) if result.meta_error().map(|e| e.category()) == Some(ErrorCategory::RateLimit));
+        let (result, failure) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(failure) => (None, Some(failure)),
+        };
+
+        (BulkItem::Profile(BulkProfileResult { index, username, result, failure }), rate_limited)
+    }))
+}
+
+/// Iterator over `BulkPageResult`s or `BulkProfileResult`s as they finish. Use each item's
+/// `index` to match it back to the input list. `to_arrow` and the other exports wait for the
+/// remaining results and include only those not yet taken from the iterator, ordered by `index`.
+#[pyclass(module = "meta_stats.bulk")]
+pub struct BulkFetchIterator {
+    fetch: Mutex<BulkFetch>,
+    #[pyo3(get)]
+    total: usize,
+}
+
+impl From<BulkFetch> for BulkFetchIterator {
+    fn from(fetch: BulkFetch) -> Self {
+        let total = fetch.total;
+        BulkFetchIterator { fetch: Mutex::new(fetch), total }
+    }
+}
+
+export_methods!(BulkFetchIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        Ok(self.next_item(py)?.map(|item| item.into_py(py)))
+    }
+
+    /// Waits for the remaining results and writes the successful ones to a `DatasetWriter`.
+    /// Returns the part files written and the results that failed.
+    fn write_dataset(&self, py: Python<'_>, writer: &DatasetWriter, captured_at: Option<f64>) -> PyResult<(Vec<DatasetPart>, Vec<PyObject>)> {
+        let (parts, failures) = write_dataset(self.drain(py)?, writer, captured_at)?;
+        Ok((parts, failures.into_iter().map(|item| item.into_py(py)).collect()))
+    }
+});
+
+impl Export for BulkFetchIterator {
+    fn table(&self) -> PyResult<Table> {
+        Python::with_gil(|py| self.drain_table(py))
+    }
+}
+
+impl BulkFetchIterator {
+    fn drain(&self, py: Python<'_>) -> PyResult<Vec<BulkItem>> {
+        let mut items = Vec::new();
+        while let Some(item) = self.next_item(py)? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    fn next_item(&self, py: Python<'_>) -> PyResult<Option<BulkItem>> {
+        loop {
+            let received = py.allow_threads(|| match self.fetch.lock() {
+                Ok(fetch) => fetch.receiver.recv_timeout(SIGNAL_CHECK_INTERVAL),
+                Err(_) => Err(RecvTimeoutError::Disconnected),
+            });
+
+            match received {
+                Ok(item) => return Ok(Some(item)),
+                Err(RecvTimeoutError::Timeout) => py.check_signals()?,
+                Err(RecvTimeoutError::Disconnected) => return Ok(None),
+            }
+        }
+    }
+
+    /// Drains the iterator into one table, ordered by `index`. A fetch only ever yields one kind
+    /// of result.
+    fn drain_table(&self, py: Python<'_>) -> PyResult<Table> {
+        let (mut pages, mut profiles) = (Vec::new(), Vec::new());
+        for item in self.drain(py)? {
+            match item {
+                BulkItem::Page(page) => pages.push(page),
+                BulkItem::Profile(profile) => profiles.push(profile),
+            }
+        }
+
+        if profiles.is_empty() {
+            pages.sort_by_key(|page| page.index);
+            Ok(BulkPageResult::table(&pages))
+        } else {
+            profiles.sort_by_key(|profile| profile.index);
+            Ok(BulkProfileResult::table(&profiles))
+        }
+    }
+}
+
+#[pyfunction]
+pub fn bulk_fetch_facebook_pages(ids: Vec<PageArg>, fields: Option<Vec<String>>, concurrency: Option<usize>, access_token: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<BulkFetchIterator> {
+    let fetch = start_facebook_pages(ids.into_iter().map(String::from).collect(), fields, concurrency, access_token, meta_version, &MetaConfig::or_from_env(config)?)?;
+    Ok(fetch.into())
+}
+
+#[pyfunction]
+pub fn bulk_fetch_instagram_profiles(usernames: Vec<HandleArg>, concurrency: Option<usize>, config: Option<MetaConfig>) -> PyResult<BulkFetchIterator> {
+    let fetch = start_instagram_profiles(usernames.into_iter().map(String::from).collect(), concurrency, &MetaConfig::or_from_env(config)?)?;
+    Ok(fetch.into())
+}
@@ -0,0 +1,257 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_TTL_SECONDS: u64 = 3600;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+    Stale,
+}
+
+impl CacheStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+            CacheStatus::Stale => "stale",
+        }
+    }
+
+    /// Combines the statuses of the several requests behind one result (e.g. paginated
+    /// edges): any stale page makes the result stale, otherwise any miss makes it a miss.
+    pub fn merge(current: Option<CacheStatus>, next: Option<CacheStatus>) -> Option<CacheStatus> {
+        match (current, next) {
+            (Some(CacheStatus::Stale), _) | (_, Some(CacheStatus::Stale)) => Some(CacheStatus::Stale),
+            (Some(CacheStatus::Miss), _) | (_, Some(CacheStatus::Miss)) => Some(CacheStatus::Miss),
+            (Some(CacheStatus::Hit), _) | (_, Some(CacheStatus::Hit)) => Some(CacheStatus::Hit),
+            (None, None) => None,
+        }
+    }
+}
+
+struct CacheConfig {
+    directory: PathBuf,
+    default_ttl: u64,
+    endpoint_ttls: HashMap<String, u64>,
+    stale_while_error: bool,
+}
+
+static CACHE: Mutex<Option<CacheConfig>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    endpoint: String,
+    stored_at: u64,
+    status: u16,
+    body: String,
+}
+
+/// A cached response found for a request, along with whether it is still within its TTL.
+pub struct CachedResponse {
+    pub fresh: bool,
+    pub status: u16,
+    pub body: String,
+}
+
+struct CacheKey {
+    key: String,
+    endpoint: String,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn sha256_hex(value: &str) -> String {
+    Sha256::digest(value.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// The Graph edge a request targets, used to look up per-endpoint TTLs: `node` for a bare
+/// object (`/v22.0/{page-id}`), otherwise the last path segment (`posts`, `web_profile_info`).
+fn endpoint_name(url: &Url) -> String {
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    let is_version = |s: &str| s.starts_with('v') && s[1..].split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    let segments: Vec<&str> = match segments.first() {
+        Some(first) if is_version(first) => segments[1..].to_vec(),
+        _ => segments,
+    };
+
+    match segments.len() {
+        0 => "root".to_string(),
+        1 => "node".to_string(),
+        _ => segments[segments.len() - 1].to_string(),
+    }
+}
+
+/// Builds the cache key from the host and port, the normalized endpoint and the query, replacing
+/// the access token with a hash of it so entries are scoped per token without the token ever
+/// being stored.
+fn cache_key(url: &str) -> Option<CacheKey> {
+    let url = Url::parse(url).ok()?;
+
+    let mut token_identity = "anonymous".to_string();
+    let mut params: Vec<(String, String)> = Vec::new();
+    for (name, value) in url.query_pairs() {
+        if name == "access_token" {
+            token_identity = sha256_hex(&value);
+        } else {
+            params.push((name.into_owned(), value.into_owned()));
+        }
+    }
+    params.sort();
+
+    let query = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let normalized = format!(
+        "{}:{}{}?{}#{}",
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or_default(),
+        url.path().trim_end_matches('/'),
+        query,
+        token_identity
+    );
+
+    Some(CacheKey {
+        key: sha256_hex(&normalized),
+        endpoint: endpoint_name(&url),
+    })
+}
+
+pub fn is_enabled() -> bool {
+    CACHE.lock().map(|cache| cache.is_some()).unwrap_or(false)
+}
+
+/// Whether a stale entry may be served when the live request fails.
+pub fn stale_while_error() -> bool {
+    CACHE
+        .lock()
+        .map(|cache| cache.as_ref().map(|c| c.stale_while_error).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+pub fn lookup(url: &str) -> Option<CachedResponse> {
+    let guard = CACHE.lock().ok()?;
+    let config = guard.as_ref()?;
+    let key = cache_key(url)?;
+
+    let raw = fs::read_to_string(config.directory.join(format!("{}.json", key.key))).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&raw).ok()?;
+
+    let ttl = config
+        .endpoint_ttls
+        .get(&key.endpoint)
+        .copied()
+        .unwrap_or(config.default_ttl);
+
+    Some(CachedResponse {
+        fresh: now().saturating_sub(entry.stored_at) < ttl,
+        status: entry.status,
+        body: entry.body,
+    })
+}
+
+pub fn store(url: &str, status: u16, body: &str) {
+    let guard = match CACHE.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    let (config, key) = match (guard.as_ref(), cache_key(url)) {
+        (Some(config), Some(key)) => (config, key),
+        _ => return,
+    };
+
+    let entry = CacheEntry {
+        endpoint: key.endpoint,
+        stored_at: now(),
+        status,
+        body: body.to_string(),
+    };
+
+    if let Ok(serialized) = serde_json::to_string(&entry) {
+        let path = config.directory.join(format!("{}.json", key.key));
+        let tmp_path = config.directory.join(format!("{}.json.tmp", key.key));
+        if fs::write(&tmp_path, serialized).is_ok() {
+            let _ = fs::rename(&tmp_path, &path);
+        }
+    }
+}
+
+#[pyfunction]
+pub fn configure_cache(directory: String, default_ttl_seconds: Option<u64>, endpoint_ttls: Option<HashMap<String, u64>>, stale_while_error: Option<bool>) -> PyResult<()> {
+    let directory = PathBuf::from(directory);
+    fs::create_dir_all(&directory)
+        .map_err(|e| PyValueError::new_err(format!("Failed to create cache directory {}: {}", directory.display(), e)))?;
+
+    let mut cache = CACHE
+        .lock()
+        .map_err(|e| PyValueError::new_err(format!("Failed to lock cache configuration: {}", e)))?;
+
+    *cache = Some(CacheConfig {
+        directory,
+        default_ttl: default_ttl_seconds.unwrap_or(DEFAULT_TTL_SECONDS),
+        endpoint_ttls: endpoint_ttls.unwrap_or_default(),
+        stale_while_error: stale_while_error.unwrap_or(false),
+    });
+
+    Ok(())
+}
+
+#[pyfunction]
+pub fn disable_cache() -> PyResult<()> {
+    let mut cache = CACHE
+        .lock()
+        .map_err(|e| PyValueError::new_err(format!("Failed to lock cache configuration: {}", e)))?;
+
+    *cache = None;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn clear_cache() -> PyResult<usize> {
+    let cache = CACHE
+        .lock()
+        .map_err(|e| PyValueError::new_err(format!("Failed to lock cache configuration: {}", e)))?;
+
+    let config = match cache.as_ref() {
+        Some(config) => config,
+        None => return Ok(0),
+    };
+
+    let entries = fs::read_dir(&config.directory)
+        .map_err(|e| PyValueError::new_err(format!("Failed to read cache directory {}: {}", config.directory.display(), e)))?;
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().map(|ext| ext == "json").unwrap_or(false) && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
@@ -0,0 +1,310 @@
+use crate::error::MetaStatsError;
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Query parameters and JSON keys whose values are replaced before anything is written to a cassette.
+const SCRUBBED_KEYS: [&str; 3] = ["access_token", "client_secret", "client_id"];
+const REDACTED: &str = "REDACTED";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CassetteMode {
+    Record,
+    Replay,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Interaction {
+    method: String,
+    url: String,
+    status: u16,
+    body: String,
+    #[serde(skip)]
+    played: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    file: CassetteFile,
+}
+
+static CASSETTE: Mutex<Option<Cassette>> = Mutex::new(None);
+
+/// Outcome of consulting the active cassette for a request.
+pub enum Playback {
+    /// No cassette is loaded, or it is recording: the request goes to the network.
+    Live { record: bool },
+    Replayed { status: u16, body: String },
+}
+
+/// Replaces the value of every scrubbed key in `text`, whether it appears as a query
+/// parameter (`access_token=...`) or as a JSON string (`"access_token": "..."`, with any
+/// whitespace around the colon).
+pub fn scrub(text: &str) -> String {
+    let mut scrubbed = text.to_string();
+
+    for key in SCRUBBED_KEYS {
+        for pattern in [format!("{}=", key), format!("\"{}\"", key)] {
+            let json = pattern.starts_with('"');
+            let mut output = String::with_capacity(scrubbed.len());
+            let mut rest = scrubbed.as_str();
+
+            while let Some(index) = rest.find(&pattern) {
+                let preceded_by_name_char = rest[..index]
+                    .chars()
+                    .next_back()
+                    .map(|c| c.is_ascii_alphanumeric() || c == '_')
+                    .unwrap_or(false);
+
+                let value_start = match json {
+                    true => json_value_start(&rest[index + pattern.len()..]).map(|start| index + pattern.len() + start),
+                    false => Some(index + pattern.len()).filter(|_| !preceded_by_name_char),
+                };
+                let Some(value_start) = value_start else {
+                    output.push_str(&rest[..index + pattern.len()]);
+                    rest = &rest[index + pattern.len()..];
+                    continue;
+                };
+                output.push_str(&rest[..value_start]);
+                rest = &rest[value_start..];
+
+                let value_end = match json {
+                    true => json_string_end(rest),
+                    false => rest
+                        .find(|c: char| matches!(c, '&' | '"' | '\\' | '#') || c.is_whitespace())
+                        .unwrap_or(rest.len()),
+                };
+                output.push_str(REDACTED);
+                rest = &rest[value_end..];
+            }
+
+            output.push_str(rest);
+            scrubbed = output;
+        }
+    }
+
+    scrubbed
+}
+
+/// Where the string value starts in what follows a JSON key, if the key is followed by a
+/// colon and a string.
+fn json_value_start(after_key: &str) -> Option<usize> {
+    let colon = after_key.len() - after_key.trim_start().len();
+    let after_colon = after_key[colon..].strip_prefix(':')?;
+    let quote = after_colon.len() - after_colon.trim_start().len();
+    after_colon[quote..].starts_with('"').then_some(colon + 1 + quote + 1)
+}
+
+/// The index of the quote closing a JSON string, skipping escaped characters.
+fn json_string_end(value: &str) -> usize {
+    let mut chars = value.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            },
+            '"' => return index,
+            _ => {},
+        }
+    }
+    value.len()
+}
+
+/// Looks up the recorded response for `url` when replaying. Interactions are played back in
+/// the order they were recorded; once every match has been played the last one is repeated.
+pub fn playback(url: &str) -> Result<Playback, MetaStatsError> {
+    let mut guard = CASSETTE
+        .lock()
+        .map_err(|e| MetaStatsError::Io(format!("Failed to lock cassette: {}", e)))?;
+
+    let cassette = match guard.as_mut() {
+        Some(cassette) => cassette,
+        None => return Ok(Playback::Live { record: false }),
+    };
+
+    if cassette.mode == CassetteMode::Record {
+        return Ok(Playback::Live { record: true });
+    }
+
+    let url = scrub(url);
+    let interactions = &mut cassette.file.interactions;
+    let index = interactions
+        .iter()
+        .position(|i| i.method == "GET" && i.url == url && !i.played)
+        .or_else(|| interactions.iter().rposition(|i| i.method == "GET" && i.url == url));
+
+    match index {
+        Some(index) => {
+            let interaction = &mut interactions[index];
+            interaction.played = true;
+            Ok(Playback::Replayed {
+                status: interaction.status,
+                body: interaction.body.clone(),
+            })
+        },
+        None => Err(MetaStatsError::Request(format!(
+            "No recorded interaction for GET {} in cassette {}",
+            url,
+            cassette.path.display()
+        ))),
+    }
+}
+
+/// Appends a live response to the recording cassette and saves it, scrubbing secrets from
+/// both the URL and the body.
+pub fn record(url: &str, status: u16, body: &str) -> Result<(), MetaStatsError> {
+    let mut guard = CASSETTE
+        .lock()
+        .map_err(|e| MetaStatsError::Io(format!("Failed to lock cassette: {}", e)))?;
+
+    if let Some(cassette) = guard.as_mut() {
+        cassette.file.interactions.push(Interaction {
+            method: "GET".to_string(),
+            url: scrub(url),
+            status,
+            body: scrub(body),
+            played: false,
+        });
+
+        let serialized = serde_json::to_string_pretty(&cassette.file)
+            .map_err(|e| MetaStatsError::Io(format!("Failed to serialize cassette: {}", e)))?;
+        fs::write(&cassette.path, serialized)
+            .map_err(|e| MetaStatsError::Io(format!("Failed to write cassette {}: {}", cassette.path.display(), e)))?;
+    }
+
+    Ok(())
+}
+
+/// Loads the cassette at `path`: replayed from the file, or recorded into it, replacing any
+/// cassette already loaded.
+pub fn load(path: PathBuf, record: bool) -> Result<(), MetaStatsError> {
+    let (mode, file) = match record {
+        false => {
+            let raw = fs::read_to_string(&path)
+                .map_err(|e| MetaStatsError::Io(format!("Failed to read cassette {}: {}", path.display(), e)))?;
+            let file = serde_json::from_str::<CassetteFile>(&raw)
+                .map_err(|e| MetaStatsError::Io(format!("Failed to parse cassette {}: {}", path.display(), e)))?;
+            (CassetteMode::Replay, file)
+        },
+        true => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .map_err(|e| MetaStatsError::Io(format!("Failed to create cassette directory {}: {}", parent.display(), e)))?;
+            }
+            (CassetteMode::Record, CassetteFile::default())
+        },
+    };
+
+    let mut cassette = CASSETTE
+        .lock()
+        .map_err(|e| MetaStatsError::Io(format!("Failed to lock cassette: {}", e)))?;
+
+    *cassette = Some(Cassette { path, mode, file });
+    Ok(())
+}
+
+pub fn eject() -> Result<(), MetaStatsError> {
+    let mut cassette = CASSETTE
+        .lock()
+        .map_err(|e| MetaStatsError::Io(format!("Failed to lock cassette: {}", e)))?;
+
+    *cassette = None;
+    Ok(())
+}
+
+#[pyfunction]
+pub fn use_cassette(path: String, mode: Option<String>) -> PyResult<()> {
+    let record = match mode.as_deref() {
+        None | Some("replay") => false,
+        Some("record") => true,
+        Some(other) => Err(PyValueError::new_err(format!("mode must be either 'record' or 'replay', got '{}'", other)))?,
+    };
+
+    Ok(load(PathBuf::from(path), record)?)
+}
+
+#[pyfunction]
+pub fn eject_cassette() -> PyResult<()> {
+    Ok(eject()?)
+}
+
+/// A cassette loaded by a Rust test. The cassette is global, so the guard also holds a lock
+/// that keeps tests using one from running at the same time, and ejects it when dropped.
+#[cfg(test)]
+pub struct TestCassette {
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+impl TestCassette {
+    /// Replays `test/cassettes/<name>.json`.
+    pub fn replay(name: &str) -> TestCassette {
+        TestCassette::load(PathBuf::from(format!("{}/test/cassettes/{}.json", env!("CARGO_MANIFEST_DIR"), name)), false)
+    }
+
+    pub fn load(path: PathBuf, record: bool) -> TestCassette {
+        let cassette = TestCassette::live();
+        if let Err(e) = load(path, record) {
+            panic!("{}", e);
+        }
+        cassette
+    }
+
+    /// No cassette, for a test whose requests go to the network (or a mock server) and would
+    /// otherwise be intercepted by another test's cassette.
+    pub fn live() -> TestCassette {
+        static TEST_LOCK: Mutex<()> = Mutex::new(());
+        TestCassette { _lock: TEST_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) }
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestCassette {
+    fn drop(&mut self) {
+        let _ = eject();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrubs_query_parameters() {
+        let url = "https://graph.facebook.com/v22.0/oauth/access_token?client_id=123&client_secret=shh&grant_type=client_credentials";
+        assert_eq!(
+            scrub(url),
+            "https://graph.facebook.com/v22.0/oauth/access_token?client_id=REDACTED&client_secret=REDACTED&grant_type=client_credentials"
+        );
+    }
+
+    #[test]
+    fn scrubs_json_bodies_and_paging_links() {
+        let body = r#"{"access_token":"EAAB|xyz","paging":{"next":"https:\/\/graph.facebook.com\/v22.0\/1\/posts?access_token=EAAB|xyz&limit=25"}}"#;
+        assert_eq!(
+            scrub(body),
+            r#"{"access_token":"REDACTED","paging":{"next":"https:\/\/graph.facebook.com\/v22.0\/1\/posts?access_token=REDACTED&limit=25"}}"#
+        );
+    }
+
+    #[test]
+    fn scrubs_json_with_whitespace_around_the_colon() {
+        let body = "{\n  \"access_token\" : \"EAAB|x\\\"yz\",\n  \"client_id\":\"123\", \"token_type\": \"bearer\"\n}";
+        assert_eq!(scrub(body), "{\n  \"access_token\" : \"REDACTED\",\n  \"client_id\":\"REDACTED\", \"token_type\": \"bearer\"\n}");
+    }
+
+    #[test]
+    fn leaves_similarly_named_parameters_alone() {
+        let url = "https://example.com/?page_access_token=keep&access_token=drop";
+        assert_eq!(scrub(url), "https://example.com/?page_access_token=keep&access_token=REDACTED");
+    }
+}
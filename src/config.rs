@@ -0,0 +1,360 @@
+use crate::error::MetaStatsError;
+use crate::meta;
+use crate::version::GraphVersion;
+use dotenv::dotenv;
+use pyo3::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_ENV_PREFIX: &str = "META_";
+pub const DEFAULT_PROFILE: &str = "default";
+const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+const DEFAULT_CONNECT_TIMEOUT_SECONDS: u64 = 10;
+const DEFAULT_MAX_RETRIES: u32 = 0;
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+const DEFAULT_MAX_CONCURRENCY_PER_HOST: usize = 8;
+
+/// One layer of settings. Every source (file, environment, explicit arguments) produces one
+/// and they are merged field by field.
+#[derive(Deserialize, Default, Clone)]
+pub struct ConfigValues {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub version: Option<String>,
+    pub graph_base_url: Option<String>,
+    pub instagram_base_url: Option<String>,
+    pub proxy: Option<String>,
+    pub timeout_seconds: Option<u64>,
+    pub connect_timeout_seconds: Option<u64>,
+    pub max_retries: Option<u32>,
+    pub retry_backoff_ms: Option<u64>,
+    pub max_concurrency_per_host: Option<usize>,
+}
+
+impl ConfigValues {
+    /// Fills every field that is unset here from `fallback`.
+    fn or(self, fallback: ConfigValues) -> ConfigValues {
+        ConfigValues {
+            client_id: self.client_id.or(fallback.client_id),
+            client_secret: self.client_secret.or(fallback.client_secret),
+            version: self.version.or(fallback.version),
+            graph_base_url: self.graph_base_url.or(fallback.graph_base_url),
+            instagram_base_url: self.instagram_base_url.or(fallback.instagram_base_url),
+            proxy: self.proxy.or(fallback.proxy),
+            timeout_seconds: self.timeout_seconds.or(fallback.timeout_seconds),
+            connect_timeout_seconds: self.connect_timeout_seconds.or(fallback.connect_timeout_seconds),
+            max_retries: self.max_retries.or(fallback.max_retries),
+            retry_backoff_ms: self.retry_backoff_ms.or(fallback.retry_backoff_ms),
+            max_concurrency_per_host: self.max_concurrency_per_host.or(fallback.max_concurrency_per_host),
+        }
+    }
+
+    fn from_env(prefix: &str) -> Result<ConfigValues, MetaStatsError> {
+        let string = |name: &str| env::var(format!("{}{}", prefix, name)).ok().filter(|v| !v.is_empty());
+
+        Ok(ConfigValues {
+            client_id: string("CLIENT_ID"),
+            client_secret: string("CLIENT_SECRET"),
+            version: string("VERSION"),
+            graph_base_url: string("GRAPH_BASE_URL"),
+            instagram_base_url: string("INSTAGRAM_BASE_URL"),
+            proxy: string("PROXY"),
+            timeout_seconds: parse_env(prefix, "TIMEOUT_SECONDS")?,
+            connect_timeout_seconds: parse_env(prefix, "CONNECT_TIMEOUT_SECONDS")?,
+            max_retries: parse_env(prefix, "MAX_RETRIES")?,
+            retry_backoff_ms: parse_env(prefix, "RETRY_BACKOFF_MS")?,
+            max_concurrency_per_host: parse_env(prefix, "MAX_CONCURRENCY_PER_HOST")?,
+        })
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(prefix: &str, name: &str) -> Result<Option<T>, MetaStatsError> {
+    let name = format!("{}{}", prefix, name);
+    match env::var(&name) {
+        Ok(value) if !value.is_empty() => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| MetaStatsError::Config(format!("{} must be a non-negative integer, got '{}'", name, value))),
+        _ => Ok(None),
+    }
+}
+
+/// Layout of a config file: top-level keys are shared by every profile and each
+/// `[profiles.<name>]` table overrides them.
+#[derive(Deserialize, Default)]
+struct ConfigFile {
+    #[serde(flatten)]
+    shared: ConfigValues,
+    #[serde(default)]
+    profiles: HashMap<String, ConfigValues>,
+}
+
+fn read_config_file(path: &Path, profile: &str) -> Result<ConfigValues, MetaStatsError> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| MetaStatsError::Config(format!("Failed to read config file {}: {}", path.display(), e)))?;
+    let mut file: ConfigFile = toml::from_str(&raw)
+        .map_err(|e| MetaStatsError::Config(format!("Failed to parse config file {}: {}", path.display(), e)))?;
+
+    match file.profiles.remove(profile) {
+        Some(values) => Ok(values.or(file.shared)),
+        None if profile == DEFAULT_PROFILE => Ok(file.shared),
+        None => Err(MetaStatsError::Config(format!("Profile '{}' not found in config file {}", profile, path.display()))),
+    }
+}
+
+/// Immutable settings for one Meta app: credentials, API version, hosts and HTTP behaviour.
+///
+/// Every fetch function takes an optional `config`; without one the settings are read from
+/// `META_*` environment variables (and `.env`) as before.
+#[pyclass(frozen, module = "meta_stats.config")]
+#[derive(Clone)]
+pub struct MetaConfig {
+    profile: String,
+    env_prefix: String,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    version: Option<String>,
+    graph_base_url: Option<String>,
+    instagram_base_url: Option<String>,
+    proxy: Option<String>,
+    timeout_seconds: u64,
+    connect_timeout_seconds: u64,
+    max_retries: u32,
+    retry_backoff_ms: u64,
+    max_concurrency_per_host: usize,
+}
+
+impl MetaConfig {
+    /// Builds a config from, in increasing precedence, the profile in a TOML file, environment
+    /// variables starting with `env_prefix` and `explicit` values.
+    ///
+    /// The file defaults to `<prefix>CONFIG` and the profile to `<prefix>PROFILE`, then `default`.
+    pub fn load(path: Option<PathBuf>, profile: Option<String>, env_prefix: Option<String>, explicit: ConfigValues) -> Result<MetaConfig, MetaStatsError> {
+        dotenv().ok();
+
+        let prefix = env_prefix.unwrap_or_else(|| DEFAULT_ENV_PREFIX.to_string());
+        let profile = profile
+            .or_else(|| env::var(format!("{}PROFILE", prefix)).ok().filter(|p| !p.is_empty()))
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        let path = path.or_else(|| env::var(format!("{}CONFIG", prefix)).ok().filter(|p| !p.is_empty()).map(PathBuf::from));
+
+        let file_values = match path {
+            Some(path) => read_config_file(&path, &profile)?,
+            None => ConfigValues::default(),
+        };
+
+        let values = explicit.or(ConfigValues::from_env(&prefix)?).or(file_values);
+
+        let config = MetaConfig {
+            profile,
+            env_prefix: prefix,
+            client_id: values.client_id,
+            client_secret: values.client_secret,
+            version: values.version,
+            graph_base_url: values.graph_base_url.map(|u| u.trim_end_matches('/').to_string()),
+            instagram_base_url: values.instagram_base_url.map(|u| u.trim_end_matches('/').to_string()),
+            proxy: values.proxy,
+            timeout_seconds: values.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS),
+            connect_timeout_seconds: values.connect_timeout_seconds.unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECONDS),
+            max_retries: values.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+            retry_backoff_ms: values.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS),
+            max_concurrency_per_host: values.max_concurrency_per_host.unwrap_or(DEFAULT_MAX_CONCURRENCY_PER_HOST),
+        };
+
+        if let Some(version) = &config.version {
+            GraphVersion::parse(version).map_err(|e| MetaStatsError::Config(e.to_string()))?;
+        }
+
+        if config.timeout_seconds == 0 || config.connect_timeout_seconds == 0 {
+            return Err(MetaStatsError::Config("timeouts must be at least one second".to_string()));
+        }
+
+        if config.max_concurrency_per_host == 0 {
+            return Err(MetaStatsError::Config("max_concurrency_per_host must be at least 1".to_string()));
+        }
+
+        Ok(config)
+    }
+
+    /// The configuration used when a caller does not pass one: `META_*` environment variables only.
+    pub fn from_env() -> Result<MetaConfig, MetaStatsError> {
+        MetaConfig::load(None, None, None, ConfigValues::default())
+    }
+
+    pub fn or_from_env(config: Option<MetaConfig>) -> Result<MetaConfig, MetaStatsError> {
+        match config {
+            Some(config) => Ok(config),
+            None => MetaConfig::from_env(),
+        }
+    }
+
+    fn required(&self, value: &Option<String>, name: &str) -> Result<String, MetaStatsError> {
+        value.clone().ok_or_else(|| {
+            MetaStatsError::Config(format!(
+                "{}{} not set in environment and no {} in config profile '{}'",
+                self.env_prefix,
+                name.to_uppercase(),
+                name,
+                self.profile
+            ))
+        })
+    }
+
+    pub fn client_id(&self) -> Result<String, MetaStatsError> {
+        self.required(&self.client_id, "client_id")
+    }
+
+    pub fn client_secret(&self) -> Result<String, MetaStatsError> {
+        self.required(&self.client_secret, "client_secret")
+    }
+
+    pub fn version(&self) -> Result<String, MetaStatsError> {
+        self.required(&self.version, "version")
+    }
+
+    /// The profile's Graph host, or the process-wide one from `set_base_urls`.
+    pub fn graph_base_url(&self) -> String {
+        self.graph_base_url.clone().unwrap_or_else(meta::graph_base_url)
+    }
+
+    pub fn instagram_base_url(&self) -> String {
+        self.instagram_base_url.clone().unwrap_or_else(meta::instagram_base_url)
+    }
+
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds)
+    }
+
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_seconds)
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms)
+    }
+
+    /// Upper bound on in-flight requests to one host across every bulk fetch in the process.
+    pub fn max_concurrency_per_host(&self) -> usize {
+        self.max_concurrency_per_host
+    }
+}
+
+#[pymethods]
+impl MetaConfig {
+    #[getter(profile)]
+    fn py_profile(&self) -> String {
+        self.profile.clone()
+    }
+
+    #[getter(client_id)]
+    fn py_client_id(&self) -> Option<String> {
+        self.client_id.clone()
+    }
+
+    #[getter(has_client_secret)]
+    fn py_has_client_secret(&self) -> bool {
+        self.client_secret.is_some()
+    }
+
+    #[getter(version)]
+    fn py_version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    #[getter(graph_base_url)]
+    fn py_graph_base_url(&self) -> Option<String> {
+        self.graph_base_url.clone()
+    }
+
+    #[getter(instagram_base_url)]
+    fn py_instagram_base_url(&self) -> Option<String> {
+        self.instagram_base_url.clone()
+    }
+
+    #[getter(proxy)]
+    fn py_proxy(&self) -> Option<String> {
+        self.proxy.clone()
+    }
+
+    #[getter(timeout_seconds)]
+    fn py_timeout_seconds(&self) -> u64 {
+        self.timeout_seconds
+    }
+
+    #[getter(connect_timeout_seconds)]
+    fn py_connect_timeout_seconds(&self) -> u64 {
+        self.connect_timeout_seconds
+    }
+
+    #[getter(max_retries)]
+    fn py_max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    #[getter(retry_backoff_ms)]
+    fn py_retry_backoff_ms(&self) -> u64 {
+        self.retry_backoff_ms
+    }
+
+    #[getter(max_concurrency_per_host)]
+    fn py_max_concurrency_per_host(&self) -> usize {
+        self.max_concurrency_per_host
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MetaConfig(profile={:?}, client_id={:?}, version={:?})",
+            self.profile,
+            self.client_id.as_deref().unwrap_or(""),
+            self.version.as_deref().unwrap_or("")
+        )
+    }
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub fn load_config(
+    path: Option<String>,
+    profile: Option<String>,
+    env_prefix: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    version: Option<String>,
+    graph_base_url: Option<String>,
+    instagram_base_url: Option<String>,
+    proxy: Option<String>,
+    timeout_seconds: Option<u64>,
+    connect_timeout_seconds: Option<u64>,
+    max_retries: Option<u32>,
+    retry_backoff_ms: Option<u64>,
+    max_concurrency_per_host: Option<usize>,
+) -> PyResult<MetaConfig> {
+    let explicit = ConfigValues {
+        client_id,
+        client_secret,
+        version,
+        graph_base_url,
+        instagram_base_url,
+        proxy,
+        timeout_seconds,
+        connect_timeout_seconds,
+        max_retries,
+        retry_backoff_ms,
+        max_concurrency_per_host,
+    };
+
+    Ok(MetaConfig::load(path.map(PathBuf::from), profile, env_prefix, explicit)?)
+}
@@ -0,0 +1,635 @@
+use crate::auth;
+use crate::config::MetaConfig;
+use crate::db::{self, db_error, unix_now};
+use crate::error::MetaStatsError;
+use crate::facebook::{self, PostInfo};
+use crate::handle::{FacebookPageRef, HandleArg, InstagramHandle, PageArg};
+use crate::http::HttpClient;
+use crate::instagram::{self, FeedMedia};
+use crate::meta::ErrorCategory;
+use crate::protocol::{model_methods, Repr};
+use crate::timestamp;
+use crate::version::GraphVersion;
+use pyo3::prelude::*;
+use pyo3::types::PyBool;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Schema migrations, applied in order by `db::open`. Items are keyed on the account's numeric
+/// id, which stays the same when the page or user is renamed; `target` is the name it was
+/// crawled under.
+const MIGRATIONS: [&str; 2] = ["
+CREATE TABLE IF NOT EXISTS targets (
+    platform TEXT NOT NULL,
+    target TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    user_id TEXT,
+    cursor TEXT,
+    expected_items INTEGER,
+    pages_fetched INTEGER NOT NULL DEFAULT 0,
+    items_fetched INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    PRIMARY KEY (platform, target)
+);
+CREATE TABLE IF NOT EXISTS items (
+    platform TEXT NOT NULL,
+    target TEXT NOT NULL,
+    item_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    PRIMARY KEY (platform, item_id)
+);
+CREATE INDEX IF NOT EXISTS items_by_target ON items (platform, target, created_at);
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    started_at REAL NOT NULL,
+    finished_at REAL,
+    pages_fetched INTEGER NOT NULL DEFAULT 0,
+    items_fetched INTEGER NOT NULL DEFAULT 0,
+    stopped_reason TEXT
+);
+", "
+ALTER TABLE items ADD COLUMN account_id TEXT;
+UPDATE items SET account_id = COALESCE(
+    (SELECT user_id FROM targets WHERE targets.platform = items.platform AND targets.target = items.target),
+    items.target
+);
+DROP INDEX items_by_target;
+CREATE INDEX items_by_account ON items (platform, account_id, created_at);
+"];
+
+const FACEBOOK: &str = "facebook";
+const INSTAGRAM: &str = "instagram";
+
+/// Snapshot of a crawl job. `eta_seconds` extrapolates the item rate of the current (or most
+/// recent) run over the items still expected, using the average size of finished targets
+/// where a target's total is not known up front.
+#[pyclass(module = "meta_stats.crawl")]
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CrawlProgress {
+    #[pyo3(get)]
+    targets_total: usize,
+    #[pyo3(get)]
+    targets_done: usize,
+    #[pyo3(get)]
+    targets_failed: usize,
+    #[pyo3(get)]
+    targets_pending: usize,
+    #[pyo3(get)]
+    pages_fetched: i64,
+    #[pyo3(get)]
+    items_fetched: i64,
+    #[pyo3(get)]
+    elapsed_seconds: f64,
+    #[pyo3(get)]
+    eta_seconds: Option<f64>,
+    /// Why the last run stopped early: `auth`, `rate_limit`, `transient`, `request`, `max_pages`,
+    /// `cancelled`, or `error` when it raised.
+    #[pyo3(get)]
+    stopped_reason: Option<String>,
+    #[pyo3(get)]
+    last_error: Option<String>,
+}
+
+model_methods!(CrawlProgress {
+    #[getter(is_complete)]
+    fn py_is_complete(&self) -> bool {
+        self.is_complete()
+    }
+});
+
+impl Repr for CrawlProgress {
+    fn repr(&self) -> String {
+        format!(
+            "CrawlProgress({}/{} targets, {} items{})",
+            self.targets_done + self.targets_failed,
+            self.targets_total,
+            self.items_fetched,
+            self.stopped_reason.as_ref().map(|reason| format!(", stopped: {}", reason)).unwrap_or_default()
+        )
+    }
+}
+
+impl CrawlProgress {
+    pub fn is_complete(&self) -> bool {
+        self.targets_pending == 0
+    }
+
+    pub fn stopped_reason(&self) -> Option<&str> {
+        self.stopped_reason.as_deref()
+    }
+}
+
+struct Target {
+    platform: String,
+    target: String,
+    user_id: Option<String>,
+    cursor: Option<String>,
+}
+
+/// What to do after a target's request failed.
+enum Failure {
+    /// Leave the target pending and end the run; resuming later should succeed.
+    StopRun { reason: &'static str, message: String },
+    /// Mark the target failed and move on.
+    FailTarget(String),
+}
+
+fn meta_failure(category: ErrorCategory, message: String) -> Failure {
+    match category {
+        ErrorCategory::Auth => Failure::StopRun { reason: "auth", message },
+        ErrorCategory::RateLimit => Failure::StopRun { reason: "rate_limit", message },
+        ErrorCategory::Transient => Failure::StopRun { reason: "transient", message },
+        _ => Failure::FailTarget(message),
+    }
+}
+
+fn local_failure(error: MetaStatsError) -> Result<Failure, MetaStatsError> {
+    match error {
+        MetaStatsError::Request(message) => Ok(Failure::StopRun { reason: "request", message }),
+        MetaStatsError::Parse(message) => Ok(Failure::FailTarget(message)),
+        other => Err(other),
+    }
+}
+
+/// Options for one `CrawlJob::run`.
+#[derive(Default)]
+pub struct RunOptions {
+    pub access_token: Option<String>,
+    pub meta_version: Option<String>,
+    /// Stop after this many post or media pages; the job resumes from there next time.
+    pub max_pages: Option<usize>,
+}
+
+/// A crawl of full post history for Facebook pages and media history for Instagram users,
+/// checkpointed to SQLite after every page so an interrupted run resumes from its last cursor.
+///
+/// Only cursors are stored, never `paging.next` URLs, so no access token is written to disk.
+#[pyclass(module = "meta_stats.crawl")]
+pub struct CrawlJob {
+    connection: Mutex<Connection>,
+    #[pyo3(get)]
+    path: String,
+}
+
+impl CrawlJob {
+    pub fn open(path: &str) -> Result<CrawlJob, MetaStatsError> {
+        let connection = db::open(path, &MIGRATIONS)?;
+        Ok(CrawlJob { connection: Mutex::new(connection), path: path.to_string() })
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, MetaStatsError> {
+        self.connection
+            .lock()
+            .map_err(|e| MetaStatsError::Io(format!("Failed to lock crawl database: {}", e)))
+    }
+
+    /// Queues targets that are not already part of the job. Returns how many were added.
+    /// Targets may be URLs or handles; they are stored as the page id or username they name,
+    /// and nothing is queued if any of them is invalid.
+    pub fn add_targets(&self, platform: &str, targets: &[String]) -> Result<usize, MetaStatsError> {
+        let targets = targets.iter().map(|target| normalize_target(platform, target)).collect::<Result<Vec<_>, _>>()?;
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(db_error)?;
+
+        let mut added = 0;
+
+        for target in &targets {
+            added += transaction
+                .execute(
+                    "INSERT OR IGNORE INTO targets (platform, target, position)
+                     VALUES (?1, ?2, (SELECT COALESCE(MAX(position), -1) + 1 FROM targets))",
+                    params![platform, target],
+                )
+                .map_err(db_error)?;
+        }
+
+        transaction.commit().map_err(db_error)?;
+        Ok(added)
+    }
+
+    /// Puts failed targets back in the queue, keeping their cursors.
+    pub fn retry_failed(&self) -> Result<usize, MetaStatsError> {
+        self.connection()?
+            .execute("UPDATE targets SET status = 'pending', last_error = NULL WHERE status = 'failed'", [])
+            .map_err(db_error)
+    }
+
+    fn next_target(&self) -> Result<Option<Target>, MetaStatsError> {
+        self.connection()?
+            .query_row(
+                "SELECT platform, target, user_id, cursor FROM targets WHERE status = 'pending' ORDER BY position LIMIT 1",
+                [],
+                |row| Ok(Target { platform: row.get(0)?, target: row.get(1)?, user_id: row.get(2)?, cursor: row.get(3)? }),
+            )
+            .optional()
+            .map_err(db_error)
+    }
+
+    fn set_target_error(&self, target: &Target, status: &str, message: &str) -> Result<(), MetaStatsError> {
+        self.connection()?
+            .execute(
+                "UPDATE targets SET status = ?1, last_error = ?2 WHERE platform = ?3 AND target = ?4",
+                params![status, message, target.platform, target.target],
+            )
+            .map(|_| ())
+            .map_err(db_error)
+    }
+
+    /// Stores the target's numeric id, so later runs keep working if its name changes.
+    fn set_user_id(&self, target: &Target, user_id: &str, expected_items: Option<i64>) -> Result<(), MetaStatsError> {
+        self.connection()?
+            .execute(
+                "UPDATE targets SET user_id = ?1, expected_items = ?2 WHERE platform = ?3 AND target = ?4",
+                params![user_id, expected_items, target.platform, target.target],
+            )
+            .map(|_| ())
+            .map_err(db_error)
+    }
+
+    /// Stores one page of the account's items and advances the target's cursor in a single
+    /// transaction.
+    fn checkpoint(&self, target: &Target, account_id: &str, items: &[(String, String, String)], next_cursor: Option<&str>, run_id: i64) -> Result<(), MetaStatsError> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(db_error)?;
+
+        for (item_id, created_at, payload) in items {
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO items (platform, target, account_id, item_id, created_at, payload) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![target.platform, target.target, account_id, item_id, created_at, payload],
+                )
+                .map_err(db_error)?;
+        }
+
+        let status = if next_cursor.is_some() { "pending" } else { "done" };
+        transaction
+            .execute(
+                "UPDATE targets SET cursor = ?1, status = ?2, last_error = NULL, pages_fetched = pages_fetched + 1, items_fetched = items_fetched + ?3
+                 WHERE platform = ?4 AND target = ?5",
+                params![next_cursor, status, items.len() as i64, target.platform, target.target],
+            )
+            .map_err(db_error)?;
+        transaction
+            .execute(
+                "UPDATE runs SET pages_fetched = pages_fetched + 1, items_fetched = items_fetched + ?1 WHERE id = ?2",
+                params![items.len() as i64, run_id],
+            )
+            .map_err(db_error)?;
+
+        transaction.commit().map_err(db_error)
+    }
+
+    /// Current counts, with the ETA based on the most recent run.
+    pub fn progress(&self) -> Result<CrawlProgress, MetaStatsError> {
+        let connection = self.connection()?;
+
+        let mut progress = CrawlProgress::default();
+        let mut statement = connection
+            .prepare("SELECT status, COUNT(*), SUM(pages_fetched), SUM(items_fetched) FROM targets GROUP BY status")
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?)))
+            .map_err(db_error)?;
+
+        for row in rows {
+            let (status, count, pages, items) = row.map_err(db_error)?;
+            let count = count as usize;
+            match status.as_str() {
+                "done" => progress.targets_done = count,
+                "failed" => progress.targets_failed = count,
+                _ => progress.targets_pending += count,
+            }
+            progress.targets_total += count;
+            progress.pages_fetched += pages;
+            progress.items_fetched += items;
+        }
+
+        let last_run: Option<(f64, Option<f64>, i64, Option<String>)> = connection
+            .query_row(
+                "SELECT started_at, finished_at, items_fetched, stopped_reason FROM runs ORDER BY id DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(db_error)?;
+
+        if let Some((started_at, finished_at, run_items, stopped_reason)) = last_run {
+            let elapsed = (finished_at.unwrap_or_else(unix_now) - started_at).max(0.0);
+            progress.elapsed_seconds = elapsed;
+            progress.stopped_reason = stopped_reason;
+            progress.eta_seconds = Self::estimate_eta(&connection, run_items, elapsed)?;
+        }
+
+        progress.last_error = connection
+            .query_row(
+                "SELECT last_error FROM targets WHERE last_error IS NOT NULL ORDER BY position DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)?;
+
+        Ok(progress)
+    }
+
+    fn estimate_eta(connection: &Connection, run_items: i64, elapsed_seconds: f64) -> Result<Option<f64>, MetaStatsError> {
+        let remaining: (i64, i64) = connection
+            .query_row(
+                "SELECT COALESCE(SUM(MAX(expected_items - items_fetched, 0)), 0), COUNT(*) - COUNT(expected_items)
+                 FROM targets WHERE status = 'pending'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(db_error)?;
+        let (known_remaining, unknown_targets) = remaining;
+
+        let average_done: Option<f64> = connection
+            .query_row("SELECT AVG(items_fetched) FROM targets WHERE status = 'done'", [], |row| row.get(0))
+            .map_err(db_error)?;
+
+        if run_items <= 0 || elapsed_seconds <= 0.0 {
+            return Ok(if known_remaining == 0 && unknown_targets == 0 { Some(0.0) } else { None });
+        }
+
+        let unknown_remaining = match (unknown_targets, average_done) {
+            (0, _) => 0.0,
+            (count, Some(average)) => count as f64 * average,
+            (_, None) => return Ok(None),
+        };
+
+        let rate = run_items as f64 / elapsed_seconds;
+        Ok(Some((known_remaining as f64 + unknown_remaining) / rate))
+    }
+
+    /// Works through pending targets until the queue is empty, `max_pages` is reached or an
+    /// error that affects the whole job (expired token, rate limit, network) occurs. Errors
+    /// specific to one target (missing page, no permission) mark it failed and the run continues.
+    ///
+    /// `on_progress` is called after every page; returning `false` stops the run. The run is
+    /// recorded as finished even when it ends in an error, with `error` as its stopped reason.
+    pub fn run(&self, options: RunOptions, config: &MetaConfig, on_progress: &mut dyn FnMut(&CrawlProgress) -> bool) -> Result<CrawlProgress, MetaStatsError> {
+        let started = Instant::now();
+        let run_id = {
+            let connection = self.connection()?;
+            connection
+                .execute("INSERT INTO runs (started_at) VALUES (?1)", params![unix_now()])
+                .map_err(db_error)?;
+            connection.last_insert_rowid()
+        };
+
+        let outcome = self.run_targets(options, config, run_id, started, on_progress);
+        let stopped_reason = match &outcome {
+            Ok(stopped_reason) => stopped_reason.clone(),
+            Err(_) => Some("error".to_string()),
+        };
+        let finished = self.connection().and_then(|connection| {
+            connection
+                .execute(
+                    "UPDATE runs SET finished_at = ?1, stopped_reason = ?2 WHERE id = ?3",
+                    params![unix_now(), stopped_reason, run_id],
+                )
+                .map_err(db_error)
+        });
+        outcome?;
+        finished?;
+
+        let mut progress = self.progress()?;
+        progress.elapsed_seconds = started.elapsed().as_secs_f64();
+        Ok(progress)
+    }
+
+    /// The loop behind `run`, returning why it stopped early.
+    fn run_targets(&self, options: RunOptions, config: &MetaConfig, run_id: i64, started: Instant, on_progress: &mut dyn FnMut(&CrawlProgress) -> bool) -> Result<Option<String>, MetaStatsError> {
+        let client = HttpClient::new(config)?;
+        let mut access_token = options.access_token;
+        let mut meta_version: Option<GraphVersion> = None;
+        let mut pages = 0;
+
+        while let Some(target) = self.next_target()? {
+            if options.max_pages.map(|max| pages >= max).unwrap_or(false) {
+                return Ok(Some("max_pages".to_string()));
+            }
+
+            let outcome = if target.platform == FACEBOOK {
+                let version = match meta_version {
+                    Some(version) => version,
+                    None => *meta_version.insert(GraphVersion::resolve(options.meta_version.clone(), config)?),
+                };
+                let token = match &access_token {
+                    Some(token) => Ok(Ok(token.clone())),
+                    None => auth::fetch_meta_access_token(None, None, None, None, Some(version.to_string()), config)
+                        .map(|token| token.map(|token| access_token.insert(token).clone())),
+                };
+                match token {
+                    Ok(Ok(token)) => self.crawl_facebook_page(&client, &token, &version, &target, run_id, config)?,
+                    // No Facebook target can be fetched without a token, whatever Meta's reason was.
+                    Ok(Err(error)) => Some(Failure::StopRun { reason: "auth", message: error.message().to_string() }),
+                    Err(error) => Some(local_failure(error)?),
+                }
+            } else {
+                self.crawl_instagram_user(&client, &target, run_id, config)?
+            };
+
+            match outcome {
+                Some(Failure::StopRun { reason, message }) => {
+                    self.set_target_error(&target, "pending", &message)?;
+                    return Ok(Some(reason.to_string()));
+                },
+                Some(Failure::FailTarget(message)) => self.set_target_error(&target, "failed", &message)?,
+                None => pages += 1,
+            }
+
+            let mut progress = self.progress()?;
+            progress.elapsed_seconds = started.elapsed().as_secs_f64();
+            if !on_progress(&progress) {
+                return Ok(Some("cancelled".to_string()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn crawl_facebook_page(&self, client: &HttpClient, access_token: &str, meta_version: &GraphVersion, target: &Target, run_id: i64, config: &MetaConfig) -> Result<Option<Failure>, MetaStatsError> {
+        let page_id = match &target.user_id {
+            Some(page_id) => page_id.clone(),
+            None => match facebook::resolve_page_id(&target.target, Some(access_token.to_string()), Some(meta_version.to_string()), None, false, config) {
+                Ok(Ok(page_id)) => {
+                    self.set_user_id(target, &page_id, None)?;
+                    page_id
+                },
+                Ok(Err(error)) => return Ok(Some(meta_failure(error.category(), error.message().to_string()))),
+                Err(error) => return local_failure(error).map(Some),
+            },
+        };
+
+        let page = match facebook::fetch_facebook_posts_page(client, access_token, &page_id, meta_version, target.cursor.as_deref(), config) {
+            Ok(Ok(page)) => page,
+            Ok(Err(error)) => return Ok(Some(meta_failure(error.category(), error.message().to_string()))),
+            Err(error) => return local_failure(error).map(Some),
+        };
+
+        let (posts, next_cursor) = page;
+        let items = posts
+            .iter()
+            .map(|post| {
+                let payload = serde_json::to_string(post).map_err(|e| MetaStatsError::Parse(e.to_string()))?;
+                Ok((post.id().to_string(), post.created_time().map(|time| timestamp::format(&time)).unwrap_or_default(), payload))
+            })
+            .collect::<Result<Vec<_>, MetaStatsError>>()?;
+
+        self.checkpoint(target, &page_id, &items, next_cursor.as_deref(), run_id)?;
+        Ok(None)
+    }
+
+    fn crawl_instagram_user(&self, client: &HttpClient, target: &Target, run_id: i64, config: &MetaConfig) -> Result<Option<Failure>, MetaStatsError> {
+        let user_id = match &target.user_id {
+            Some(user_id) => user_id.clone(),
+            None => match instagram::fetch_instagram_page_info(Some(target.target.clone()), config) {
+                Ok(result) => match result.into_result() {
+                    Ok(page_info) => {
+                        let user = page_info.user();
+                        self.set_user_id(target, user.id(), Some(user.timeline_media().count()))?;
+                        user.id().to_string()
+                    },
+                    Err(error) => return Ok(Some(meta_failure(error.category(), error.message().to_string()))),
+                },
+                Err(error) => return local_failure(error).map(Some),
+            },
+        };
+
+        let page = match instagram::fetch_instagram_feed_page(client, &user_id, target.cursor.as_deref(), config) {
+            Ok(Ok(page)) => page,
+            Ok(Err(error)) => return Ok(Some(meta_failure(error.category(), error.message().to_string()))),
+            Err(error) => return local_failure(error).map(Some),
+        };
+
+        let (media, next_max_id) = page;
+        let items = media
+            .iter()
+            .map(|media| {
+                let payload = serde_json::to_string(media).map_err(|e| MetaStatsError::Parse(e.to_string()))?;
+                Ok((media.id().to_string(), media.taken_at().map(|time| format!("{:012}", time.timestamp())).unwrap_or_default(), payload))
+            })
+            .collect::<Result<Vec<_>, MetaStatsError>>()?;
+
+        self.checkpoint(target, &user_id, &items, next_max_id.as_deref(), run_id)?;
+        Ok(None)
+    }
+
+    /// Items of the account crawled as `target`, or with `target` as its id.
+    fn stored_items<T: serde::de::DeserializeOwned>(&self, platform: &str, target: &str) -> Result<Vec<T>, MetaStatsError> {
+        let connection = self.connection()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT payload FROM items WHERE platform = ?1 AND account_id = COALESCE(
+                     (SELECT user_id FROM targets WHERE platform = ?1 AND target = ?2), ?2
+                 ) ORDER BY created_at DESC",
+            )
+            .map_err(db_error)?;
+        let payloads = statement
+            .query_map(params![platform, target], |row| row.get::<_, String>(0))
+            .map_err(db_error)?;
+
+        payloads
+            .map(|payload| {
+                let payload = payload.map_err(db_error)?;
+                serde_json::from_str(&payload).map_err(|e| MetaStatsError::Parse(format!("Corrupt crawl item: {}", e)))
+            })
+            .collect()
+    }
+
+    /// Posts crawled so far for a page, by the name it was added as or its id, newest first.
+    pub fn facebook_posts(&self, page_id: &str) -> Result<Vec<PostInfo>, MetaStatsError> {
+        self.stored_items(FACEBOOK, &normalize_target(FACEBOOK, page_id)?)
+    }
+
+    /// Media crawled so far for an Instagram user, by the username it was added as or its id,
+    /// newest first.
+    pub fn instagram_media(&self, username: &str) -> Result<Vec<FeedMedia>, MetaStatsError> {
+        self.stored_items(INSTAGRAM, &normalize_target(INSTAGRAM, username)?)
+    }
+}
+
+/// The page id or username a crawl target is stored under.
+fn normalize_target(platform: &str, target: &str) -> Result<String, MetaStatsError> {
+    match platform {
+        FACEBOOK => Ok(FacebookPageRef::parse(target)?.to_string()),
+        _ => Ok(InstagramHandle::parse(target)?.to_string()),
+    }
+}
+
+#[pymethods]
+impl CrawlJob {
+    /// Opens the job stored at `path`, creating it if needed.
+    #[staticmethod]
+    #[pyo3(name = "open")]
+    fn py_open(path: String) -> PyResult<CrawlJob> {
+        Ok(CrawlJob::open(&path)?)
+    }
+
+    fn add_facebook_pages(&self, page_ids: Vec<PageArg>) -> PyResult<usize> {
+        let page_ids: Vec<String> = page_ids.into_iter().map(String::from).collect();
+        Ok(self.add_targets(FACEBOOK, &page_ids)?)
+    }
+
+    fn add_instagram_users(&self, usernames: Vec<HandleArg>) -> PyResult<usize> {
+        let usernames: Vec<String> = usernames.into_iter().map(String::from).collect();
+        Ok(self.add_targets(INSTAGRAM, &usernames)?)
+    }
+
+    #[pyo3(name = "retry_failed")]
+    fn py_retry_failed(&self) -> PyResult<usize> {
+        Ok(self.retry_failed()?)
+    }
+
+    #[pyo3(name = "progress")]
+    fn py_progress(&self) -> PyResult<CrawlProgress> {
+        Ok(self.progress()?)
+    }
+
+    /// Runs the job with the GIL released. `progress_callback(progress)` is called after every
+    /// page; returning `False` stops the run, and an exception stops it and is re-raised.
+    #[pyo3(name = "run")]
+    fn py_run(&self, py: Python<'_>, access_token: Option<String>, meta_version: Option<String>, max_pages: Option<usize>, progress_callback: Option<PyObject>, config: Option<MetaConfig>) -> PyResult<CrawlProgress> {
+        let config = MetaConfig::or_from_env(config)?;
+        let options = RunOptions { access_token, meta_version, max_pages };
+        let mut callback_error: Option<PyErr> = None;
+
+        let result = py.allow_threads(|| {
+            let mut on_progress = |progress: &CrawlProgress| match &progress_callback {
+                Some(callback) => Python::with_gil(|py| match callback.call1(py, (progress.clone(),)) {
+                    Ok(keep_going) => !keep_going.as_ref(py).is(PyBool::new(py, false)),
+                    Err(e) => {
+                        callback_error = Some(e);
+                        false
+                    },
+                }),
+                None => true,
+            };
+            self.run(options, &config, &mut on_progress)
+        });
+
+        match callback_error {
+            Some(e) => Err(e),
+            None => Ok(result?),
+        }
+    }
+
+    #[pyo3(name = "facebook_posts")]
+    fn py_facebook_posts(&self, page_id: PageArg) -> PyResult<Vec<PostInfo>> {
+        Ok(self.facebook_posts(&String::from(page_id))?)
+    }
+
+    #[pyo3(name = "instagram_media")]
+    fn py_instagram_media(&self, username: HandleArg) -> PyResult<Vec<FeedMedia>> {
+        Ok(self.instagram_media(&String::from(username))?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CrawlJob({:?})", self.path)
+    }
+}
@@ -0,0 +1,244 @@
+use crate::db::unix_now;
+use crate::error::MetaStatsError;
+use crate::export::{Table, Tabular};
+use crate::facebook::{CommentNode, PageEngagement, PostInfo};
+use crate::instagram::InstagramPageInfo;
+use crate::protocol::{model_methods, Repr};
+use arrow_array::RecordBatch;
+use chrono::DateTime;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Dataset names, each a directory under the writer's root with its own fixed schema.
+pub const POSTS: &str = "posts";
+pub const COMMENTS: &str = "comments";
+pub const PAGE_ENGAGEMENT: &str = "page_engagement";
+pub const INSTAGRAM_PROFILES: &str = "instagram_profiles";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DatasetFormat {
+    Parquet,
+    Csv,
+}
+
+impl DatasetFormat {
+    pub fn parse(format: &str) -> Result<DatasetFormat, MetaStatsError> {
+        match format.to_ascii_lowercase().as_str() {
+            "parquet" => Ok(DatasetFormat::Parquet),
+            "csv" => Ok(DatasetFormat::Csv),
+            other => Err(MetaStatsError::InvalidArgument(format!("Unknown dataset format '{}', expected 'parquet' or 'csv'", other))),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DatasetFormat::Parquet => "parquet",
+            DatasetFormat::Csv => "csv",
+        }
+    }
+}
+
+/// One file added to a dataset by a single write.
+#[pyclass(frozen, module = "meta_stats.dataset")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DatasetPart {
+    #[pyo3(get)]
+    dataset: String,
+    #[pyo3(get)]
+    platform: String,
+    #[pyo3(get)]
+    path: String,
+    #[pyo3(get)]
+    rows: usize,
+}
+
+impl DatasetPart {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+}
+
+model_methods!(frozen DatasetPart);
+
+impl Repr for DatasetPart {
+    fn repr(&self) -> String {
+        format!("DatasetPart({}, {} rows)", self.path, self.rows)
+    }
+}
+
+/// `YYYY-MM-DD` of a Unix timestamp in UTC.
+fn utc_date(seconds: f64) -> String {
+    DateTime::from_timestamp(seconds.floor() as i64, 0).unwrap_or_default().date_naive().to_string()
+}
+
+fn io_error(path: &Path, e: impl std::fmt::Display) -> MetaStatsError {
+    MetaStatsError::Io(format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Writes results as Hive-style partitioned files, `<root>/<dataset>/platform=<platform>/date=<YYYY-MM-DD>/part-*.parquet`,
+/// partitioned by the UTC date of capture. Every write adds a new part file, so a dataset is
+/// appended to by writing again; files are written under a hidden temporary name and renamed
+/// into place, so readers never see a partial file.
+#[pyclass(module = "meta_stats.dataset")]
+pub struct DatasetWriter {
+    root: PathBuf,
+    format: DatasetFormat,
+    sequence: AtomicUsize,
+}
+
+impl DatasetWriter {
+    pub fn open(root: &str, format: DatasetFormat) -> Result<DatasetWriter, MetaStatsError> {
+        let root = PathBuf::from(root);
+        fs::create_dir_all(&root).map_err(|e| io_error(&root, e))?;
+        Ok(DatasetWriter { root, format, sequence: AtomicUsize::new(0) })
+    }
+
+    pub fn format(&self) -> DatasetFormat {
+        self.format
+    }
+
+    /// Adds `table` to `dataset` as one part file, prefixed with a `captured_at` column.
+    /// Returns `None` without writing anything when the table has no rows.
+    pub fn write(&self, dataset: &str, platform: &str, captured_at: Option<f64>, table: Table) -> Result<Option<DatasetPart>, MetaStatsError> {
+        let rows = table.num_rows();
+        if rows == 0 {
+            return Ok(None);
+        }
+
+        let captured_at = captured_at.unwrap_or_else(unix_now);
+        let batch = Table::new().float64("captured_at", vec![Some(captured_at); rows]).append(table).record_batch()?;
+
+        let directory = self.root.join(dataset).join(format!("platform={}", platform)).join(format!("date={}", utc_date(captured_at)));
+        fs::create_dir_all(&directory).map_err(|e| io_error(&directory, e))?;
+
+        let name = format!(
+            "part-{}-{}-{}.{}",
+            (unix_now() * 1000.0) as u64,
+            std::process::id(),
+            self.sequence.fetch_add(1, Ordering::Relaxed),
+            self.format.extension()
+        );
+        let path = directory.join(&name);
+        let tmp_path = directory.join(format!(".{}.tmp", name));
+
+        let written = self.write_file(&tmp_path, &batch).and_then(|_| fs::rename(&tmp_path, &path).map_err(|e| io_error(&path, e)));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+
+        Ok(Some(DatasetPart { dataset: dataset.to_string(), platform: platform.to_string(), path: path.to_string_lossy().into_owned(), rows }))
+    }
+
+    fn write_file(&self, path: &Path, batch: &RecordBatch) -> Result<(), MetaStatsError> {
+        let file = File::create(path).map_err(|e| io_error(path, e))?;
+        let file = match self.format {
+            DatasetFormat::Parquet => {
+                let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+                let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties)).map_err(|e| io_error(path, e))?;
+                writer.write(batch).map_err(|e| io_error(path, e))?;
+                writer.into_inner().map_err(|e| io_error(path, e))?
+            },
+            DatasetFormat::Csv => {
+                let mut writer = arrow_csv::WriterBuilder::new().with_header(true).build(file);
+                writer.write(batch).map_err(|e| io_error(path, e))?;
+                writer.into_inner()
+            },
+        };
+        file.sync_all().map_err(|e| io_error(path, e))
+    }
+
+    pub fn write_posts(&self, page_id: &str, posts: &[PostInfo], captured_at: Option<f64>) -> Result<Option<DatasetPart>, MetaStatsError> {
+        let table = Table::new().utf8("page_id", vec![Some(page_id); posts.len()]).append(PostInfo::table(posts));
+        self.write(POSTS, "facebook", captured_at, table)
+    }
+
+    pub fn write_comments(&self, post_id: &str, comments: &[CommentNode], captured_at: Option<f64>) -> Result<Option<DatasetPart>, MetaStatsError> {
+        let comments = CommentNode::table(comments);
+        let table = Table::new().utf8("post_id", vec![Some(post_id); comments.num_rows()]).append(comments);
+        self.write(COMMENTS, "facebook", captured_at, table)
+    }
+
+    pub fn write_page_engagement(&self, pages: &[PageEngagement], captured_at: Option<f64>) -> Result<Option<DatasetPart>, MetaStatsError> {
+        self.write(PAGE_ENGAGEMENT, "facebook", captured_at, PageEngagement::table(pages))
+    }
+
+    pub fn write_instagram_profiles(&self, profiles: &[InstagramPageInfo], captured_at: Option<f64>) -> Result<Option<DatasetPart>, MetaStatsError> {
+        self.write(INSTAGRAM_PROFILES, "instagram", captured_at, InstagramPageInfo::table(profiles))
+    }
+}
+
+#[pymethods]
+impl DatasetWriter {
+    /// `format` is `parquet` (the default) or `csv`.
+    #[staticmethod]
+    #[pyo3(name = "open")]
+    fn py_open(root: String, format: Option<&str>) -> PyResult<DatasetWriter> {
+        let format = format.map(DatasetFormat::parse).transpose()?.unwrap_or(DatasetFormat::Parquet);
+        Ok(DatasetWriter::open(&root, format)?)
+    }
+
+    #[getter]
+    fn root(&self) -> String {
+        self.root.to_string_lossy().into_owned()
+    }
+
+    #[getter(format)]
+    fn py_format(&self) -> &'static str {
+        self.format.extension()
+    }
+
+    #[pyo3(name = "write_posts")]
+    fn py_write_posts(&self, page_id: &str, posts: Vec<PostInfo>, captured_at: Option<f64>) -> PyResult<Option<DatasetPart>> {
+        Ok(self.write_posts(page_id, &posts, captured_at)?)
+    }
+
+    #[pyo3(name = "write_comments")]
+    fn py_write_comments(&self, post_id: &str, comments: Vec<CommentNode>, captured_at: Option<f64>) -> PyResult<Option<DatasetPart>> {
+        Ok(self.write_comments(post_id, &comments, captured_at)?)
+    }
+
+    #[pyo3(name = "write_page_engagement")]
+    fn py_write_page_engagement(&self, pages: Vec<PageEngagement>, captured_at: Option<f64>) -> PyResult<Option<DatasetPart>> {
+        Ok(self.write_page_engagement(&pages, captured_at)?)
+    }
+
+    #[pyo3(name = "write_instagram_profiles")]
+    fn py_write_instagram_profiles(&self, profiles: Vec<InstagramPageInfo>, captured_at: Option<f64>) -> PyResult<Option<DatasetPart>> {
+        Ok(self.write_instagram_profiles(&profiles, captured_at)?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DatasetWriter({:?}, {})", self.root, self.format.extension())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_utc_dates() {
+        assert_eq!(utc_date(0.0), "1970-01-01");
+        assert_eq!(utc_date(951_782_400.0), "2000-02-29");
+        assert_eq!(utc_date(1_735_689_599.0), "2024-12-31");
+        assert_eq!(utc_date(-1.0), "1969-12-31");
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(DatasetFormat::parse("Parquet").unwrap(), DatasetFormat::Parquet);
+        assert_eq!(DatasetFormat::parse("csv").unwrap().extension(), "csv");
+        assert!(DatasetFormat::parse("xlsx").is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::error::MetaStatsError;
+use rusqlite::Connection;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn db_error(e: rusqlite::Error) -> MetaStatsError {
+    MetaStatsError::Io(format!("Database error: {}", e))
+}
+
+pub(crate) fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+/// Opens the SQLite file at `path` and brings its schema up to date. `migrations[n]` upgrades
+/// a database at `PRAGMA user_version` n to n + 1; each runs in its own transaction. Existing
+/// migrations must never be edited once released, only appended to.
+pub(crate) fn open(path: &str, migrations: &[&str]) -> Result<Connection, MetaStatsError> {
+    let mut connection = Connection::open(path).map_err(db_error)?;
+    connection.busy_timeout(std::time::Duration::from_secs(5)).map_err(db_error)?;
+
+    let version: usize = connection
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(db_error)?;
+    if version > migrations.len() {
+        return Err(MetaStatsError::Io(format!(
+            "{} has schema version {}, newer than this library supports ({})",
+            path,
+            version,
+            migrations.len()
+        )));
+    }
+
+    for (index, migration) in migrations.iter().enumerate().skip(version) {
+        let transaction = connection.transaction().map_err(db_error)?;
+        transaction.execute_batch(migration).map_err(db_error)?;
+        transaction
+            .execute_batch(&format!("PRAGMA user_version = {}", index + 1))
+            .map_err(db_error)?;
+        transaction.commit().map_err(db_error)?;
+    }
+
+    Ok(connection)
+}
@@ -0,0 +1,284 @@
+use crate::export::{export_methods, Export, Table, Tabular};
+use crate::facebook::PostInfo;
+use crate::instagram::{FeedMedia, InstagramPageInfo};
+use crate::protocol::{model_methods, Repr};
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Modified z-score above which a post counts as an outlier (Iglewicz and Hoaglin's cut-off).
+const OUTLIER_Z_SCORE: f64 = 3.5;
+
+/// Interactions on one post relative to the account's follower count.
+#[pyclass(frozen, module = "meta_stats.engagement")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PostEngagement {
+    #[pyo3(get)]
+    post_id: String,
+    #[pyo3(get)]
+    likes: i64,
+    #[pyo3(get)]
+    comments: i64,
+    /// Facebook only; `None` when the post has no share count.
+    #[pyo3(get)]
+    shares: Option<i64>,
+    /// Total Facebook reactions, likes included, when supplied.
+    #[pyo3(get)]
+    reactions: Option<i64>,
+    #[pyo3(get)]
+    interactions: i64,
+    /// Interactions as a percentage of followers; `None` when the follower count is zero.
+    #[pyo3(get)]
+    engagement_rate: Option<f64>,
+    /// Whether the post's rate is far above the account's usual rate.
+    #[pyo3(get)]
+    is_outlier: bool,
+}
+
+impl PostEngagement {
+    fn new(post_id: &str, likes: i64, comments: i64, shares: Option<i64>, reactions: Option<i64>, followers: i64) -> PostEngagement {
+        // Reactions include likes, so they replace rather than add to the like count.
+        let interactions = reactions.unwrap_or(likes).max(likes) + comments + shares.unwrap_or(0);
+        PostEngagement {
+            post_id: post_id.to_string(),
+            likes,
+            comments,
+            shares,
+            reactions,
+            interactions,
+            engagement_rate: (followers > 0).then(|| interactions as f64 / followers as f64 * 100.0),
+            is_outlier: false,
+        }
+    }
+
+    pub fn post_id(&self) -> &str {
+        &self.post_id
+    }
+
+    pub fn engagement_rate(&self) -> Option<f64> {
+        self.engagement_rate
+    }
+
+    pub fn is_outlier(&self) -> bool {
+        self.is_outlier
+    }
+}
+
+impl Tabular for PostEngagement {
+    fn table(rows: &[PostEngagement]) -> Table {
+        Table::new()
+            .utf8("post_id", rows.iter().map(|post| Some(&post.post_id)))
+            .int64("likes", rows.iter().map(|post| Some(post.likes)))
+            .int64("comments", rows.iter().map(|post| Some(post.comments)))
+            .int64("shares", rows.iter().map(|post| post.shares))
+            .int64("reactions", rows.iter().map(|post| post.reactions))
+            .int64("interactions", rows.iter().map(|post| Some(post.interactions)))
+            .float64("engagement_rate", rows.iter().map(|post| post.engagement_rate))
+            .boolean("is_outlier", rows.iter().map(|post| Some(post.is_outlier)))
+    }
+}
+
+model_methods!(frozen PostEngagement);
+
+impl Repr for PostEngagement {
+    fn repr(&self) -> String {
+        format!(
+            "PostEngagement({}, {} interactions, rate={}{})",
+            self.post_id,
+            self.interactions,
+            self.engagement_rate.map(|rate| format!("{:.2}%", rate)).unwrap_or_else(|| "None".to_string()),
+            if self.is_outlier { ", outlier" } else { "" }
+        )
+    }
+}
+
+/// Engagement across an account's recent posts.
+#[pyclass(frozen, module = "meta_stats.engagement")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EngagementSummary {
+    /// `facebook` or `instagram`.
+    #[pyo3(get)]
+    platform: String,
+    #[pyo3(get)]
+    account: Option<String>,
+    /// Follower count the rates are relative to, as of when the posts were fetched.
+    #[pyo3(get)]
+    followers: i64,
+    /// The posts considered, newest first.
+    #[pyo3(get)]
+    posts: Vec<PostEngagement>,
+    #[pyo3(get)]
+    total_interactions: i64,
+    #[pyo3(get)]
+    mean_rate: Option<f64>,
+    #[pyo3(get)]
+    median_rate: Option<f64>,
+}
+
+impl Export for EngagementSummary {
+    fn table(&self) -> PyResult<Table> {
+        Ok(PostEngagement::table(&self.posts))
+    }
+}
+
+impl EngagementSummary {
+    fn new(platform: &str, account: Option<String>, followers: i64, mut posts: Vec<PostEngagement>) -> EngagementSummary {
+        let mut rates: Vec<f64> = posts.iter().filter_map(|post| post.engagement_rate).collect();
+        rates.sort_by(f64::total_cmp);
+
+        let median_rate = median(&rates);
+        let mean_rate = (!rates.is_empty()).then(|| rates.iter().sum::<f64>() / rates.len() as f64);
+
+        // Outliers by modified z-score, which uses the median absolute deviation so a single
+        // viral post does not inflate the spread it is measured against.
+        if let (Some(median_rate), true) = (median_rate, rates.len() >= 3) {
+            let mut deviations: Vec<f64> = rates.iter().map(|rate| (rate - median_rate).abs()).collect();
+            deviations.sort_by(f64::total_cmp);
+            let mad = median(&deviations).unwrap_or(0.0);
+            let mean_deviation = deviations.iter().sum::<f64>() / deviations.len() as f64;
+
+            // When most posts have the same rate the MAD is zero; fall back to the mean
+            // absolute deviation with its matching constant.
+            let scale = if mad > 0.0 { mad / 0.6745 } else { mean_deviation * 1.253314 };
+            if scale > 0.0 {
+                for post in posts.iter_mut() {
+                    if let Some(rate) = post.engagement_rate {
+                        post.is_outlier = (rate - median_rate) / scale > OUTLIER_Z_SCORE;
+                    }
+                }
+            }
+        }
+
+        EngagementSummary {
+            platform: platform.to_string(),
+            account,
+            followers,
+            total_interactions: posts.iter().map(|post| post.interactions).sum(),
+            posts,
+            mean_rate,
+            median_rate,
+        }
+    }
+
+    pub fn posts(&self) -> &[PostEngagement] {
+        &self.posts
+    }
+
+    pub fn mean_rate(&self) -> Option<f64> {
+        self.mean_rate
+    }
+
+    pub fn median_rate(&self) -> Option<f64> {
+        self.median_rate
+    }
+
+    pub fn outliers(&self) -> impl Iterator<Item = &PostEngagement> {
+        self.posts.iter().filter(|post| post.is_outlier)
+    }
+}
+
+export_methods!(frozen model EngagementSummary {
+    #[getter]
+    fn post_count(&self) -> usize {
+        self.posts.len()
+    }
+
+    #[getter(outliers)]
+    fn py_outliers(&self) -> Vec<PostEngagement> {
+        self.outliers().cloned().collect()
+    }
+});
+
+impl Repr for EngagementSummary {
+    fn repr(&self) -> String {
+        format!(
+            "EngagementSummary({} {}, {} posts, median={})",
+            self.platform,
+            self.account.as_deref().unwrap_or("?"),
+            self.posts.len(),
+            self.median_rate.map(|rate| format!("{:.2}%", rate)).unwrap_or_else(|| "None".to_string())
+        )
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        Ok(self.table()?.html("EngagementSummary")?)
+    }
+}
+
+/// Median of sorted values.
+fn median(sorted: &[f64]) -> Option<f64> {
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[n / 2]),
+        n => Some((sorted[n / 2 - 1] + sorted[n / 2]) / 2.0),
+    }
+}
+
+/// Engagement over the newest `last_n` Facebook posts (all when `None`). Posts need like and
+/// comment summaries, as returned by `get_facebook_page_posts_with_summary`; `reactions` maps
+/// post ids to total reaction counts where they have been fetched.
+pub fn facebook_engagement(posts: &[PostInfo], followers: i64, last_n: Option<usize>, reactions: Option<&HashMap<String, i64>>, account: Option<String>) -> EngagementSummary {
+    let mut posts: Vec<&PostInfo> = posts.iter().collect();
+    posts.sort_by_key(|post| std::cmp::Reverse(post.created_time()));
+
+    let posts = posts
+        .into_iter()
+        .take(last_n.unwrap_or(usize::MAX))
+        .map(|post| {
+            let reactions = reactions.and_then(|reactions| reactions.get(post.id()).copied());
+            PostEngagement::new(post.id(), post.like_count().unwrap_or(0), post.comment_count().unwrap_or(0), post.share_count(), reactions, followers)
+        })
+        .collect();
+
+    EngagementSummary::new("facebook", account, followers, posts)
+}
+
+/// Engagement over the newest `last_n` items of an Instagram media feed.
+pub fn instagram_engagement(media: &[FeedMedia], followers: i64, last_n: Option<usize>, account: Option<String>) -> EngagementSummary {
+    let mut media: Vec<&FeedMedia> = media.iter().collect();
+    media.sort_by_key(|media| std::cmp::Reverse(media.taken_at()));
+
+    let posts = media
+        .into_iter()
+        .take(last_n.unwrap_or(usize::MAX))
+        .map(|media| PostEngagement::new(media.id(), media.like_count(), media.comment_count(), None, None, followers))
+        .collect();
+
+    EngagementSummary::new("instagram", account, followers, posts)
+}
+
+/// Engagement over the recent media included in a profile response, using the follower
+/// count from the same response.
+pub fn instagram_profile_engagement(page_info: &InstagramPageInfo, last_n: Option<usize>, account: Option<String>) -> EngagementSummary {
+    let user = page_info.user();
+    let followers = user.followers_count();
+
+    let mut nodes: Vec<_> = user.timeline_media().nodes().collect();
+    nodes.sort_by_key(|node| std::cmp::Reverse(node.taken_at()));
+
+    let posts = nodes
+        .into_iter()
+        .take(last_n.unwrap_or(usize::MAX))
+        .map(|node| PostEngagement::new(node.id(), node.like_count(), node.comment_count(), None, None, followers))
+        .collect();
+
+    EngagementSummary::new("instagram", account, followers, posts)
+}
+
+#[pyfunction]
+#[pyo3(name = "facebook_engagement")]
+pub fn py_facebook_engagement(posts: Vec<PostInfo>, followers: i64, last_n: Option<usize>, reactions: Option<HashMap<String, i64>>, account: Option<String>) -> EngagementSummary {
+    facebook_engagement(&posts, followers, last_n, reactions.as_ref(), account)
+}
+
+#[pyfunction]
+#[pyo3(name = "instagram_engagement")]
+pub fn py_instagram_engagement(media: Vec<FeedMedia>, followers: i64, last_n: Option<usize>, account: Option<String>) -> EngagementSummary {
+    instagram_engagement(&media, followers, last_n, account)
+}
+
+#[pyfunction]
+#[pyo3(name = "instagram_profile_engagement")]
+pub fn py_instagram_profile_engagement(page_info: InstagramPageInfo, last_n: Option<usize>, username: Option<String>) -> EngagementSummary {
+    instagram_profile_engagement(&page_info, last_n, username)
+}
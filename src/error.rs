@@ -0,0 +1,53 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::PyErr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Failures that happen before a Meta response can be turned into a result. Errors reported
+/// by Meta itself are not represented here; they come back as the `error` side of a result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum MetaStatsError {
+    /// Credentials or settings are missing from the environment or configuration.
+    Config(String),
+    /// A required argument was missing or had an unsupported value.
+    InvalidArgument(String),
+    /// The HTTP request could not be sent or its body could not be read.
+    Request(String),
+    /// The response was neither the expected model nor a Meta error.
+    Parse(String),
+    /// A local file (cache entry, cassette, ...) could not be read or written.
+    Io(String),
+}
+
+impl MetaStatsError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MetaStatsError::Config(_) => "config",
+            MetaStatsError::InvalidArgument(_) => "invalid_argument",
+            MetaStatsError::Request(_) => "request",
+            MetaStatsError::Parse(_) => "parse",
+            MetaStatsError::Io(_) => "io",
+        }
+    }
+}
+
+impl fmt::Display for MetaStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaStatsError::Config(message)
+            | MetaStatsError::InvalidArgument(message)
+            | MetaStatsError::Request(message)
+            | MetaStatsError::Parse(message)
+            | MetaStatsError::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for MetaStatsError {}
+
+impl From<MetaStatsError> for PyErr {
+    fn from(error: MetaStatsError) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
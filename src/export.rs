@@ -0,0 +1,391 @@
+use crate::engagement::PostEngagement;
+use crate::error::MetaStatsError;
+use crate::facebook::{CommentNode, PageEngagement, PageEvent, PageVideo, PostInfo};
+use crate::instagram::{FeedMedia, InstagramPageInfo, MediaNode};
+use crate::protocol;
+use crate::snapshot::Snapshot;
+use crate::timestamp;
+use arrow_array::ffi::{to_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+use arrow_array::{Array, ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray, StructArray, TimestampSecondArray};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::{PyImportError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use std::ffi::CString;
+use std::sync::Arc;
+
+/// Columns built from a collection of results, one row per item, with nested summaries
+/// flattened into plain columns.
+#[derive(Default)]
+pub struct Table {
+    fields: Vec<Field>,
+    columns: Vec<ArrayRef>,
+}
+
+impl Table {
+    pub fn new() -> Table {
+        Table::default()
+    }
+
+    fn column(mut self, name: &str, data_type: DataType, column: ArrayRef) -> Table {
+        self.fields.push(Field::new(name, data_type, true));
+        self.columns.push(column);
+        self
+    }
+
+    pub fn utf8<S: AsRef<str>>(self, name: &str, values: impl IntoIterator<Item = Option<S>>) -> Table {
+        self.column(name, DataType::Utf8, Arc::new(values.into_iter().collect::<StringArray>()))
+    }
+
+    pub fn int64(self, name: &str, values: impl IntoIterator<Item = Option<i64>>) -> Table {
+        self.column(name, DataType::Int64, Arc::new(values.into_iter().collect::<Int64Array>()))
+    }
+
+    pub fn float64(self, name: &str, values: impl IntoIterator<Item = Option<f64>>) -> Table {
+        self.column(name, DataType::Float64, Arc::new(values.into_iter().collect::<Float64Array>()))
+    }
+
+    pub fn boolean(self, name: &str, values: impl IntoIterator<Item = Option<bool>>) -> Table {
+        self.column(name, DataType::Boolean, Arc::new(values.into_iter().collect::<BooleanArray>()))
+    }
+
+    /// Seconds in UTC, which pandas and Polars read as timezone-aware datetimes. The zone is
+    /// written as an offset, since named zones need arrow's `chrono-tz` feature to format.
+    pub fn timestamp(self, name: &str, values: impl IntoIterator<Item = Option<DateTime<Utc>>>) -> Table {
+        let column = values.into_iter().map(|time| time.map(|time| time.timestamp())).collect::<TimestampSecondArray>().with_timezone("+00:00");
+        self.column(name, DataType::Timestamp(TimeUnit::Second, Some("+00:00".into())), Arc::new(column))
+    }
+
+    /// A time as the Graph API writes it, e.g. `2024-03-01T18:00:00+0000`, the form the
+    /// column had before times were parsed. Tables pair it with a `timestamp` column named
+    /// `<name>_utc` at the end, so existing datasets keep their layout.
+    pub fn graph_time(self, name: &str, values: impl IntoIterator<Item = Option<DateTime<Utc>>>) -> Table {
+        self.utf8(name, values.into_iter().map(|time| time.map(|time| timestamp::format(&time))))
+    }
+
+    /// Unix seconds, the form Instagram's `taken_at` had before times were parsed, paired with
+    /// `<name>_utc` like `graph_time`.
+    pub fn unix_time(self, name: &str, values: impl IntoIterator<Item = Option<DateTime<Utc>>>) -> Table {
+        self.int64(name, values.into_iter().map(|time| time.map(|time| time.timestamp())))
+    }
+
+    /// Adds the columns of `other` after these.
+    pub fn append(mut self, other: Table) -> Table {
+        self.fields.extend(other.fields);
+        self.columns.extend(other.columns);
+        self
+    }
+
+    pub fn column_names(&self) -> Vec<&str> {
+        self.fields.iter().map(|field| field.name().as_str()).collect()
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map(|column| column.len()).unwrap_or(0)
+    }
+
+    pub fn record_batch(&self) -> Result<RecordBatch, MetaStatsError> {
+        RecordBatch::try_new(Arc::new(Schema::new(self.fields.clone())), self.columns.clone())
+            .map_err(|e| MetaStatsError::Parse(format!("Failed to build Arrow table: {}", e)))
+    }
+
+    /// The first rows as an HTML table, for Jupyter's `_repr_html_`.
+    pub fn html(&self, caption: &str) -> Result<String, MetaStatsError> {
+        let options = FormatOptions::default();
+        let formatters = self
+            .columns
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| MetaStatsError::Parse(format!("Failed to format Arrow table: {}", e)))?;
+        let rows = (0..self.num_rows())
+            .map(|row| formatters.iter().map(|formatter| formatter.value(row).to_string()).collect())
+            .collect();
+        let columns: Vec<String> = self.column_names().into_iter().map(str::to_string).collect();
+        Ok(protocol::html_table(caption, &columns, rows))
+    }
+
+    /// The table as a struct array in the Arrow C data interface, ready to hand to another
+    /// Arrow implementation without copying the buffers.
+    fn to_ffi(&self) -> Result<(FFI_ArrowArray, FFI_ArrowSchema), MetaStatsError> {
+        let array = StructArray::from(self.record_batch()?);
+        to_ffi(&array.to_data()).map_err(|e| MetaStatsError::Parse(format!("Failed to export Arrow table: {}", e)))
+    }
+}
+
+/// Types with a columnar form. Implemented next to each model, where its fields are visible.
+pub trait Tabular: Sized {
+    fn table(rows: &[Self]) -> Table;
+}
+
+fn import<'py>(py: Python<'py>, module: &str, method: &str) -> PyResult<&'py PyModule> {
+    py.import(module)
+        .map_err(|_| PyImportError::new_err(format!("{}() needs the optional '{}' package; install it with pip install {}", method, module, module)))
+}
+
+/// A `pyarrow.RecordBatch` with the table's columns, imported through the C data interface.
+pub fn to_arrow(py: Python<'_>, table: &Table) -> PyResult<PyObject> {
+    let pyarrow = import(py, "pyarrow", "to_arrow")?;
+    let (array, schema) = table.to_ffi()?;
+    // pyarrow moves the data out of both structs; dropping them afterwards releases nothing.
+    let batch = pyarrow
+        .getattr("RecordBatch")?
+        .call_method1("_import_from_c", (&array as *const FFI_ArrowArray as usize, &schema as *const FFI_ArrowSchema as usize))?;
+    Ok(batch.into())
+}
+
+pub fn to_pandas(py: Python<'_>, table: &Table) -> PyResult<PyObject> {
+    import(py, "pandas", "to_pandas")?;
+    to_arrow(py, table)?.call_method0(py, "to_pandas")
+}
+
+pub fn to_polars(py: Python<'_>, table: &Table) -> PyResult<PyObject> {
+    let polars = import(py, "polars", "to_polars")?;
+    Ok(polars.call_method1("from_arrow", (to_arrow(py, table)?,))?.into())
+}
+
+/// The `(schema, array)` capsule pair of the Arrow PyCapsule interface, so that pyarrow,
+/// Polars, DuckDB and other Arrow consumers can read a collection directly.
+pub fn arrow_c_array(py: Python<'_>, table: &Table) -> PyResult<(PyObject, PyObject)> {
+    let (array, schema) = table.to_ffi()?;
+    let schema_name = CString::new("arrow_schema").expect("capsule name has no NUL byte");
+    let array_name = CString::new("arrow_array").expect("capsule name has no NUL byte");
+    Ok((PyCapsule::new(py, schema, Some(schema_name))?.into(), PyCapsule::new(py, array, Some(array_name))?.into()))
+}
+
+/// Results and lists with a columnar form, which `export_methods!` gives `to_arrow`,
+/// `to_pandas`, `to_polars` and the Arrow PyCapsule interface.
+pub trait Export {
+    /// The rows, or a `ValueError` when there are none to export.
+    fn table(&self) -> PyResult<Table>;
+}
+
+/// A `#[pymethods]` block with the export methods of an `Export` type, followed by the type's
+/// own methods. `export_methods!(model X { ... })` and `export_methods!(frozen model X { ... })`
+/// put them in `X`'s `model_methods!` block instead, since pyo3 allows one block per class.
+macro_rules! export_methods {
+    ($model:ident { $($methods:tt)* }) => {
+        crate::export::export_methods!(@methods pymethods $model { $($methods)* });
+    };
+    (model $model:ident { $($methods:tt)* }) => {
+        crate::export::export_methods!(@methods model $model { $($methods)* });
+    };
+    (frozen model $model:ident { $($methods:tt)* }) => {
+        crate::export::export_methods!(@methods frozen $model { $($methods)* });
+    };
+    (@methods $block:ident $model:ident { $($methods:tt)* }) => {
+        crate::export::export_methods!(@block $block $model {
+            /// The rows as a `pyarrow.RecordBatch`.
+            fn to_arrow(&self, py: Python<'_>) -> PyResult<PyObject> {
+                crate::export::to_arrow(py, &crate::export::Export::table(self)?)
+            }
+
+            fn to_pandas(&self, py: Python<'_>) -> PyResult<PyObject> {
+                crate::export::to_pandas(py, &crate::export::Export::table(self)?)
+            }
+
+            fn to_polars(&self, py: Python<'_>) -> PyResult<PyObject> {
+                crate::export::to_polars(py, &crate::export::Export::table(self)?)
+            }
+
+            #[pyo3(signature = (requested_schema=None))]
+            fn __arrow_c_array__(&self, py: Python<'_>, requested_schema: Option<PyObject>) -> PyResult<(PyObject, PyObject)> {
+                let _ = requested_schema;
+                crate::export::arrow_c_array(py, &crate::export::Export::table(self)?)
+            }
+
+            $($methods)*
+        });
+    };
+    (@block pymethods $model:ident { $($methods:tt)* }) => {
+        #[pymethods]
+        impl $model {
+            $($methods)*
+        }
+    };
+    (@block model $model:ident { $($methods:tt)* }) => {
+        crate::protocol::model_methods!($model { $($methods)* });
+    };
+    (@block frozen $model:ident { $($methods:tt)* }) => {
+        crate::protocol::model_methods!(frozen $model { $($methods)* });
+    };
+}
+
+pub(crate) use export_methods;
+
+/// The table for a result's rows, or a `ValueError` carrying the Meta error when the request
+/// failed and there are no rows to export.
+pub fn result_table<T: Tabular>(rows: Option<&[T]>, error: Option<&str>) -> PyResult<Table> {
+    match rows {
+        Some(rows) => Ok(T::table(rows)),
+        None => Err(PyValueError::new_err(format!("Cannot export a failed result: {}", error.unwrap_or("no data")))),
+    }
+}
+
+/// Any list of results the module-level functions can export.
+#[derive(FromPyObject)]
+pub enum Rows {
+    Posts(Vec<PostInfo>),
+    Comments(Vec<CommentNode>),
+    Events(Vec<PageEvent>),
+    Videos(Vec<PageVideo>),
+    TimelineMedia(Vec<MediaNode>),
+    FeedMedia(Vec<FeedMedia>),
+    Engagement(Vec<PostEngagement>),
+    Snapshots(Vec<Snapshot>),
+    Pages(Vec<PageEngagement>),
+    Profiles(Vec<InstagramPageInfo>),
+}
+
+/// The `kind` names of the `Rows` variants, in order.
+const KINDS: [&str; 10] = ["posts", "comments", "events", "videos", "timeline_media", "feed_media", "engagement", "snapshots", "pages", "profiles"];
+
+impl Rows {
+    /// Empty rows of the named kind.
+    fn empty(kind: &str) -> Result<Rows, MetaStatsError> {
+        match kind {
+            "posts" => Ok(Rows::Posts(Vec::new())),
+            "comments" => Ok(Rows::Comments(Vec::new())),
+            "events" => Ok(Rows::Events(Vec::new())),
+            "videos" => Ok(Rows::Videos(Vec::new())),
+            "timeline_media" => Ok(Rows::TimelineMedia(Vec::new())),
+            "feed_media" => Ok(Rows::FeedMedia(Vec::new())),
+            "engagement" => Ok(Rows::Engagement(Vec::new())),
+            "snapshots" => Ok(Rows::Snapshots(Vec::new())),
+            "pages" => Ok(Rows::Pages(Vec::new())),
+            "profiles" => Ok(Rows::Profiles(Vec::new())),
+            other => Err(MetaStatsError::InvalidArgument(format!("kind must be one of {}, got '{}'", KINDS.join(", "), other))),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        let index = match self {
+            Rows::Posts(_) => 0,
+            Rows::Comments(_) => 1,
+            Rows::Events(_) => 2,
+            Rows::Videos(_) => 3,
+            Rows::TimelineMedia(_) => 4,
+            Rows::FeedMedia(_) => 5,
+            Rows::Engagement(_) => 6,
+            Rows::Snapshots(_) => 7,
+            Rows::Pages(_) => 8,
+            Rows::Profiles(_) => 9,
+        };
+        KINDS[index]
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Rows::Posts(rows) => rows.is_empty(),
+            Rows::Comments(rows) => rows.is_empty(),
+            Rows::Events(rows) => rows.is_empty(),
+            Rows::Videos(rows) => rows.is_empty(),
+            Rows::TimelineMedia(rows) => rows.is_empty(),
+            Rows::FeedMedia(rows) => rows.is_empty(),
+            Rows::Engagement(rows) => rows.is_empty(),
+            Rows::Snapshots(rows) => rows.is_empty(),
+            Rows::Pages(rows) => rows.is_empty(),
+            Rows::Profiles(rows) => rows.is_empty(),
+        }
+    }
+
+    /// Checks the rows against `kind`. An empty list has no items to tell what it holds, so it
+    /// needs a `kind` to get the right columns.
+    pub fn of_kind(self, kind: Option<&str>) -> Result<Rows, MetaStatsError> {
+        match (kind, self.is_empty()) {
+            (None, true) => Err(MetaStatsError::InvalidArgument(format!(
+                "Cannot tell what an empty list holds; pass kind as one of {}",
+                KINDS.join(", ")
+            ))),
+            (None, false) => Ok(self),
+            (Some(kind), true) => Rows::empty(kind),
+            (Some(kind), false) if kind == self.kind() => Ok(self),
+            (Some(kind), false) => Err(MetaStatsError::InvalidArgument(format!("Expected {} but the list holds {}", kind, self.kind()))),
+        }
+    }
+
+    pub fn table(&self) -> Table {
+        match self {
+            Rows::Posts(rows) => PostInfo::table(rows),
+            Rows::Comments(rows) => CommentNode::table(rows),
+            Rows::Events(rows) => PageEvent::table(rows),
+            Rows::Videos(rows) => PageVideo::table(rows),
+            Rows::TimelineMedia(rows) => MediaNode::table(rows),
+            Rows::FeedMedia(rows) => FeedMedia::table(rows),
+            Rows::Engagement(rows) => PostEngagement::table(rows),
+            Rows::Snapshots(rows) => Snapshot::table(rows),
+            Rows::Pages(rows) => PageEngagement::table(rows),
+            Rows::Profiles(rows) => InstagramPageInfo::table(rows),
+        }
+    }
+}
+
+/// Exports a list of posts, comments, events, videos, media, post engagement, snapshots, pages or
+/// profiles, such as those returned by `CrawlJob.facebook_posts` or `SnapshotStore.history`.
+/// `kind` names what the list holds (`posts`, `comments`, `events`, `videos`, `timeline_media`,
+/// `feed_media`, `engagement`, `snapshots`, `pages` or `profiles`); it is required for an empty
+/// list, whose columns could otherwise be any of these.
+#[pyfunction]
+#[pyo3(name = "to_arrow")]
+pub fn py_to_arrow(py: Python<'_>, rows: Rows, kind: Option<&str>) -> PyResult<PyObject> {
+    to_arrow(py, &rows.of_kind(kind)?.table())
+}
+
+#[pyfunction]
+#[pyo3(name = "to_pandas")]
+pub fn py_to_pandas(py: Python<'_>, rows: Rows, kind: Option<&str>) -> PyResult<PyObject> {
+    to_pandas(py, &rows.of_kind(kind)?.table())
+}
+
+#[pyfunction]
+#[pyo3(name = "to_polars")]
+pub fn py_to_polars(py: Python<'_>, rows: Rows, kind: Option<&str>) -> PyResult<PyObject> {
+    to_polars(py, &rows.of_kind(kind)?.table())
+}
+
+/// Names of the columns a list would be exported with.
+#[pyfunction]
+pub fn columns(rows: Rows, kind: Option<&str>) -> PyResult<Vec<String>> {
+    Ok(rows.of_kind(kind)?.table().column_names().into_iter().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int64Type;
+
+    #[test]
+    fn builds_nullable_columns() {
+        let table = Table::new()
+            .utf8("id", [Some("1"), Some("2")])
+            .int64("likes", [Some(10), None])
+            .boolean("is_video", [Some(false), Some(true)]);
+        let batch = table.record_batch().unwrap();
+
+        assert_eq!(table.column_names(), vec!["id", "likes", "is_video"]);
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(1).as_primitive::<Int64Type>().value(0), 10);
+        assert!(batch.column(1).is_null(1));
+    }
+
+    #[test]
+    fn mismatched_lengths_are_an_error() {
+        let table = Table::new().utf8("id", [Some("1")]).int64("likes", [Some(1), Some(2)]);
+        assert!(table.record_batch().is_err());
+    }
+
+    #[test]
+    fn exports_through_the_c_data_interface() {
+        let table = Table::new().utf8("id", [Some("a"), None]).float64("rate", [Some(1.5), Some(2.5)]);
+        let (array, schema) = table.to_ffi().unwrap();
+        let imported = unsafe { arrow_array::ffi::from_ffi(array, &schema) }.unwrap();
+        let imported = StructArray::from(imported);
+
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported.column_names(), vec!["id", "rate"]);
+        assert_eq!(imported.column(0).as_string::<i32>().value(0), "a");
+    }
+}
@@ -1,13 +1,25 @@
 use crate::auth;
+use crate::cache::CacheStatus;
+use crate::config::MetaConfig;
+use crate::error::MetaStatsError;
+use crate::export::{self, export_methods, Export, Table, Tabular};
+use crate::handle::{FacebookPageRef, PageArg};
+use crate::http::{self, HttpClient};
 use crate::meta;
+use crate::protocol::{self, model_methods, Repr};
+use crate::snapshot::{self, SnapshotStore};
+use crate::timestamp::{self, TimeArg};
+use crate::version::GraphVersion;
+use chrono::{DateTime, SecondsFormat, Utc};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 //use pyo3::types::{PyDict, PyList};
-use pyo3::exceptions::PyValueError;
-use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct BasicPageInfo {
     #[pyo3(get)]
     #[serde(default)]
@@ -17,8 +29,8 @@ pub struct BasicPageInfo {
     name: String,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct PageCategory {
     #[pyo3(get)]
     #[serde(default)]
@@ -28,8 +40,8 @@ pub struct PageCategory {
     name: String,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct PageEngagement {
     #[pyo3(get)]
     #[serde(default)]
@@ -58,25 +70,74 @@ pub struct PageEngagement {
 }
 
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommentParent {
+    #[pyo3(get)]
+    id: String,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct CommentInfo {
     #[pyo3(get)]
     message: Option<String>,
     #[pyo3(get)]
-    created_time: Option<String>,
+    #[serde(default, with = "timestamp::graph_option")]
+    created_time: Option<DateTime<Utc>>,
+    #[pyo3(get)]
+    id: Option<String>,
+    #[pyo3(get)]
+    like_count: Option<i32>,
+    #[pyo3(get)]
+    comment_count: Option<i32>,
+    #[pyo3(get)]
+    parent: Option<CommentParent>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommentNode {
+    #[pyo3(get)]
+    comment: CommentInfo,
+    #[pyo3(get)]
+    replies: Vec<CommentNode>,
+}
+
+impl PageEngagement {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn followers_count(&self) -> i64 {
+        i64::from(self.followers_count)
+    }
+
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+
+    pub fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("followers_count", f64::from(self.followers_count)),
+            ("fan_count", f64::from(self.fan_count)),
+            ("talking_about_count", f64::from(self.talking_about_count)),
+            ("rating_count", f64::from(self.rating_count)),
+            ("overall_star_rating", f64::from(self.overall_star_rating)),
+        ]
+    }
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct LikeInfo {
     #[pyo3(get)]
     id: Option<String>,
 }
 
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct LikeSummary {
     #[pyo3(get)]
     #[serde(default)]
@@ -90,8 +151,8 @@ pub struct LikeSummary {
 }
 
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct CommentSummary {
     #[pyo3(get)]
     #[serde(default)]
@@ -104,8 +165,8 @@ pub struct CommentSummary {
     can_comment: bool,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct BasicCommentsInfo {
     #[pyo3(get)]
     #[serde(default)]
@@ -117,8 +178,8 @@ pub struct BasicCommentsInfo {
     summary: CommentSummary,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct BasicLikesInfo {
     #[pyo3(get)]
     #[serde(default)]
@@ -131,8 +192,56 @@ pub struct BasicLikesInfo {
 }
 
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PostShares {
+    #[pyo3(get)]
+    #[serde(default)]
+    count: i32,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PostAttachment {
+    #[pyo3(get)]
+    media_type: Option<String>,
+    #[pyo3(get)]
+    url: Option<String>,
+    #[pyo3(get)]
+    title: Option<String>,
+    #[pyo3(get)]
+    subattachments: Option<PostAttachments>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PostAttachments {
+    #[pyo3(get)]
+    #[serde(default)]
+    data: Vec<PostAttachment>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MessageTag {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    #[serde(default)]
+    name: String,
+    #[pyo3(get)]
+    r#type: Option<String>,
+    #[pyo3(get)]
+    #[serde(default)]
+    offset: i32,
+    #[pyo3(get)]
+    #[serde(default)]
+    length: i32,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct PostInfo {
     #[pyo3(get)]
     #[serde(default)]
@@ -140,19 +249,38 @@ pub struct PostInfo {
     #[pyo3(get)]
     message: Option<String>,
     #[pyo3(get)]
-    #[serde(default)]
-    created_time: String,
+    #[serde(default, with = "timestamp::graph_option")]
+    created_time: Option<DateTime<Utc>>,
     #[pyo3(get)]
     story: Option<String>,
     #[pyo3(get)]
     likes: Option<BasicLikesInfo>,
     #[pyo3(get)]
     comments: Option<BasicCommentsInfo>,
+    #[pyo3(get)]
+    permalink_url: Option<String>,
+    #[pyo3(get)]
+    shares: Option<PostShares>,
+    #[pyo3(get)]
+    status_type: Option<String>,
+    #[pyo3(get)]
+    full_picture: Option<String>,
+    #[pyo3(get)]
+    attachments: Option<PostAttachments>,
+    #[pyo3(get)]
+    is_published: Option<bool>,
+    #[pyo3(get)]
+    place: Option<Place>,
+    #[pyo3(get)]
+    message_tags: Option<Vec<MessageTag>>,
 }
 
+/// Post fields beyond the defaults, requested when a posts function is called with `extended_fields`.
+const POST_EXTENDED_FIELDS: &str = "permalink_url,shares,status_type,full_picture,attachments{media_type,url,title,subattachments},is_published,place,message_tags";
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct BasicPostsInfo {
     #[pyo3(get)]
     data: Vec<PostInfo>,
@@ -161,22 +289,592 @@ pub struct BasicPostsInfo {
 
 }
 
-#[pyclass]
-#[derive(Clone)]
+export_methods!(model BasicPostsInfo {
+    fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        protocol::iter(py, &self.data)
+    }
+
+    /// An item by position, or a list of items for a slice.
+    fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+        protocol::get_item(py, &self.data, index)
+    }
+});
+
+impl Export for BasicPostsInfo {
+    fn table(&self) -> PyResult<Table> {
+        Ok(PostInfo::table(&self.data))
+    }
+}
+
+impl BasicPostsInfo {
+    pub fn data(&self) -> &[PostInfo] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<PostInfo> {
+        self.data
+    }
+
+    pub fn next_url(&self) -> Option<&str> {
+        self.paging.next_url()
+    }
+}
+
+impl PostInfo {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Total likes, when the posts were requested with `likes.summary(true)`.
+    pub fn like_count(&self) -> Option<i64> {
+        self.likes.as_ref().map(|likes| i64::from(likes.summary.total_count))
+    }
+
+    /// Total comments, when the posts were requested with `comments.summary(true)`.
+    pub fn comment_count(&self) -> Option<i64> {
+        self.comments.as_ref().map(|comments| i64::from(comments.summary.total_count))
+    }
+
+    pub fn share_count(&self) -> Option<i64> {
+        self.shares.as_ref().map(|shares| i64::from(shares.count))
+    }
+
+    /// Like, comment and share totals, for the ones present in the response.
+    pub fn metrics(&self) -> Vec<(&'static str, f64)> {
+        [("likes", self.like_count()), ("comments", self.comment_count()), ("shares", self.share_count())]
+            .into_iter()
+            .filter_map(|(metric, count)| count.map(|count| (metric, count as f64)))
+            .collect()
+    }
+
+    pub fn created_time(&self) -> Option<DateTime<Utc>> {
+        self.created_time
+    }
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PlaceLocation {
+    #[pyo3(get)]
+    city: Option<String>,
+    #[pyo3(get)]
+    country: Option<String>,
+    #[pyo3(get)]
+    latitude: Option<f64>,
+    #[pyo3(get)]
+    longitude: Option<f64>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Place {
+    #[pyo3(get)]
+    id: Option<String>,
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    location: Option<PlaceLocation>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PageEvent {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    #[serde(default)]
+    name: String,
+    #[pyo3(get)]
+    description: Option<String>,
+    #[pyo3(get)]
+    #[serde(default, with = "timestamp::graph_option")]
+    start_time: Option<DateTime<Utc>>,
+    #[pyo3(get)]
+    #[serde(default, with = "timestamp::graph_option")]
+    end_time: Option<DateTime<Utc>>,
+    #[pyo3(get)]
+    timezone: Option<String>,
+    #[pyo3(get)]
+    place: Option<Place>,
+    #[pyo3(get)]
+    ticket_uri: Option<String>,
+    #[pyo3(get)]
+    #[serde(default)]
+    attending_count: i32,
+    #[pyo3(get)]
+    #[serde(default)]
+    interested_count: i32,
+    #[pyo3(get)]
+    #[serde(default)]
+    maybe_count: i32,
+    #[pyo3(get)]
+    #[serde(default)]
+    is_canceled: bool,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct VideoInsights {
+    #[pyo3(get)]
+    total_video_views: Option<i64>,
+    #[pyo3(get)]
+    total_video_impressions: Option<i64>,
+    #[pyo3(get)]
+    total_video_complete_views: Option<i64>,
+    #[pyo3(get)]
+    total_video_avg_time_watched: Option<i64>,
+    #[pyo3(get)]
+    total_video_view_total_time: Option<i64>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PageVideo {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    title: Option<String>,
+    #[pyo3(get)]
+    description: Option<String>,
+    #[pyo3(get)]
+    length: Option<f64>,
+    #[pyo3(get)]
+    #[serde(default, with = "timestamp::graph_option")]
+    created_time: Option<DateTime<Utc>>,
+    #[pyo3(get)]
+    permalink_url: Option<String>,
+    #[pyo3(get)]
+    views: Option<i64>,
+    #[pyo3(get)]
+    insights: Option<VideoInsights>,
+}
+
+#[derive(Deserialize)]
+struct InsightValue {
+    value: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct InsightMetric {
+    name: String,
+    #[serde(default)]
+    values: Vec<InsightValue>,
+}
+
+impl VideoInsights {
+    const METRICS: &'static str = "total_video_views,total_video_impressions,total_video_complete_views,total_video_avg_time_watched,total_video_view_total_time";
+
+    fn from_metrics(metrics: Vec<InsightMetric>) -> Self {
+        let mut insights = VideoInsights::default();
+
+        for metric in metrics {
+            let value = metric.values.first().and_then(|v| v.value.as_i64());
+            match metric.name.as_str() {
+                "total_video_views" => insights.total_video_views = value,
+                "total_video_impressions" => insights.total_video_impressions = value,
+                "total_video_complete_views" => insights.total_video_complete_views = value,
+                "total_video_avg_time_watched" => insights.total_video_avg_time_watched = value,
+                "total_video_view_total_time" => insights.total_video_view_total_time = value,
+                _ => {}
+            }
+        }
+
+        insights
+    }
+}
+
+model_methods!(
+    BasicPageInfo,
+    PageCategory,
+    PageEngagement,
+    CommentParent,
+    CommentInfo,
+    CommentNode,
+    LikeInfo,
+    LikeSummary,
+    CommentSummary,
+    PostShares,
+    PostAttachment,
+    MessageTag,
+    PostInfo,
+    PlaceLocation,
+    Place,
+    PageEvent,
+    VideoInsights,
+    PageVideo,
+    InstagramBusinessAccount,
+    PageLinks,
+);
+
+impl Repr for BasicPageInfo {
+    fn repr(&self) -> String {
+        format!("BasicPageInfo({}, {})", self.id, self.name)
+    }
+}
+
+impl Repr for PageCategory {
+    fn repr(&self) -> String {
+        format!("PageCategory({})", self.name)
+    }
+}
+
+impl Repr for PageEngagement {
+    fn repr(&self) -> String {
+        format!("PageEngagement({}, {}, {})", self.id, protocol::count(self.followers_count, "follower"), protocol::count(self.fan_count, "fan"))
+    }
+}
+
+impl Repr for CommentParent {
+    fn repr(&self) -> String {
+        format!("CommentParent({})", self.id)
+    }
+}
+
+impl Repr for CommentInfo {
+    fn repr(&self) -> String {
+        let message = protocol::truncate(self.message.as_deref().unwrap_or_default(), 40);
+        format!("CommentInfo({}, {:?})", self.id.as_deref().unwrap_or("?"), message)
+    }
+}
+
+impl Repr for CommentNode {
+    fn repr(&self) -> String {
+        format!("CommentNode({}, {})", self.comment.id.as_deref().unwrap_or("?"), protocol::count(self.replies.len(), "reply"))
+    }
+}
+
+impl Repr for LikeInfo {
+    fn repr(&self) -> String {
+        format!("LikeInfo({})", self.id.as_deref().unwrap_or("?"))
+    }
+}
+
+impl Repr for LikeSummary {
+    fn repr(&self) -> String {
+        format!("LikeSummary({})", protocol::count(self.total_count, "like"))
+    }
+}
+
+impl Repr for CommentSummary {
+    fn repr(&self) -> String {
+        format!("CommentSummary({})", protocol::count(self.total_count, "comment"))
+    }
+}
+
+impl Repr for BasicCommentsInfo {
+    fn repr(&self) -> String {
+        format!("BasicCommentsInfo({} of {})", self.data.len(), protocol::count(self.summary.total_count, "comment"))
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        protocol::rows_html("BasicCommentsInfo", &self.data)
+    }
+}
+
+impl Repr for BasicLikesInfo {
+    fn repr(&self) -> String {
+        format!("BasicLikesInfo({} of {})", self.data.len(), protocol::count(self.summary.total_count, "like"))
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        protocol::rows_html("BasicLikesInfo", &self.data)
+    }
+}
+
+impl Repr for PostShares {
+    fn repr(&self) -> String {
+        format!("PostShares({})", self.count)
+    }
+}
+
+impl Repr for PostAttachment {
+    fn repr(&self) -> String {
+        let title = protocol::truncate(self.title.as_deref().unwrap_or_default(), 40);
+        format!("PostAttachment({}, {:?})", self.media_type.as_deref().unwrap_or("?"), title)
+    }
+}
+
+impl Repr for PostAttachments {
+    fn repr(&self) -> String {
+        format!("PostAttachments({})", protocol::count(self.data.len(), "attachment"))
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        protocol::rows_html("PostAttachments", &self.data)
+    }
+}
+
+impl Repr for MessageTag {
+    fn repr(&self) -> String {
+        format!("MessageTag({}, {})", self.name, self.id)
+    }
+}
+
+impl Repr for PostInfo {
+    fn repr(&self) -> String {
+        let text = protocol::truncate(self.message.as_deref().or(self.story.as_deref()).unwrap_or_default(), 40);
+        let created_time = self.created_time.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true)).unwrap_or_default();
+        format!("PostInfo({}, {}, {:?})", self.id, created_time, text)
+    }
+}
+
+impl Repr for BasicPostsInfo {
+    fn repr(&self) -> String {
+        match self.paging.next_url() {
+            Some(_) => format!("BasicPostsInfo({}, more pages)", protocol::count(self.data.len(), "post")),
+            None => format!("BasicPostsInfo({})", protocol::count(self.data.len(), "post")),
+        }
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        Ok(self.table()?.html("BasicPostsInfo")?)
+    }
+}
+
+impl Repr for PlaceLocation {
+    fn repr(&self) -> String {
+        format!("PlaceLocation({}, {})", self.city.as_deref().unwrap_or("?"), self.country.as_deref().unwrap_or("?"))
+    }
+}
+
+impl Repr for Place {
+    fn repr(&self) -> String {
+        format!("Place({})", self.name.as_deref().unwrap_or("?"))
+    }
+}
+
+impl Repr for PageEvent {
+    fn repr(&self) -> String {
+        let name = protocol::truncate(&self.name, 40);
+        let start_time = self.start_time.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true)).unwrap_or_else(|| "?".to_string());
+        match self.is_canceled {
+            true => format!("PageEvent({}, {:?}, {}, canceled)", self.id, name, start_time),
+            false => format!("PageEvent({}, {:?}, {})", self.id, name, start_time),
+        }
+    }
+}
+
+impl Repr for VideoInsights {
+    fn repr(&self) -> String {
+        format!("VideoInsights({})", protocol::count(self.total_video_views.unwrap_or_default(), "view"))
+    }
+}
+
+impl Repr for PageVideo {
+    fn repr(&self) -> String {
+        let title = protocol::truncate(self.title.as_deref().unwrap_or_default(), 40);
+        format!("PageVideo({}, {:?}, {})", self.id, title, protocol::count(self.views.unwrap_or_default(), "view"))
+    }
+}
+
+impl Repr for InstagramBusinessAccount {
+    fn repr(&self) -> String {
+        format!("InstagramBusinessAccount({}, {})", self.id, self.username.as_deref().unwrap_or("?"))
+    }
+}
+
+impl Repr for PageLinks {
+    fn repr(&self) -> String {
+        let instagram = self.instagram_business_account.as_ref().or(self.connected_instagram_account.as_ref());
+        match instagram.and_then(|account| account.username.as_deref()) {
+            Some(username) => format!("PageLinks({}, {}, instagram={})", self.id, self.name, username),
+            None => format!("PageLinks({}, {})", self.id, self.name),
+        }
+    }
+}
+
+model_methods!(BasicCommentsInfo {
+    fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        protocol::iter(py, &self.data)
+    }
+
+    /// An item by position, or a list of items for a slice.
+    fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+        protocol::get_item(py, &self.data, index)
+    }
+});
+
+model_methods!(BasicLikesInfo {
+    fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        protocol::iter(py, &self.data)
+    }
+
+    /// An item by position, or a list of items for a slice.
+    fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+        protocol::get_item(py, &self.data, index)
+    }
+});
+
+model_methods!(PostAttachments {
+    fn __len__(&self) -> usize {
+        self.data.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        protocol::iter(py, &self.data)
+    }
+
+    /// An item by position, or a list of items for a slice.
+    fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+        protocol::get_item(py, &self.data, index)
+    }
+});
+
+impl Tabular for PageEngagement {
+    fn table(rows: &[PageEngagement]) -> Table {
+        Table::new()
+            .utf8("id", rows.iter().map(|page| Some(&page.id)))
+            .utf8("category", rows.iter().map(|page| Some(&page.category)))
+            .int64("followers_count", rows.iter().map(|page| Some(i64::from(page.followers_count))))
+            .int64("fan_count", rows.iter().map(|page| Some(i64::from(page.fan_count))))
+            .int64("talking_about_count", rows.iter().map(|page| Some(i64::from(page.talking_about_count))))
+            .int64("rating_count", rows.iter().map(|page| Some(i64::from(page.rating_count))))
+            .float64("overall_star_rating", rows.iter().map(|page| Some(f64::from(page.overall_star_rating))))
+    }
+}
+
+impl Tabular for PostInfo {
+    fn table(rows: &[PostInfo]) -> Table {
+        fn place(post: &PostInfo) -> Option<&Place> {
+            post.place.as_ref()
+        }
+
+        Table::new()
+            .utf8("id", rows.iter().map(|post| Some(&post.id)))
+            .graph_time("created_time", rows.iter().map(|post| post.created_time))
+            .utf8("message", rows.iter().map(|post| post.message.as_ref()))
+            .utf8("story", rows.iter().map(|post| post.story.as_ref()))
+            .int64("like_count", rows.iter().map(PostInfo::like_count))
+            .int64("comment_count", rows.iter().map(PostInfo::comment_count))
+            .int64("share_count", rows.iter().map(PostInfo::share_count))
+            .utf8("permalink_url", rows.iter().map(|post| post.permalink_url.as_ref()))
+            .utf8("status_type", rows.iter().map(|post| post.status_type.as_ref()))
+            .utf8("full_picture", rows.iter().map(|post| post.full_picture.as_ref()))
+            .boolean("is_published", rows.iter().map(|post| post.is_published))
+            .utf8("place_id", rows.iter().map(|post| place(post).and_then(|place| place.id.as_ref())))
+            .utf8("place_name", rows.iter().map(|post| place(post).and_then(|place| place.name.as_ref())))
+            .timestamp("created_time_utc", rows.iter().map(|post| post.created_time))
+    }
+}
+
+/// Comments flattened depth-first, so each reply follows its parent; `depth` is 0 for
+/// top-level comments.
+impl Tabular for CommentNode {
+    fn table(rows: &[CommentNode]) -> Table {
+        fn walk<'a>(nodes: &'a [CommentNode], parent: Option<&'a str>, depth: i64, out: &mut Vec<(&'a CommentInfo, Option<&'a str>, i64)>) {
+            for node in nodes {
+                out.push((&node.comment, parent, depth));
+                walk(&node.replies, node.comment.id.as_deref(), depth + 1, out);
+            }
+        }
+
+        let mut comments = Vec::new();
+        walk(rows, None, 0, &mut comments);
+
+        Table::new()
+            .utf8("id", comments.iter().map(|(comment, _, _)| comment.id.as_ref()))
+            .utf8("parent_id", comments.iter().map(|(comment, parent, _)| parent.or(comment.parent.as_ref().map(|parent| parent.id.as_str()))))
+            .int64("depth", comments.iter().map(|(_, _, depth)| Some(*depth)))
+            .graph_time("created_time", comments.iter().map(|(comment, _, _)| comment.created_time))
+            .utf8("message", comments.iter().map(|(comment, _, _)| comment.message.as_ref()))
+            .int64("like_count", comments.iter().map(|(comment, _, _)| comment.like_count.map(i64::from)))
+            .int64("comment_count", comments.iter().map(|(comment, _, _)| comment.comment_count.map(i64::from)))
+            .timestamp("created_time_utc", comments.iter().map(|(comment, _, _)| comment.created_time))
+    }
+}
+
+impl Tabular for PageEvent {
+    fn table(rows: &[PageEvent]) -> Table {
+        fn place(event: &PageEvent) -> Option<&Place> {
+            event.place.as_ref()
+        }
+        fn location(event: &PageEvent) -> Option<&PlaceLocation> {
+            event.place.as_ref().and_then(|place| place.location.as_ref())
+        }
+
+        Table::new()
+            .utf8("id", rows.iter().map(|event| Some(&event.id)))
+            .utf8("name", rows.iter().map(|event| Some(&event.name)))
+            .utf8("description", rows.iter().map(|event| event.description.as_ref()))
+            .graph_time("start_time", rows.iter().map(|event| event.start_time))
+            .graph_time("end_time", rows.iter().map(|event| event.end_time))
+            .utf8("timezone", rows.iter().map(|event| event.timezone.as_ref()))
+            .utf8("place_name", rows.iter().map(|event| place(event).and_then(|place| place.name.as_ref())))
+            .utf8("city", rows.iter().map(|event| location(event).and_then(|location| location.city.as_ref())))
+            .utf8("country", rows.iter().map(|event| location(event).and_then(|location| location.country.as_ref())))
+            .float64("latitude", rows.iter().map(|event| location(event).and_then(|location| location.latitude)))
+            .float64("longitude", rows.iter().map(|event| location(event).and_then(|location| location.longitude)))
+            .utf8("ticket_uri", rows.iter().map(|event| event.ticket_uri.as_ref()))
+            .int64("attending_count", rows.iter().map(|event| Some(i64::from(event.attending_count))))
+            .int64("interested_count", rows.iter().map(|event| Some(i64::from(event.interested_count))))
+            .int64("maybe_count", rows.iter().map(|event| Some(i64::from(event.maybe_count))))
+            .boolean("is_canceled", rows.iter().map(|event| Some(event.is_canceled)))
+            .timestamp("start_time_utc", rows.iter().map(|event| event.start_time))
+            .timestamp("end_time_utc", rows.iter().map(|event| event.end_time))
+    }
+}
+
+/// Videos with their insights flattened into `total_video_*` columns, null when the insights
+/// were not requested or not available.
+impl Tabular for PageVideo {
+    fn table(rows: &[PageVideo]) -> Table {
+        let insight = |metric: fn(&VideoInsights) -> Option<i64>| rows.iter().map(move |video| video.insights.as_ref().and_then(metric));
+        Table::new()
+            .utf8("id", rows.iter().map(|video| Some(&video.id)))
+            .utf8("title", rows.iter().map(|video| video.title.as_ref()))
+            .utf8("description", rows.iter().map(|video| video.description.as_ref()))
+            .float64("length", rows.iter().map(|video| video.length))
+            .graph_time("created_time", rows.iter().map(|video| video.created_time))
+            .utf8("permalink_url", rows.iter().map(|video| video.permalink_url.as_ref()))
+            .int64("views", rows.iter().map(|video| video.views))
+            .int64("total_video_views", insight(|insights| insights.total_video_views))
+            .int64("total_video_impressions", insight(|insights| insights.total_video_impressions))
+            .int64("total_video_complete_views", insight(|insights| insights.total_video_complete_views))
+            .int64("total_video_avg_time_watched", insight(|insights| insights.total_video_avg_time_watched))
+            .int64("total_video_view_total_time", insight(|insights| insights.total_video_view_total_time))
+            .timestamp("created_time_utc", rows.iter().map(|video| video.created_time))
+    }
+}
+
+/// One page of a Graph API edge, used when following `paging.next` links.
+#[derive(Deserialize)]
+struct EdgePage<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+    paging: Option<meta::Paging>,
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct BasicPageInfoResult {
     is_success: bool,
     page_info: Option<BasicPageInfo>,
     error: Option<meta::MetaError>,
+    cache_status: Option<CacheStatus>,
 }
 
-#[pymethods]
-impl BasicPageInfoResult {
+model_methods!(BasicPageInfoResult {
     #[staticmethod]
     fn success(info: BasicPageInfo) -> Self {
         BasicPageInfoResult {
             is_success: true,
             page_info: Some(info),
             error: None,
+            cache_status: None,
         }
     }
 
@@ -186,6 +884,7 @@ impl BasicPageInfoResult {
             is_success: false,
             page_info: None,
             error: Some(err),
+            cache_status: None,
         }
     }
 
@@ -193,12 +892,12 @@ impl BasicPageInfoResult {
     fn is_success(&self) -> bool {
         self.is_success
     }
-    
+
     #[getter]
     fn is_error(&self) -> bool {
         !self.is_success
     }
-    
+
     #[getter]
     fn page_info(&self) -> Option<Py<BasicPageInfo>> {
         match &self.page_info {
@@ -215,41 +914,72 @@ impl BasicPageInfoResult {
         }
     }
 
-    fn __repr__(&self) -> PyResult<String> {
-        if self.is_success {
-            Ok("BasicPageInfoResult(Success)".to_string())
-        } else {
-            Ok("BasicPageInfoResult(Error)".to_string())
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for BasicPageInfoResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.page_info, &self.error) {
+            (Some(value), None) => value.repr(),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("BasicPageInfoResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.page_info, &self.error) {
+            (Some(value), None) => protocol::fields_html(value),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl BasicPageInfoResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn into_result(self) -> Result<BasicPageInfo, meta::MetaError> {
+        match (self.page_info, self.error) {
+            (Some(page_info), None) => Ok(page_info),
+            (_, error) => Err(error.unwrap_or_default()),
         }
     }
 }
 
-#[pyclass]
-#[derive(Clone)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct PageEngagementResult {
     is_success: bool,
     page_engagement: Option<PageEngagement>,
     error: Option<meta::MetaError>,
+    cache_status: Option<CacheStatus>,
 }
 
-#[pymethods]
-impl PageEngagementResult {
-
+model_methods!(PageEngagementResult {
     #[staticmethod]
     fn success(info: PageEngagement) -> Self {
         PageEngagementResult {
             is_success: true,
             page_engagement: Some(info),
             error: None,
+            cache_status: None,
         }
     }
 
     #[staticmethod]
-    fn error(err: meta::MetaError) -> Self {
+    pub fn error(err: meta::MetaError) -> Self {
         PageEngagementResult {
             is_success: false,
             page_engagement: None,
             error: Some(err),
+            cache_status: None,
         }
     }
 
@@ -279,41 +1009,81 @@ impl PageEngagementResult {
         }
     }
 
-    fn __repr__(&self) -> PyResult<String> {
-        if self.is_success {
-            Ok("PageEngagementResult(Success)".to_string())
-        } else {
-            Ok("PageEngagementResult(Error)".to_string())
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for PageEngagementResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.page_engagement, &self.error) {
+            (Some(value), None) => value.repr(),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("PageEngagementResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.page_engagement, &self.error) {
+            (Some(value), None) => protocol::fields_html(value),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl PageEngagementResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn meta_error(&self) -> Option<&meta::MetaError> {
+        self.error.as_ref()
+    }
+
+    pub fn page(&self) -> Option<&PageEngagement> {
+        self.page_engagement.as_ref()
+    }
+
+    pub fn into_result(self) -> Result<PageEngagement, meta::MetaError> {
+        match (self.page_engagement, self.error) {
+            (Some(page_engagement), None) => Ok(page_engagement),
+            (_, error) => Err(error.unwrap_or_default()),
         }
     }
 }
 
 
-#[pyclass]
-#[derive(Clone)]
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct BasicPostsInfoResult {
     is_success: bool,
     posts_info: Option<BasicPostsInfo>,
     error: Option<meta::MetaError>,
+    cache_status: Option<CacheStatus>,
 }
 
-#[pymethods]
-impl BasicPostsInfoResult {
+export_methods!(model BasicPostsInfoResult {
     #[staticmethod]
     fn success(info: BasicPostsInfo) -> Self {
         BasicPostsInfoResult {
             is_success: true,
             posts_info: Some(info),
             error: None,
+            cache_status: None,
         }
     }
- 
+
     #[staticmethod]
     fn error(err: meta::MetaError) -> Self {
         BasicPostsInfoResult {
             is_success: false,
             posts_info: None,
             error: Some(err),
+            cache_status: None,
         }
     }
 
@@ -321,12 +1091,12 @@ impl BasicPostsInfoResult {
     fn is_success(&self) -> bool {
         self.is_success
     }
-    
+
     #[getter]
     fn is_error(&self) -> bool {
         !self.is_success
     }
-    
+
     #[getter]
     fn posts_info(&self) -> Option<Py<BasicPostsInfo>> {
         match &self.posts_info {
@@ -343,135 +1113,505 @@ impl BasicPostsInfoResult {
         }
     }
 
-    fn __repr__(&self) -> PyResult<String> {
-        if self.is_success {
-            Ok("BasicPostsInfoResult(Success)".to_string())
-        } else {
-            Ok("BasicPostsInfoResult(Error)".to_string())
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for BasicPostsInfoResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.posts_info, &self.error) {
+            (Some(value), None) => value.repr(),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("BasicPostsInfoResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.posts_info, &self.error) {
+            (Some(_), None) => Ok(self.table()?.html("BasicPostsInfoResult")?),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl Export for BasicPostsInfoResult {
+    fn table(&self) -> PyResult<Table> {
+        export::result_table(self.posts_info.as_ref().map(|info| info.data.as_slice()), self.error.as_ref().map(meta::MetaError::message))
+    }
+}
+
+impl BasicPostsInfoResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn into_result(self) -> Result<BasicPostsInfo, meta::MetaError> {
+        match (self.posts_info, self.error) {
+            (Some(posts_info), None) => Ok(posts_info),
+            (_, error) => Err(error.unwrap_or_default()),
+        }
+    }
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageEventsResult {
+    is_success: bool,
+    events: Option<Vec<PageEvent>>,
+    error: Option<meta::MetaError>,
+    cache_status: Option<CacheStatus>,
+}
+
+export_methods!(model PageEventsResult {
+    #[staticmethod]
+    fn success(events: Vec<PageEvent>) -> Self {
+        PageEventsResult {
+            is_success: true,
+            events: Some(events),
+            error: None,
+            cache_status: None,
+        }
+    }
+
+    #[staticmethod]
+    fn error(err: meta::MetaError) -> Self {
+        PageEventsResult {
+            is_success: false,
+            events: None,
+            error: Some(err),
+            cache_status: None,
+        }
+    }
+
+    #[getter]
+    fn is_success(&self) -> bool {
+        self.is_success
+    }
+
+    #[getter]
+    fn is_error(&self) -> bool {
+        !self.is_success
+    }
+
+    #[getter]
+    fn events(&self) -> Option<Vec<PageEvent>> {
+        self.events.clone()
+    }
+
+    #[getter]
+    fn error_info(&self) -> Option<Py<meta::MetaError>> {
+        match &self.error {
+            Some(err) => Python::with_gil(|py| Some(Py::new(py, err.clone()).unwrap())),
+            None => None,
+        }
+    }
+
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for PageEventsResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.events, &self.error) {
+            (Some(value), None) => protocol::count(value.len(), "event"),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("PageEventsResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.events, &self.error) {
+            (Some(_), None) => Ok(self.table()?.html("PageEventsResult")?),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl Export for PageEventsResult {
+    fn table(&self) -> PyResult<Table> {
+        export::result_table(self.events.as_deref(), self.error.as_ref().map(meta::MetaError::message))
+    }
+}
+
+impl PageEventsResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn into_result(self) -> Result<Vec<PageEvent>, meta::MetaError> {
+        match (self.events, self.error) {
+            (Some(events), None) => Ok(events),
+            (_, error) => Err(error.unwrap_or_default()),
+        }
+    }
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageVideosResult {
+    is_success: bool,
+    videos: Option<Vec<PageVideo>>,
+    error: Option<meta::MetaError>,
+    cache_status: Option<CacheStatus>,
+}
+
+export_methods!(model PageVideosResult {
+    #[staticmethod]
+    fn success(videos: Vec<PageVideo>) -> Self {
+        PageVideosResult {
+            is_success: true,
+            videos: Some(videos),
+            error: None,
+            cache_status: None,
+        }
+    }
+
+    #[staticmethod]
+    fn error(err: meta::MetaError) -> Self {
+        PageVideosResult {
+            is_success: false,
+            videos: None,
+            error: Some(err),
+            cache_status: None,
+        }
+    }
+
+    #[getter]
+    fn is_success(&self) -> bool {
+        self.is_success
+    }
+
+    #[getter]
+    fn is_error(&self) -> bool {
+        !self.is_success
+    }
+
+    #[getter]
+    fn videos(&self) -> Option<Vec<PageVideo>> {
+        self.videos.clone()
+    }
+
+    #[getter]
+    fn error_info(&self) -> Option<Py<meta::MetaError>> {
+        match &self.error {
+            Some(err) => Python::with_gil(|py| Some(Py::new(py, err.clone()).unwrap())),
+            None => None,
+        }
+    }
+
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for PageVideosResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.videos, &self.error) {
+            (Some(value), None) => protocol::count(value.len(), "video"),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("PageVideosResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.videos, &self.error) {
+            (Some(_), None) => Ok(self.table()?.html("PageVideosResult")?),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl Export for PageVideosResult {
+    fn table(&self) -> PyResult<Table> {
+        export::result_table(self.videos.as_deref(), self.error.as_ref().map(meta::MetaError::message))
+    }
+}
+
+impl PageVideosResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn into_result(self) -> Result<Vec<PageVideo>, meta::MetaError> {
+        match (self.videos, self.error) {
+            (Some(videos), None) => Ok(videos),
+            (_, error) => Err(error.unwrap_or_default()),
+        }
+    }
+}
+
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostCommentsResult {
+    is_success: bool,
+    comments: Option<Vec<CommentNode>>,
+    error: Option<meta::MetaError>,
+    cache_status: Option<CacheStatus>,
+}
+
+export_methods!(model PostCommentsResult {
+    #[staticmethod]
+    fn success(comments: Vec<CommentNode>) -> Self {
+        PostCommentsResult {
+            is_success: true,
+            comments: Some(comments),
+            error: None,
+            cache_status: None,
+        }
+    }
+
+    #[staticmethod]
+    fn error(err: meta::MetaError) -> Self {
+        PostCommentsResult {
+            is_success: false,
+            comments: None,
+            error: Some(err),
+            cache_status: None,
+        }
+    }
+
+    #[getter]
+    fn is_success(&self) -> bool {
+        self.is_success
+    }
+
+    #[getter]
+    fn is_error(&self) -> bool {
+        !self.is_success
+    }
+
+    #[getter]
+    fn comments(&self) -> Option<Vec<CommentNode>> {
+        self.comments.clone()
+    }
+
+    #[getter]
+    fn error_info(&self) -> Option<Py<meta::MetaError>> {
+        match &self.error {
+            Some(err) => Python::with_gil(|py| Some(Py::new(py, err.clone()).unwrap())),
+            None => None,
+        }
+    }
+
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for PostCommentsResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.comments, &self.error) {
+            (Some(value), None) => protocol::count(value.len(), "comment thread"),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("PostCommentsResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.comments, &self.error) {
+            (Some(_), None) => Ok(self.table()?.html("PostCommentsResult")?),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl Export for PostCommentsResult {
+    fn table(&self) -> PyResult<Table> {
+        export::result_table(self.comments.as_deref(), self.error.as_ref().map(meta::MetaError::message))
+    }
+}
+
+impl PostCommentsResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn into_result(self) -> Result<Vec<CommentNode>, meta::MetaError> {
+        match (self.comments, self.error) {
+            (Some(comments), None) => Ok(comments),
+            (_, error) => Err(error.unwrap_or_default()),
+        }
+    }
+}
+
+/// Requests `url` and keeps following `paging.next` until the edge is exhausted,
+/// returning every `data` item, or the first Meta error encountered along the way.
+/// The cache status of each page request is merged into `cache_status`.
+fn fetch_all_pages<T: DeserializeOwned>(client: &HttpClient, url: String, label: &str, cache_status: &mut Option<CacheStatus>) -> Result<Result<Vec<T>, meta::MetaError>, MetaStatsError> {
+    let mut items = Vec::new();
+    let mut next = Some(url);
+
+    while let Some(url) = next {
+        let response = http::get(client, &url, &[], label)?;
+        *cache_status = CacheStatus::merge(*cache_status, response.cache_status);
+
+        let status = response.status;
+        let raw_text = response.text;
+
+        if let Ok(error) = serde_json::from_str::<meta::MetaError>(&raw_text) {
+            return Ok(Err(error));
         }
+
+        let page = serde_json::from_str::<EdgePage<T>>(&raw_text).map_err(|e| {
+            MetaStatsError::Parse(format!(
+                "Failed to parse response as either {} or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
+                label, status, e, raw_text
+            ))
+        })?;
+
+        items.extend(page.data);
+        next = page.paging.and_then(|paging| paging.next_url().map(str::to_string));
     }
-}
 
+    Ok(Ok(items))
+}
 
-#[pyfunction]
-pub fn get_facebook_page_info(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>) -> PyResult<Py<BasicPageInfoResult>> {
+pub fn fetch_facebook_page_info(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<BasicPageInfoResult, MetaStatsError> {
     let access_token = match access_token {
         Some(access_token) => access_token,
-        None => auth::get_meta_access_token(None, None, None, None, None)?,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(BasicPageInfoResult::error(error)),
+        },
     };
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| PyValueError::new_err(format!("Failed to create HTTP client for facebook page info: {}", e)))?;
+    let client = HttpClient::new(config)?;
 
 
 
-    let meta_version = match meta_version {
-        Some(meta_version) => meta_version,
-        None => auth::get_meta_version()?,
-    };
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
 
 
     match page_id {
         Some(page_id) => {
+            let page_id = FacebookPageRef::parse(&page_id)?;
 
-            let url = format!("https://graph.facebook.com/{}/{}?access_token={}", meta_version, page_id, access_token);
-            let res = client
-                .get(&url)
-                .send()
-                .map_err(|e| PyValueError::new_err(format!("Request failed for BasicPageInfo: {}", e)))?;
-            
-            let status = res.status();
-            let raw_text = res
-                .text()
-                .map_err(|e| PyValueError::new_err(format!("Failed to get response text for BasicPageInfo: {}", e)))?;
+            let url = format!("{}/{}/{}?access_token={}", config.graph_base_url(), meta_version, page_id, access_token);
+            let response = http::get(&client, &url, &[], "BasicPageInfo")?;
+            let status = response.status;
+            let raw_text = response.text;
             
             let error_result: Result<meta::MetaError, _> = serde_json::from_str(&raw_text);
             
             if let Ok(error) = error_result {
-                let result = BasicPageInfoResult::error(error);
-                return Python::with_gil(|py| {
-                    Py::new(py, result).map_err(|e| PyErr::from(e))
-                });
+                let result = BasicPageInfoResult::error(error).with_cache_status(response.cache_status);
+                return Ok(result);
             }
             
             match serde_json::from_str::<BasicPageInfo>(&raw_text) {
                 Ok(page_info) => {
-                    let result = BasicPageInfoResult::success(page_info);
-                    Python::with_gil(|py| {
-                        Py::new(py, result).map_err(|e| PyErr::from(e))
-                    })
+                    let result = BasicPageInfoResult::success(page_info).with_cache_status(response.cache_status);
+                    Ok(result)
                 },
                 Err(e) => {
                     eprintln!("Failed to parse Facebook response: {}", e);
                     eprintln!("Raw response: {}", raw_text);
                     
-                    Err(PyValueError::new_err(format!(
+                    Err(MetaStatsError::Parse(format!(
                         "Failed to parse response as either a Facebook page or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
                         status, e, raw_text
                     )))
                 }
             }
         },
-        None => Err(PyValueError::new_err("page_id must be set to the page id of the page to get info for"))?,
+        None => Err(MetaStatsError::InvalidArgument("page_id must be set to the page id of the page to get info for".to_string()))?,
     }
 
 }
 
-#[pyfunction]
-pub fn get_facebook_page_followers(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>) -> PyResult<Py<PageEngagementResult>> {
+/// Fields requested by `get_facebook_page_followers`, and the ones `bulk_fetch_facebook_pages`
+/// accepts.
+pub const PAGE_ENGAGEMENT_FIELDS: [&str; 8] = [
+    "category",
+    "category_list",
+    "followers_count",
+    "fan_count",
+    "new_like_count",
+    "overall_star_rating",
+    "rating_count",
+    "talking_about_count",
+];
+
+/// Checks `fields` against `PAGE_ENGAGEMENT_FIELDS` and joins them for the `fields` parameter.
+pub fn page_engagement_fields(fields: &[String]) -> Result<String, MetaStatsError> {
+    match fields.iter().find(|field| !PAGE_ENGAGEMENT_FIELDS.contains(&field.as_str())) {
+        Some(unknown) => Err(MetaStatsError::InvalidArgument(format!(
+            "'{}' is not a page engagement field; expected one of {}",
+            unknown,
+            PAGE_ENGAGEMENT_FIELDS.join(", ")
+        ))),
+        None => Ok(fields.join(",")),
+    }
+}
+
+pub fn fetch_facebook_page_followers(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<PageEngagementResult, MetaStatsError> {
+    fetch_facebook_page_engagement(access_token, page_id, meta_version, None, config)
+}
+
+/// Fetches a subset of `PAGE_ENGAGEMENT_FIELDS` (all of them when `fields` is `None`).
+pub fn fetch_facebook_page_engagement(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, fields: Option<&[String]>, config: &MetaConfig) -> Result<PageEngagementResult, MetaStatsError> {
+    let fields = match fields {
+        Some(fields) => page_engagement_fields(fields)?,
+        None => PAGE_ENGAGEMENT_FIELDS.join(","),
+    };
+
     let access_token = match access_token {
         Some(access_token) => access_token,
-        None => auth::get_meta_access_token(None, None, None, None, None)?,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(PageEngagementResult::error(error)),
+        },
     };
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| PyValueError::new_err(format!("Failed to create HTTP client for facebook page followers: {}", e)))?;
+    let client = HttpClient::new(config)?;
 
 
-    let meta_version = match meta_version {
-        Some(meta_version) => meta_version,
-        None => auth::get_meta_version()?,
-    };
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
 
     match page_id {
         Some(page_id) => {
+            let page_id = FacebookPageRef::parse(&page_id)?;
 
-            let url = format!("https://graph.facebook.com/{}/{}?fields=category,category_list,followers_count,fan_count,new_like_count,overall_star_rating,rating_count,talking_about_count&access_token={}", meta_version, page_id, access_token);
-            let res = client
-                .get(&url)
-                .send()
-                .map_err(|e| PyValueError::new_err(format!("Request failed for PageEngagement: {}", e)))?;
-            
-            let status = res.status();
-            let raw_text = res
-                .text()
-                .map_err(|e| PyValueError::new_err(format!("Failed to get response text for PageEngagement: {}", e)))?;
+            let url = format!("{}/{}/{}?fields={}&access_token={}", config.graph_base_url(), meta_version, page_id, fields, access_token);
+            let response = http::get(&client, &url, &[], "PageEngagement")?;
+            let status = response.status;
+            let raw_text = response.text;
             
             let error_result: Result<meta::MetaError, _> = serde_json::from_str(&raw_text);
             
             if let Ok(error) = error_result {
-                let result = PageEngagementResult::error(error);
-                return Python::with_gil(|py| {
-                    Py::new(py, result).map_err(|e| PyErr::from(e))
-                });
+                let result = PageEngagementResult::error(error).with_cache_status(response.cache_status);
+                return Ok(result);
             }
             
             match serde_json::from_str::<PageEngagement>(&raw_text) {
                 Ok(page_info) => {
-                    let result = PageEngagementResult::success(page_info);
-                    Python::with_gil(|py| {
-                        Py::new(py, result).map_err(|e| PyErr::from(e))
-                    })
+                    let result = PageEngagementResult::success(page_info).with_cache_status(response.cache_status);
+                    Ok(result)
                 },
                 Err(e) => {
                     eprintln!("Failed to parse Facebook response: {}", e);
                     eprintln!("Raw response: {}", raw_text);
                     
-                    Err(PyValueError::new_err(format!(
+                    Err(MetaStatsError::Parse(format!(
                         "Failed to parse response as either a Facebook page or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
                         status, e, raw_text
                     )))
@@ -479,63 +1619,71 @@ pub fn get_facebook_page_followers(access_token: Option<String>, page_id: Option
             }
 
         },
-        None => Err(PyValueError::new_err("page_id must be set to the page id of the page to get info for"))?,
+        None => Err(MetaStatsError::InvalidArgument("page_id must be set to the page id of the page to get info for".to_string()))?,
     }
 
 }
 
-#[pyfunction]
-pub fn get_facebook_page_posts(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>) -> PyResult<Py<BasicPostsInfoResult>> {
+/// `&since=…&until=…` for a posts request. The Graph API takes unix seconds and filters on
+/// `created_time`, `since` inclusive.
+fn time_range(since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>) -> Result<String, MetaStatsError> {
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            return Err(MetaStatsError::InvalidArgument(format!("since ({}) is after until ({})", timestamp::format(&since), timestamp::format(&until))));
+        }
+    }
+    let since = since.map(|since| format!("&since={}", since.timestamp())).unwrap_or_default();
+    let until = until.map(|until| format!("&until={}", until.timestamp())).unwrap_or_default();
+    Ok(since + &until)
+}
+
+pub fn fetch_facebook_page_posts(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, extended_fields: Option<bool>, since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>, config: &MetaConfig) -> Result<BasicPostsInfoResult, MetaStatsError> {
     let access_token = match access_token {
         Some(access_token) => access_token,
-        None => auth::get_meta_access_token(None, None, None, None, None)?,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(BasicPostsInfoResult::error(error)),
+        },
     };
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| PyValueError::new_err(format!("Failed to create HTTP client for facebook page posts: {}", e)))?;
+    let client = HttpClient::new(config)?;
 
-    let meta_version = match meta_version {
-        Some(meta_version) => meta_version,
-        None => auth::get_meta_version()?,
-    };
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
 
     match page_id {
         Some(page_id) => {
+            let page_id = FacebookPageRef::parse(&page_id)?;
 
-            let url = format!("https://graph.facebook.com/{}/{}/posts?&access_token={}", meta_version, page_id, access_token);
-            let res = client
-                .get(&url)
-                .send()
-                .map_err(|e| PyValueError::new_err(format!("Request failed for BasicPostsInfo: {}", e)))?;
-            
-            let status = res.status();
-            let raw_text = res
-                .text()
-                .map_err(|e| PyValueError::new_err(format!("Failed to get response text for BasicPostsInfo: {}", e)))?;
+            let fields = if extended_fields.unwrap_or(false) {
+                format!("fields=id,message,created_time,story,{}", POST_EXTENDED_FIELDS)
+            } else {
+                String::new()
+            };
+
+            let time_range = time_range(since, until)?;
+
+            let url = format!("{}/{}/{}/posts?{}{}&access_token={}", config.graph_base_url(), meta_version, page_id, fields, time_range, access_token);
+            let response = http::get(&client, &url, &[], "BasicPostsInfo")?;
+            let status = response.status;
+            let raw_text = response.text;
             
             let error_result: Result<meta::MetaError, _> = serde_json::from_str(&raw_text);
 
             if let Ok(error) = error_result {
-                let result = BasicPostsInfoResult::error(error);
-                return Python::with_gil(|py| {
-                    Py::new(py, result).map_err(|e| PyErr::from(e))
-                });
+                let result = BasicPostsInfoResult::error(error).with_cache_status(response.cache_status);
+                return Ok(result);
             }
             
             match serde_json::from_str::<BasicPostsInfo>(&raw_text) {
                 Ok(page_info) => {
-                    let result = BasicPostsInfoResult::success(page_info);
-                    Python::with_gil(|py| {
-                        Py::new(py, result).map_err(|e| PyErr::from(e))
-                    })
+                    let result = BasicPostsInfoResult::success(page_info).with_cache_status(response.cache_status);
+                    Ok(result)
                 },
                 Err(e) => {
                     eprintln!("Failed to parse Facebook response: {}", e);
                     eprintln!("Raw response: {}", raw_text);
                     
-                    Err(PyValueError::new_err(format!(
+                    Err(MetaStatsError::Parse(format!(
                         "Failed to parse response as either a Facebook posts or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
                         status, e, raw_text
                     )))
@@ -544,65 +1692,60 @@ pub fn get_facebook_page_posts(access_token: Option<String>, page_id: Option<Str
 
 
         },
-        None => Err(PyValueError::new_err("page_id must be set to the page id of the page to get info for"))?,
+        None => Err(MetaStatsError::InvalidArgument("page_id must be set to the page id of the page to get info for".to_string()))?,
     }
 
 }
 
-#[pyfunction]
-pub fn get_facebook_page_posts_with_summary(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>) -> PyResult<Py<BasicPostsInfoResult>> {
+pub fn fetch_facebook_page_posts_with_summary(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, extended_fields: Option<bool>, since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>, config: &MetaConfig) -> Result<BasicPostsInfoResult, MetaStatsError> {
     let access_token = match access_token {
         Some(access_token) => access_token,
-        None => auth::get_meta_access_token(None, None, None, None, None)?,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(BasicPostsInfoResult::error(error)),
+        },
     };
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| PyValueError::new_err(format!("Failed to create HTTP client for facebook page posts: {}", e)))?;
+    let client = HttpClient::new(config)?;
 
-    let meta_version = match meta_version {
-        Some(meta_version) => meta_version,
-        None => auth::get_meta_version()?,
-    };
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
 
     match page_id {
         Some(page_id) => {
+            let page_id = FacebookPageRef::parse(&page_id)?;
 
-            let url = format!("https://graph.facebook.com/{}/{}/posts?fields=id,message,created_time,likes.summary(true),comments.summary(true)&access_token={}", meta_version, page_id, access_token);
+            let extra_fields = if extended_fields.unwrap_or(false) {
+                format!(",{}", POST_EXTENDED_FIELDS)
+            } else {
+                String::new()
+            };
 
-            let res = client
-                .get(&url)
-                .send()
-                .map_err(|e| PyValueError::new_err(format!("Request failed for BasicPostsInfo: {}", e)))?;
-            
-            let status = res.status();
-            let raw_text = res
-                .text()
-                .map_err(|e| PyValueError::new_err(format!("Failed to get response text for BasicPostsInfo: {}", e)))?;
+            let time_range = time_range(since, until)?;
+
+            let url = format!("{}/{}/{}/posts?fields=id,message,created_time,likes.summary(true),comments.summary(true){}{}&access_token={}", config.graph_base_url(), meta_version, page_id, extra_fields, time_range, access_token);
+
+            let response = http::get(&client, &url, &[], "BasicPostsInfo")?;
+            let status = response.status;
+            let raw_text = response.text;
             
             let error_result: Result<meta::MetaError, _> = serde_json::from_str(&raw_text);
 
             
             if let Ok(error) = error_result {
-                let result = BasicPostsInfoResult::error(error);
-                return Python::with_gil(|py| {
-                    Py::new(py, result).map_err(|e| PyErr::from(e))
-                });
+                let result = BasicPostsInfoResult::error(error).with_cache_status(response.cache_status);
+                return Ok(result);
             }
             
             match serde_json::from_str::<BasicPostsInfo>(&raw_text) {
                 Ok(page_info) => {
-                    let result = BasicPostsInfoResult::success(page_info);
-                    Python::with_gil(|py| {
-                        Py::new(py, result).map_err(|e| PyErr::from(e))
-                    })
+                    let result = BasicPostsInfoResult::success(page_info).with_cache_status(response.cache_status);
+                    Ok(result)
                 },
                 Err(e) => {
                     eprintln!("Failed to parse Facebook response: {}", e);
                     eprintln!("Raw response: {}", raw_text);
                     
-                    Err(PyValueError::new_err(format!(
+                    Err(MetaStatsError::Parse(format!(
                         "Failed to parse response as either a Facebook posts or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
                         status, e, raw_text
                     )))
@@ -610,46 +1753,219 @@ pub fn get_facebook_page_posts_with_summary(access_token: Option<String>, page_i
             }
 
         },
-        None => Err(PyValueError::new_err("page_id must be set to the page id of the page to get info for"))?,
+        None => Err(MetaStatsError::InvalidArgument("page_id must be set to the page id of the page to get info for".to_string()))?,
     }
 }
 
-#[pyfunction]
-pub fn get_facebook_next_results(next: String) -> PyResult<String> {
+/// An Instagram account linked to a Facebook page.
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct InstagramBusinessAccount {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    username: Option<String>,
+}
+
+impl InstagramBusinessAccount {
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+}
+
+/// A page's name, vanity username, website and linked Instagram accounts: what is needed to
+/// match it with an Instagram profile.
+#[pyclass(module = "meta_stats.facebook")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PageLinks {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    #[serde(default)]
+    name: String,
+    #[pyo3(get)]
+    username: Option<String>,
+    #[pyo3(get)]
+    website: Option<String>,
+    /// The Instagram professional account the page manages.
+    #[pyo3(get)]
+    instagram_business_account: Option<InstagramBusinessAccount>,
+    /// The Instagram account shown on the page, which need not be a professional account.
+    #[pyo3(get)]
+    connected_instagram_account: Option<InstagramBusinessAccount>,
+}
+
+impl PageLinks {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    pub fn website(&self) -> Option<&str> {
+        self.website.as_deref()
+    }
+
+    pub fn instagram_business_account(&self) -> Option<&InstagramBusinessAccount> {
+        self.instagram_business_account.as_ref()
+    }
+
+    pub fn connected_instagram_account(&self) -> Option<&InstagramBusinessAccount> {
+        self.connected_instagram_account.as_ref()
+    }
+}
+
+const PAGE_LINK_FIELDS: &str = "id,name,username,website,instagram_business_account{id,username},connected_instagram_account{id,username}";
+
+/// Fetches the page's `PageLinks`. Linked accounts the token cannot see are left as `None`.
+pub fn fetch_facebook_page_links(access_token: &str, page_id: &str, meta_version: &GraphVersion, config: &MetaConfig) -> Result<Result<PageLinks, meta::MetaError>, MetaStatsError> {
+    let page_id = FacebookPageRef::parse(page_id)?;
+    let client = HttpClient::new(config)?;
+    let url = format!("{}/{}/{}?fields={}&access_token={}", config.graph_base_url(), meta_version, page_id, PAGE_LINK_FIELDS, access_token);
 
-    let client = Client::new();
+    let response = http::get(&client, &url, &[], "PageLinks")?;
+    let raw_text = response.text;
 
-    let res = client.get(&next).send().map_err(|e| PyValueError::new_err(e.to_string()))?;
-    let raw_text = res.text().map_err(|e| PyValueError::new_err(e.to_string()))?;
+    if let Ok(error) = serde_json::from_str::<meta::MetaError>(&raw_text) {
+        return Ok(Err(error));
+    }
 
-    Ok(raw_text)
+    let links = serde_json::from_str(&raw_text).map_err(|e| {
+        MetaStatsError::Parse(format!(
+            "Failed to parse response as either page links or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
+            response.status, e, raw_text
+        ))
+    })?;
 
+    Ok(Ok(links))
 }
 
-#[pyfunction]
-pub fn parse_next_results_to_basic_posts_info(raw_text: String) -> PyResult<Py<BasicPostsInfoResult>> {
+#[derive(Deserialize)]
+struct PageId {
+    id: String,
+}
+
+/// Looks up the id of the page named `page`, requesting no other field.
+fn fetch_facebook_page_id(access_token: &str, page: &FacebookPageRef, meta_version: &GraphVersion, config: &MetaConfig) -> Result<Result<String, meta::MetaError>, MetaStatsError> {
+    let client = HttpClient::new(config)?;
+    let url = format!("{}/{}/{}?fields=id&access_token={}", config.graph_base_url(), meta_version, page, access_token);
+
+    let response = http::get(&client, &url, &[], "PageId")?;
+    let raw_text = response.text;
+
+    if let Ok(error) = serde_json::from_str::<meta::MetaError>(&raw_text) {
+        return Ok(Err(error));
+    }
+
+    let page: PageId = serde_json::from_str(&raw_text).map_err(|e| {
+        MetaStatsError::Parse(format!(
+            "Failed to parse response as either a page id or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
+            response.status, e, raw_text
+        ))
+    })?;
+
+    Ok(Ok(page.id))
+}
+
+/// The numeric id of a page given by id, vanity name or URL. Vanity names can be changed by
+/// the page's owner and then point nowhere, or at another page; ids never change. Numeric
+/// input is returned without a request.
+///
+/// With a `store`, a name already in its name history resolves without a request, and a name
+/// resolved from the API is added to it, so a name keeps resolving after the page renames.
+/// Stored names are never revalidated on their own, since a renamed page's old name no longer
+/// resolves from the API at all. `refresh` skips the store and asks the API; the answer is
+/// recorded with a new `last_seen`, so if another page has since taken the name, the name
+/// resolves to that page from then on.
+pub fn resolve_page_id(page_id: &str, access_token: Option<String>, meta_version: Option<String>, store: Option<&SnapshotStore>, refresh: bool, config: &MetaConfig) -> Result<Result<String, meta::MetaError>, MetaStatsError> {
+    let page = FacebookPageRef::parse(page_id)?;
+    if let Some(id) = page.id() {
+        return Ok(Ok(id.to_string()));
+    }
+    if let Some(id) = store.filter(|_| !refresh).map(|store| store.account_id(snapshot::FACEBOOK, page.as_str())).transpose()?.flatten() {
+        return Ok(Ok(id));
+    }
+
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
+    let access_token = match access_token {
+        Some(access_token) => access_token,
+        None => match auth::fetch_meta_access_token(None, None, None, None, Some(meta_version.to_string()), config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(Err(error)),
+        },
+    };
+
+    let page_id = fetch_facebook_page_id(&access_token, &page, &meta_version, config)?;
+    if let (Some(store), Ok(id)) = (store, &page_id) {
+        store.record_name(snapshot::FACEBOOK, id, page.as_str(), None)?;
+    }
+    Ok(page_id)
+}
+
+/// One page of posts and the cursor for the page after it, if any.
+pub type PostsPage = (Vec<PostInfo>, Option<String>);
+
+/// Fetches one page of a page's posts (with like/comment totals and the extended fields)
+/// starting after `after`. Returns the posts and the cursor for the following page.
+pub fn fetch_facebook_posts_page(client: &HttpClient, access_token: &str, page_id: &str, meta_version: &GraphVersion, after: Option<&str>, config: &MetaConfig) -> Result<Result<PostsPage, meta::MetaError>, MetaStatsError> {
+    let after = after.map(|after| format!("&after={}", after)).unwrap_or_default();
+    let url = format!("{}/{}/{}/posts?fields=id,message,created_time,likes.summary(true),comments.summary(true),{}{}&access_token={}", config.graph_base_url(), meta_version, page_id, POST_EXTENDED_FIELDS, after, access_token);
+
+    let response = http::get(client, &url, &[], "BasicPostsInfo")?;
+    let raw_text = response.text;
+
+    if let Ok(error) = serde_json::from_str::<meta::MetaError>(&raw_text) {
+        return Ok(Err(error));
+    }
+
+    let page: EdgePage<PostInfo> = serde_json::from_str(&raw_text).map_err(|e| {
+        MetaStatsError::Parse(format!(
+            "Failed to parse response as either Facebook posts or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
+            response.status, e, raw_text
+        ))
+    })?;
+
+    let next_cursor = page.paging.as_ref().and_then(|paging| paging.after_cursor().map(str::to_string));
+    Ok(Ok((page.data, next_cursor)))
+}
+
+pub fn fetch_facebook_next_results(next: String, config: &MetaConfig) -> Result<String, MetaStatsError> {
+
+    let client = HttpClient::new(config)?;
+
+    let response = http::get(&client, &next, &[], "next results")?;
+
+    Ok(response.text)
+
+}
+
+pub fn parse_basic_posts_info(raw_text: String) -> Result<BasicPostsInfoResult, MetaStatsError> {
     let error_result: Result<meta::MetaError, _> = serde_json::from_str(&raw_text);
 
             
     if let Ok(error) = error_result {
         let result = BasicPostsInfoResult::error(error);
-        return Python::with_gil(|py| {
-            Py::new(py, result).map_err(|e| PyErr::from(e))
-        });
+        return Ok(result);
     }
     
     match serde_json::from_str::<BasicPostsInfo>(&raw_text) {
         Ok(page_info) => {
             let result = BasicPostsInfoResult::success(page_info);
-            Python::with_gil(|py| {
-                Py::new(py, result).map_err(|e| PyErr::from(e))
-            })
+            Ok(result)
         },
         Err(e) => {
             eprintln!("Failed to parse Facebook response: {}", e);
             eprintln!("Raw response: {}", raw_text);
             
-            Err(PyValueError::new_err(format!(
+            Err(MetaStatsError::Parse(format!(
                 "Failed to parse response as either a Facebook posts or error. Parse error: {}, Response preview: {:.200}...",
                 e, raw_text
             )))
@@ -657,29 +1973,413 @@ pub fn parse_next_results_to_basic_posts_info(raw_text: String) -> PyResult<Py<B
     }
 }
 
-#[pyfunction]
-pub fn get_facebook_post_interactions(access_token: Option<String>, post_id: Option<String>, meta_version: Option<String>) -> PyResult<String> {
+pub fn fetch_facebook_post_interactions(access_token: Option<String>, post_id: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<String, MetaStatsError> {
     let access_token = match access_token {
         Some(access_token) => access_token,
-        None => auth::get_meta_access_token(None, None, None, None, None)?,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return serde_json::to_string(&error).map_err(|e| MetaStatsError::Parse(e.to_string())),
+        },
     };
 
-    let client = Client::new();
+    let client = HttpClient::new(config)?;
 
-    let meta_version = match meta_version {
-        Some(meta_version) => meta_version,
-        None => auth::get_meta_version()?,
-    };
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
 
     match post_id {
         Some(post_id) => {
-            let url = format!("https://graph.facebook.com/{}/{}/reactions?access_token={}", meta_version, post_id, access_token);
-            let res = client.get(&url).send().map_err(|e| PyValueError::new_err(e.to_string()))?;
-            let raw_text = res.text().map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let url = format!("{}/{}/{}/reactions?access_token={}", config.graph_base_url(), meta_version, post_id, access_token);
+            let response = http::get(&client, &url, &[], "post interactions")?;
 
-            Ok(raw_text)
+            Ok(response.text)
         },
-        None => Err(PyValueError::new_err("Post ID is required")),
+        None => Err(MetaStatsError::InvalidArgument("Post ID is required".to_string())),
     }
             
-}
\ No newline at end of file
+}
+
+pub fn fetch_facebook_page_events(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, time_filter: Option<String>, config: &MetaConfig) -> Result<PageEventsResult, MetaStatsError> {
+    let access_token = match access_token {
+        Some(access_token) => access_token,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(PageEventsResult::error(error)),
+        },
+    };
+
+    let client = HttpClient::new(config)?;
+
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
+
+    let time_filter = match time_filter.as_deref() {
+        None => String::new(),
+        Some(filter @ ("upcoming" | "past")) => format!("&time_filter={}", filter),
+        Some(other) => Err(MetaStatsError::InvalidArgument(format!("time_filter must be either 'upcoming' or 'past', got '{}'", other)))?,
+    };
+
+    match page_id {
+        Some(page_id) => {
+            let page_id = FacebookPageRef::parse(&page_id)?;
+            let url = format!("{}/{}/{}/events?fields=id,name,description,start_time,end_time,timezone,place,ticket_uri,attending_count,interested_count,maybe_count,is_canceled{}&access_token={}", config.graph_base_url(), meta_version, page_id, time_filter, access_token);
+
+            let mut cache_status = None;
+            let result = match fetch_all_pages::<PageEvent>(&client, url, "PageEvent", &mut cache_status)? {
+                Ok(events) => PageEventsResult::success(events),
+                Err(error) => PageEventsResult::error(error),
+            };
+            let result = result.with_cache_status(cache_status);
+
+            Ok(result)
+        },
+        None => Err(MetaStatsError::InvalidArgument("page_id must be set to the page id of the page to get events for".to_string()))?,
+    }
+}
+
+/// Lists a video edge (`videos` or `video_reels`) of a page and, when asked, looks up
+/// `video_insights` for each video. Insights need a page token with `read_insights`,
+/// so a video whose insights request is refused for permission simply keeps `insights`
+/// unset. Any other error, such as rate limiting or an expired token, stops the lookups and
+/// is returned as the result's error.
+fn fetch_facebook_page_video_edge(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, include_insights: Option<bool>, edge: &str, config: &MetaConfig) -> Result<PageVideosResult, MetaStatsError> {
+    let access_token = match access_token {
+        Some(access_token) => access_token,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(PageVideosResult::error(error)),
+        },
+    };
+
+    let client = HttpClient::new(config)?;
+
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
+
+    match page_id {
+        Some(page_id) => {
+            let page_id = FacebookPageRef::parse(&page_id)?;
+            let url = format!("{}/{}/{}/{}?fields=id,title,description,length,created_time,permalink_url,views&access_token={}", config.graph_base_url(), meta_version, page_id, edge, access_token);
+
+            let mut cache_status = None;
+            let mut videos = match fetch_all_pages::<PageVideo>(&client, url, "PageVideo", &mut cache_status)? {
+                Ok(videos) => videos,
+                Err(error) => {
+                    let result = PageVideosResult::error(error).with_cache_status(cache_status);
+                    return Ok(result);
+                }
+            };
+
+            if include_insights.unwrap_or(false) {
+                for video in videos.iter_mut() {
+                    let url = format!("{}/{}/{}/video_insights?metric={}&access_token={}", config.graph_base_url(), meta_version, video.id, VideoInsights::METRICS, access_token);
+                    match fetch_all_pages::<InsightMetric>(&client, url, "VideoInsights", &mut cache_status)? {
+                        Ok(metrics) => video.insights = Some(VideoInsights::from_metrics(metrics)),
+                        Err(error) if error.category() == meta::ErrorCategory::Permission => {},
+                        Err(error) => return Ok(PageVideosResult::error(error).with_cache_status(cache_status)),
+                    }
+                }
+            }
+
+            let result = PageVideosResult::success(videos).with_cache_status(cache_status);
+            Ok(result)
+        },
+        None => Err(MetaStatsError::InvalidArgument(format!("page_id must be set to the page id of the page to get {} for", edge)))?,
+    }
+}
+
+pub fn fetch_facebook_page_videos(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, include_insights: Option<bool>, config: &MetaConfig) -> Result<PageVideosResult, MetaStatsError> {
+    fetch_facebook_page_video_edge(access_token, page_id, meta_version, include_insights, "videos", config)
+}
+
+pub fn fetch_facebook_page_reels(access_token: Option<String>, page_id: Option<String>, meta_version: Option<String>, include_insights: Option<bool>, config: &MetaConfig) -> Result<PageVideosResult, MetaStatsError> {
+    fetch_facebook_page_video_edge(access_token, page_id, meta_version, include_insights, "video_reels", config)
+}
+
+const COMMENT_FIELDS: &str = "id,message,created_time,like_count,comment_count,parent{id}";
+
+/// Arranges a flat `filter=stream` comment listing into threads using each comment's
+/// `parent`. Comments whose parent is not part of the listing become top-level nodes.
+fn build_comment_tree(comments: Vec<CommentInfo>) -> Vec<CommentNode> {
+    let ids: HashSet<String> = comments.iter().filter_map(|c| c.id.clone()).collect();
+    let mut roots = Vec::new();
+    let mut children: HashMap<String, Vec<CommentInfo>> = HashMap::new();
+
+    for comment in comments {
+        match comment.parent.as_ref().map(|p| p.id.clone()) {
+            Some(parent_id) if ids.contains(&parent_id) => children.entry(parent_id).or_default().push(comment),
+            _ => roots.push(comment),
+        }
+    }
+
+    fn attach(comment: CommentInfo, children: &mut HashMap<String, Vec<CommentInfo>>) -> CommentNode {
+        let replies = comment
+            .id
+            .as_ref()
+            .and_then(|id| children.remove(id))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|reply| attach(reply, children))
+            .collect();
+
+        CommentNode { comment, replies }
+    }
+
+    roots.into_iter().map(|comment| attach(comment, &mut children)).collect()
+}
+
+/// Fetches every `filter=toplevel` comment under `object_id`, then follows the
+/// `comments` edge of each comment that reports replies.
+fn fetch_comment_threads(client: &HttpClient, meta_version: &GraphVersion, object_id: &str, order: &str, access_token: &str, cache_status: &mut Option<CacheStatus>, config: &MetaConfig) -> Result<Result<Vec<CommentNode>, meta::MetaError>, MetaStatsError> {
+    let url = format!("{}/{}/{}/comments?fields={}&order={}&filter=toplevel&access_token={}", config.graph_base_url(), meta_version, object_id, COMMENT_FIELDS, order, access_token);
+
+    let comments = match fetch_all_pages::<CommentInfo>(client, url, "CommentInfo", cache_status)? {
+        Ok(comments) => comments,
+        Err(error) => return Ok(Err(error)),
+    };
+
+    let mut nodes = Vec::with_capacity(comments.len());
+    for comment in comments {
+        let replies = match (&comment.id, comment.comment_count) {
+            (Some(id), Some(count)) if count > 0 => match fetch_comment_threads(client, meta_version, id, order, access_token, cache_status, config)? {
+                Ok(replies) => replies,
+                Err(error) => return Ok(Err(error)),
+            },
+            _ => Vec::new(),
+        };
+        nodes.push(CommentNode { comment, replies });
+    }
+
+    Ok(Ok(nodes))
+}
+
+pub fn fetch_facebook_post_comments(access_token: Option<String>, post_id: Option<String>, meta_version: Option<String>, order: Option<String>, filter: Option<String>, config: &MetaConfig) -> Result<PostCommentsResult, MetaStatsError> {
+    let access_token = match access_token {
+        Some(access_token) => access_token,
+        None => match auth::fetch_meta_access_token(None, None, None, None, None, config)? {
+            Ok(access_token) => access_token,
+            Err(error) => return Ok(PostCommentsResult::error(error)),
+        },
+    };
+
+    let client = HttpClient::new(config)?;
+
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
+
+    let order = match order.as_deref() {
+        None => "chronological",
+        Some(order @ ("chronological" | "reverse_chronological")) => order,
+        Some(other) => Err(MetaStatsError::InvalidArgument(format!("order must be either 'chronological' or 'reverse_chronological', got '{}'", other)))?,
+    };
+
+    match post_id {
+        Some(post_id) => {
+            let mut cache_status = None;
+            let comments = match filter.as_deref() {
+                None | Some("stream") => {
+                    let url = format!("{}/{}/{}/comments?fields={}&order={}&filter=stream&access_token={}", config.graph_base_url(), meta_version, post_id, COMMENT_FIELDS, order, access_token);
+                    fetch_all_pages::<CommentInfo>(&client, url, "CommentInfo", &mut cache_status)?.map(build_comment_tree)
+                },
+                Some("toplevel") => fetch_comment_threads(&client, &meta_version, &post_id, order, &access_token, &mut cache_status, config)?,
+                Some(other) => Err(MetaStatsError::InvalidArgument(format!("filter must be either 'stream' or 'toplevel', got '{}'", other)))?,
+            };
+
+            let result = match comments {
+                Ok(comments) => PostCommentsResult::success(comments),
+                Err(error) => PostCommentsResult::error(error),
+            };
+            let result = result.with_cache_status(cache_status);
+
+            Ok(result)
+        },
+        None => Err(MetaStatsError::InvalidArgument("Post ID is required".to_string())),
+    }
+}
+
+/// Raises `ValueError` with Meta's message when the page cannot be found. `refresh` asks the
+/// API even when `store` already knows the name.
+#[pyfunction]
+#[pyo3(name = "resolve_page_id")]
+pub fn py_resolve_page_id(page_id: PageArg, access_token: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>, store: Option<PyRef<'_, SnapshotStore>>, refresh: Option<bool>) -> PyResult<String> {
+    let page_id = String::from(page_id);
+    match resolve_page_id(&page_id, access_token, meta_version, store.as_deref(), refresh.unwrap_or(false), &MetaConfig::or_from_env(config)?)? {
+        Ok(id) => Ok(id),
+        Err(error) => Err(PyValueError::new_err(format!("Could not resolve page '{}': {}", page_id, error.message()))),
+    }
+}
+
+#[pyfunction]
+pub fn get_facebook_page_info(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<Py<BasicPageInfoResult>> {
+    let result = fetch_facebook_page_info(access_token, page_id.map(String::from), meta_version, &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[pyfunction]
+pub fn get_facebook_page_followers(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<Py<PageEngagementResult>> {
+    let result = fetch_facebook_page_followers(access_token, page_id.map(String::from), meta_version, &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+/// `since` and `until` are timezone-aware datetimes; only posts created between them, both
+/// inclusive, are returned.
+#[pyfunction]
+#[pyo3(signature = (access_token=None, page_id=None, meta_version=None, extended_fields=None, config=None, *, since=None, until=None))]
+pub fn get_facebook_page_posts(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, extended_fields: Option<bool>, config: Option<MetaConfig>, since: Option<TimeArg>, until: Option<TimeArg>) -> PyResult<Py<BasicPostsInfoResult>> {
+    let result = fetch_facebook_page_posts(access_token, page_id.map(String::from), meta_version, extended_fields, since.map(DateTime::from), until.map(DateTime::from), &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+/// `since` and `until` are timezone-aware datetimes; only posts created between them, both
+/// inclusive, are returned.
+#[pyfunction]
+#[pyo3(signature = (access_token=None, page_id=None, meta_version=None, extended_fields=None, config=None, *, since=None, until=None))]
+pub fn get_facebook_page_posts_with_summary(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, extended_fields: Option<bool>, config: Option<MetaConfig>, since: Option<TimeArg>, until: Option<TimeArg>) -> PyResult<Py<BasicPostsInfoResult>> {
+    let result = fetch_facebook_page_posts_with_summary(access_token, page_id.map(String::from), meta_version, extended_fields, since.map(DateTime::from), until.map(DateTime::from), &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[pyfunction]
+pub fn get_facebook_next_results(next: String, config: Option<MetaConfig>) -> PyResult<String> {
+    Ok(fetch_facebook_next_results(next, &MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+pub fn parse_next_results_to_basic_posts_info(raw_text: String) -> PyResult<Py<BasicPostsInfoResult>> {
+    let result = parse_basic_posts_info(raw_text)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[pyfunction]
+pub fn get_facebook_post_interactions(access_token: Option<String>, post_id: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<String> {
+    Ok(fetch_facebook_post_interactions(access_token, post_id, meta_version, &MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+pub fn get_facebook_page_events(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, time_filter: Option<String>, config: Option<MetaConfig>) -> PyResult<Py<PageEventsResult>> {
+    let result = fetch_facebook_page_events(access_token, page_id.map(String::from), meta_version, time_filter, &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[pyfunction]
+pub fn get_facebook_page_videos(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, include_insights: Option<bool>, config: Option<MetaConfig>) -> PyResult<Py<PageVideosResult>> {
+    let result = fetch_facebook_page_videos(access_token, page_id.map(String::from), meta_version, include_insights, &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[pyfunction]
+pub fn get_facebook_page_reels(access_token: Option<String>, page_id: Option<PageArg>, meta_version: Option<String>, include_insights: Option<bool>, config: Option<MetaConfig>) -> PyResult<Py<PageVideosResult>> {
+    let result = fetch_facebook_page_reels(access_token, page_id.map(String::from), meta_version, include_insights, &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[pyfunction]
+pub fn get_facebook_post_comments(access_token: Option<String>, post_id: Option<String>, meta_version: Option<String>, order: Option<String>, filter: Option<String>, config: Option<MetaConfig>) -> PyResult<Py<PostCommentsResult>> {
+    let result = fetch_facebook_post_comments(access_token, post_id, meta_version, order, filter, &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::TestCassette;
+    use crate::config::ConfigValues;
+
+    const TOKEN: &str = "test-token";
+    const VERSION: &str = "v22.0";
+
+    fn config() -> MetaConfig {
+        MetaConfig::load(None, None, Some("META_STATS_TEST_".to_string()), ConfigValues::default()).unwrap()
+    }
+
+    fn token() -> Option<String> {
+        Some(TOKEN.to_string())
+    }
+
+    fn page(id: &str) -> Option<String> {
+        Some(id.to_string())
+    }
+
+    fn version() -> Option<String> {
+        Some(VERSION.to_string())
+    }
+
+    #[test]
+    fn fetches_page_info_and_followers() {
+        let _cassette = TestCassette::replay("facebook");
+
+        let info = fetch_facebook_page_info(token(), page("ChachiOfficial"), version(), &config()).unwrap();
+        assert_eq!(info.page_info.unwrap().name, "Chachi");
+
+        let missing = fetch_facebook_page_info(token(), page("missingpage"), version(), &config()).unwrap();
+        assert!(!missing.is_success);
+        assert_eq!(missing.error.unwrap().code(), 803);
+
+        let engagement = fetch_facebook_page_followers(token(), page("ChachiOfficial"), version(), &config()).unwrap().page_engagement.unwrap();
+        assert_eq!(engagement.followers_count(), 52000);
+        assert_eq!(engagement.category_list[0].name, "Musician/Band");
+    }
+
+    #[test]
+    fn fetches_posts_and_following_pages() {
+        let _cassette = TestCassette::replay("facebook");
+
+        let posts = fetch_facebook_page_posts(token(), page("ChachiOfficial"), version(), None, None, None, &config()).unwrap().posts_info.unwrap();
+        assert_eq!(posts.data.len(), 2);
+        assert_eq!(timestamp::format(&posts.data[0].created_time.unwrap()), "2024-03-01T18:00:00+0000");
+
+        let next = fetch_facebook_next_results(posts.paging.next_url().unwrap().to_string(), &config()).unwrap();
+        assert!(next.contains("Tour dates announced"));
+
+        let summary = fetch_facebook_page_posts_with_summary(token(), page("ChachiOfficial"), version(), None, None, None, &config()).unwrap().posts_info.unwrap();
+        assert_eq!(summary.data[0].likes.as_ref().unwrap().summary.total_count, 120);
+
+        let reactions = fetch_facebook_post_interactions(token(), Some("100_1".to_string()), version(), &config()).unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&reactions).is_ok());
+    }
+
+    #[test]
+    fn fetches_events_videos_and_comments() {
+        let _cassette = TestCassette::replay("facebook");
+
+        let events = fetch_facebook_page_events(token(), page("ChachiOfficial"), version(), Some("upcoming".to_string()), &config()).unwrap().events.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].attending_count, 210);
+        assert!(events[1].is_canceled);
+        assert!(fetch_facebook_page_events(token(), page("ChachiOfficial"), version(), Some("someday".to_string()), &config()).is_err());
+
+        let videos = fetch_facebook_page_videos(token(), page("ChachiOfficial"), version(), Some(true), &config()).unwrap().videos.unwrap();
+        assert_eq!(videos[0].insights.as_ref().unwrap().total_video_views, Some(9100));
+
+        let reels = fetch_facebook_page_reels(token(), page("ChachiOfficial"), version(), Some(true), &config()).unwrap().videos.unwrap();
+        assert!(reels[0].insights.is_none());
+
+        let limited = fetch_facebook_page_videos(token(), page("busypage"), version(), Some(true), &config()).unwrap();
+        assert_eq!(limited.error.unwrap().category(), meta::ErrorCategory::RateLimit);
+
+        let threads = fetch_facebook_post_comments(token(), Some("100_1".to_string()), version(), None, Some("stream".to_string()), &config()).unwrap().comments.unwrap();
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].replies[0].comment.message.as_deref(), Some("Me too!"));
+    }
+
+    #[test]
+    fn replay_rejects_unrecorded_requests() {
+        let _cassette = TestCassette::replay("facebook");
+
+        assert!(matches!(fetch_facebook_page_info(token(), page("someoneelse"), version(), &config()), Err(MetaStatsError::Request(_))));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::protocol::{model_methods, Repr};
+use crate::snapshot::{self, Snapshot};
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const DAY: f64 = 86_400.0;
+
+/// Windows reported by every `GrowthReport`, in days.
+pub const GROWTH_WINDOWS: [u32; 3] = [7, 30, 90];
+
+/// Width of the rolling average, in days.
+const ROLLING_AVERAGE_DAYS: f64 = 7.0;
+
+/// Monthly active users Meta last reported for each platform, used to put growth on
+/// Facebook and Instagram on the same scale.
+pub const FACEBOOK_MONTHLY_USERS: f64 = 3.07e9;
+pub const INSTAGRAM_MONTHLY_USERS: f64 = 2.0e9;
+
+/// Change of a metric over the `days` before the report's `as_of` time.
+#[pyclass(frozen, module = "meta_stats.growth")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GrowthWindow {
+    #[pyo3(get)]
+    days: u32,
+    #[pyo3(get)]
+    start_value: f64,
+    #[pyo3(get)]
+    start_at: f64,
+    #[pyo3(get)]
+    end_value: f64,
+    #[pyo3(get)]
+    absolute: f64,
+    /// `None` when the window starts at zero.
+    #[pyo3(get)]
+    percent: Option<f64>,
+    /// Average change per day between the two snapshots actually compared.
+    #[pyo3(get)]
+    per_day: f64,
+}
+
+impl GrowthWindow {
+    pub fn absolute(&self) -> f64 {
+        self.absolute
+    }
+
+    pub fn percent(&self) -> Option<f64> {
+        self.percent
+    }
+}
+
+model_methods!(frozen GrowthWindow);
+
+impl Repr for GrowthWindow {
+    fn repr(&self) -> String {
+        match self.percent {
+            Some(percent) => format!("GrowthWindow({}d: {:+} ({:+.2}%))", self.days, self.absolute, percent),
+            None => format!("GrowthWindow({}d: {:+})", self.days, self.absolute),
+        }
+    }
+}
+
+/// Growth and trend figures for one metric of one entity, computed from its snapshot history.
+#[pyclass(frozen, module = "meta_stats.growth")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GrowthReport {
+    #[pyo3(get)]
+    entity: String,
+    #[pyo3(get)]
+    metric: String,
+    /// Time the report is computed for; defaults to the newest snapshot.
+    #[pyo3(get)]
+    as_of: f64,
+    #[pyo3(get)]
+    current: f64,
+    /// One entry per window in `GROWTH_WINDOWS` that the history covers.
+    #[pyo3(get)]
+    windows: Vec<GrowthWindow>,
+    /// `(captured_at, mean of the values in the 7 days up to it)` for every snapshot.
+    #[pyo3(get)]
+    rolling_average: Vec<(f64, f64)>,
+    /// Daily growth over the last 7 days minus daily growth over the 7 days before, so
+    /// positive values mean growth is speeding up.
+    #[pyo3(get)]
+    acceleration: Option<f64>,
+    /// 30-day absolute growth per million monthly users of the platform.
+    #[pyo3(get)]
+    normalized_growth_30d: Option<f64>,
+}
+
+impl GrowthReport {
+    pub fn window(&self, days: u32) -> Option<&GrowthWindow> {
+        self.windows.iter().find(|window| window.days == days)
+    }
+
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+
+    pub fn acceleration(&self) -> Option<f64> {
+        self.acceleration
+    }
+}
+
+model_methods!(frozen GrowthReport {
+    #[getter]
+    fn growth_7d(&self) -> Option<GrowthWindow> {
+        self.window(7).cloned()
+    }
+
+    #[getter]
+    fn growth_30d(&self) -> Option<GrowthWindow> {
+        self.window(30).cloned()
+    }
+
+    #[getter]
+    fn growth_90d(&self) -> Option<GrowthWindow> {
+        self.window(90).cloned()
+    }
+});
+
+impl Repr for GrowthReport {
+    fn repr(&self) -> String {
+        let windows: Vec<String> = self.windows.iter().map(|window| window.repr()).collect();
+        format!("GrowthReport({} {}={}, [{}])", self.entity, self.metric, self.current, windows.join(", "))
+    }
+}
+
+/// Platform size for an entity key such as `instagram_profile:hotelgaruda`.
+pub fn platform_size(entity: &str) -> Option<f64> {
+    match entity.split_once(':').map(|(kind, _)| kind) {
+        Some(snapshot::FACEBOOK_PAGE) | Some(snapshot::FACEBOOK_POST) => Some(FACEBOOK_MONTHLY_USERS),
+        Some(snapshot::INSTAGRAM_PROFILE) | Some(snapshot::INSTAGRAM_MEDIA) => Some(INSTAGRAM_MONTHLY_USERS),
+        _ => None,
+    }
+}
+
+/// Newest snapshot taken at or before `at`.
+fn value_at(history: &[Snapshot], at: f64) -> Option<&Snapshot> {
+    history.iter().rev().find(|snapshot| snapshot.captured_at() <= at)
+}
+
+fn growth_window(history: &[Snapshot], end: &Snapshot, days: u32) -> Option<GrowthWindow> {
+    let start = value_at(history, end.captured_at() - f64::from(days) * DAY)?;
+    let absolute = end.value() - start.value();
+    let elapsed_days = (end.captured_at() - start.captured_at()) / DAY;
+
+    Some(GrowthWindow {
+        days,
+        start_value: start.value(),
+        start_at: start.captured_at(),
+        end_value: end.value(),
+        absolute,
+        percent: (start.value() != 0.0).then(|| absolute / start.value() * 100.0),
+        per_day: if elapsed_days > 0.0 { absolute / elapsed_days } else { 0.0 },
+    })
+}
+
+/// Builds a report from one metric's snapshots. `history` need not be sorted; snapshots for
+/// other entities or metrics than the first one's are ignored. Returns `None` when there is
+/// no snapshot at or before `as_of`.
+///
+/// A window is only reported once the history reaches back far enough to cover it.
+pub fn growth_report(history: &[Snapshot], as_of: Option<f64>, platform_size: Option<f64>) -> Option<GrowthReport> {
+    let first = history.first()?;
+    let mut history: Vec<Snapshot> = history
+        .iter()
+        .filter(|snapshot| snapshot.entity() == first.entity() && snapshot.metric() == first.metric())
+        .cloned()
+        .collect();
+    history.sort_by(|a, b| a.captured_at().total_cmp(&b.captured_at()));
+    if let Some(as_of) = as_of {
+        history.retain(|snapshot| snapshot.captured_at() <= as_of);
+    }
+
+    let end = history.last()?.clone();
+    let as_of = as_of.unwrap_or(end.captured_at());
+
+    let windows: Vec<GrowthWindow> = GROWTH_WINDOWS
+        .iter()
+        .filter_map(|days| growth_window(&history, &end, *days))
+        .collect();
+
+    let rolling_average = history
+        .iter()
+        .map(|snapshot| {
+            let from = snapshot.captured_at() - ROLLING_AVERAGE_DAYS * DAY;
+            let values: Vec<f64> = history
+                .iter()
+                .filter(|other| other.captured_at() > from && other.captured_at() <= snapshot.captured_at())
+                .map(Snapshot::value)
+                .collect();
+            (snapshot.captured_at(), values.iter().sum::<f64>() / values.len() as f64)
+        })
+        .collect();
+
+    let acceleration = value_at(&history, end.captured_at() - 7.0 * DAY).and_then(|week_ago| {
+        let recent = growth_window(&history, &end, 7)?;
+        let previous = growth_window(&history, week_ago, 7)?;
+        Some(recent.per_day - previous.per_day)
+    });
+
+    let platform_size = platform_size.or_else(|| self::platform_size(end.entity()));
+    let normalized_growth_30d = windows
+        .iter()
+        .find(|window| window.days == 30)
+        .zip(platform_size)
+        .map(|(window, size)| window.absolute / (size / 1e6));
+
+    Some(GrowthReport {
+        entity: end.entity().to_string(),
+        metric: end.metric().to_string(),
+        as_of,
+        current: end.value(),
+        windows,
+        rolling_average,
+        acceleration,
+        normalized_growth_30d,
+    })
+}
+
+/// Computes a `GrowthReport` from snapshots such as those returned by `SnapshotStore.history`.
+/// `platform_size` overrides the monthly users used for `normalized_growth_30d`.
+#[pyfunction]
+#[pyo3(name = "growth_report")]
+pub fn py_growth_report(history: Vec<Snapshot>, as_of: Option<f64>, platform_size: Option<f64>) -> Option<GrowthReport> {
+    growth_report(&history, as_of, platform_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daily(values: &[f64]) -> Vec<Snapshot> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(day, value)| Snapshot::new("facebook_page:test", "followers_count", day as f64 * DAY, *value))
+            .collect()
+    }
+
+    #[test]
+    fn windows_need_enough_history() {
+        let report = growth_report(&daily(&[100.0; 10]), None, None).unwrap();
+        assert!(report.window(7).is_some());
+        assert!(report.window(30).is_none());
+    }
+
+    #[test]
+    fn linear_growth_has_no_acceleration() {
+        let values: Vec<f64> = (0..31).map(|day| 1000.0 + 10.0 * day as f64).collect();
+        let report = growth_report(&daily(&values), None, None).unwrap();
+
+        let month = report.window(30).unwrap();
+        assert_eq!(month.absolute(), 300.0);
+        assert_eq!(month.percent(), Some(30.0));
+        assert_eq!(report.acceleration(), Some(0.0));
+        assert_eq!(report.rolling_average.last().map(|(_, average)| *average), Some(1270.0));
+    }
+
+    #[test]
+    fn speeding_up_is_positive_acceleration() {
+        let values: Vec<f64> = (0..15).map(|day| (day * day) as f64).collect();
+        let report = growth_report(&daily(&values), Some(14.0 * DAY), None).unwrap();
+        assert!(report.acceleration().unwrap() > 0.0);
+    }
+}
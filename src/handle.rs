@@ -0,0 +1,408 @@
+use crate::error::MetaStatsError;
+use crate::protocol;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use std::fmt;
+
+const FACEBOOK_DOMAINS: [&str; 2] = ["facebook.com", "fb.com"];
+/// Short-link domain; where a link leads is only known by following the redirect.
+const FACEBOOK_SHORT_DOMAINS: [&str; 1] = ["fb.me"];
+const INSTAGRAM_DOMAINS: [&str; 2] = ["instagram.com", "instagr.am"];
+
+/// First path segments on facebook.com that are features rather than pages.
+const FACEBOOK_RESERVED: [&str; 30] = [
+    "ads", "business", "dialog", "events", "friends", "gaming", "groups", "hashtag", "help", "home.php", "login", "login.php",
+    "marketplace", "media", "messages", "notifications", "permalink.php", "photo", "photo.php", "plugins", "policies", "privacy",
+    "reel", "search", "settings", "share", "sharer", "sharer.php", "stories", "story.php",
+];
+
+/// Tabs of a page that still identify the page itself, e.g. `facebook.com/ChachiOfficial/about`.
+const FACEBOOK_PAGE_TABS: [&str; 9] = ["about", "community", "events", "followers", "mentions", "photos", "posts", "reviews", "videos"];
+
+/// First path segments on instagram.com that are features rather than profiles.
+const INSTAGRAM_RESERVED: [&str; 12] = ["about", "accounts", "developer", "direct", "explore", "legal", "p", "reel", "reels", "stories", "tv", "web"];
+
+/// Tabs of a profile, e.g. `instagram.com/hotelgaruda/reels/`.
+const INSTAGRAM_PROFILE_TABS: [&str; 5] = ["guides", "reels", "saved", "tagged", "feed"];
+
+/// Path and query of a URL whose host is one of `domains`, ignoring subdomains such as `www.`
+/// and `m.`. `None` when the host is something else.
+fn url_path<'a>(url: &'a str, domains: &[&str]) -> Option<(&'a str, &'a str)> {
+    let trimmed = url.trim();
+    let rest = trimmed.split_once("://").map(|(_, rest)| rest).unwrap_or(trimmed);
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(end);
+    let host = host.to_ascii_lowercase();
+    let on_domain = domains.iter().any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+    if !on_domain {
+        return None;
+    }
+    let path = path.split('#').next().unwrap_or_default();
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    Some((path.trim_matches('/'), query))
+}
+
+/// Whether `input` is written as a URL rather than a bare id or handle.
+fn looks_like_url(input: &str) -> bool {
+    input.contains("://") || input.contains('/') || input.contains('?')
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query.split('&').find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+}
+
+/// A Facebook page, either by numeric id or by vanity username. Its string form is what goes
+/// in the Graph API path.
+#[pyclass(frozen, module = "meta_stats.handle")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FacebookPageRef {
+    value: String,
+    numeric: bool,
+}
+
+impl fmt::Display for FacebookPageRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl FacebookPageRef {
+    /// Parses a page id (`100123`), vanity name (`ChachiOfficial` or `@ChachiOfficial`) or page
+    /// URL (`https://www.facebook.com/ChachiOfficial/`, `fb.com/profile.php?id=100123`, ...).
+    /// URLs of posts, groups, events and other non-page content are rejected.
+    pub fn parse(input: &str) -> Result<FacebookPageRef, MetaStatsError> {
+        let trimmed = input.trim();
+        if looks_like_url(trimmed) {
+            return FacebookPageRef::from_url(trimmed);
+        }
+        FacebookPageRef::from_name(trimmed.strip_prefix('@').unwrap_or(trimmed))
+            .ok_or_else(|| invalid(input, "is not a Facebook page id, username or URL"))
+    }
+
+    /// Parses a page URL only; bare names are rejected.
+    pub fn from_url(url: &str) -> Result<FacebookPageRef, MetaStatsError> {
+        if url_path(url, &FACEBOOK_SHORT_DOMAINS).is_some() {
+            return Err(invalid(url, "is a short link; short links must be expanded to the facebook.com URL first"));
+        }
+        let (path, query) = url_path(url, &FACEBOOK_DOMAINS).ok_or_else(|| invalid(url, "is not a Facebook URL"))?;
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        let page = match segments.as_slice() {
+            [] => return Err(invalid(url, "does not name a page")),
+            ["profile.php"] => query_param(query, "id").and_then(FacebookPageRef::from_name).filter(|page| page.numeric),
+            ["pages", _, id] | ["people", _, id] => FacebookPageRef::from_name(id).filter(|page| page.numeric),
+            // `pages/category/Musician-Band/ChachiOfficial`, or `.../Chachi-100123` for a page without a vanity name.
+            ["pages", "category", _, name] => match name.rsplit_once('-') {
+                Some((_, id)) if id.chars().all(|c| c.is_ascii_digit()) => FacebookPageRef::from_name(id),
+                _ => FacebookPageRef::from_name(name),
+            },
+            ["pg", name] | ["pg", name, _] => FacebookPageRef::from_name(name),
+            [first, ..] if FACEBOOK_RESERVED.contains(&first.to_ascii_lowercase().as_str()) => {
+                return Err(invalid(url, "links to a Facebook feature, not a page"));
+            },
+            [name] => FacebookPageRef::from_name(name),
+            [name, tab] if FACEBOOK_PAGE_TABS.contains(&tab.to_ascii_lowercase().as_str()) => FacebookPageRef::from_name(name),
+            _ => return Err(invalid(url, "links to a post or other content, not a page")),
+        };
+        page.ok_or_else(|| invalid(url, "does not contain a valid page id or username"))
+    }
+
+    /// A numeric id, or a vanity name of letters, digits, periods and hyphens.
+    fn from_name(name: &str) -> Option<FacebookPageRef> {
+        if name.is_empty() || name.len() > 50 {
+            return None;
+        }
+        if name.chars().all(|c| c.is_ascii_digit()) {
+            return Some(FacebookPageRef { value: name.to_string(), numeric: true });
+        }
+        let valid = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+            && name.chars().any(|c| c.is_ascii_alphabetic());
+        valid.then(|| FacebookPageRef { value: name.to_string(), numeric: false })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.numeric.then_some(self.value.as_str())
+    }
+
+    pub fn vanity(&self) -> Option<&str> {
+        (!self.numeric).then_some(self.value.as_str())
+    }
+
+    pub fn url(&self) -> String {
+        match self.numeric {
+            true => format!("https://www.facebook.com/profile.php?id={}", self.value),
+            false => format!("https://www.facebook.com/{}", self.value),
+        }
+    }
+}
+
+#[pymethods]
+impl FacebookPageRef {
+    /// Same as `parse`.
+    #[new]
+    fn py_new(input: &str) -> PyResult<FacebookPageRef> {
+        Ok(FacebookPageRef::parse(input)?)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "parse")]
+    fn py_parse(input: &str) -> PyResult<FacebookPageRef> {
+        Ok(FacebookPageRef::parse(input)?)
+    }
+
+    /// Numeric page id, when the page was given by id.
+    #[getter(id)]
+    fn py_id(&self) -> Option<&str> {
+        self.id()
+    }
+
+    /// Vanity username, when the page was given by name.
+    #[getter(vanity)]
+    fn py_vanity(&self) -> Option<&str> {
+        self.vanity()
+    }
+
+    #[getter]
+    fn is_numeric(&self) -> bool {
+        self.numeric
+    }
+
+    #[getter(url)]
+    fn py_url(&self) -> String {
+        self.url()
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        match self.numeric {
+            true => format!("FacebookPageRef(id={})", self.value),
+            false => format!("FacebookPageRef(vanity={})", self.value),
+        }
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        protocol::compare(self, other, op, py)
+    }
+
+    fn __hash__(&self) -> u64 {
+        protocol::hash(self)
+    }
+
+    /// Pickles as a call to `parse` with the string form.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (String,))> {
+        Ok((py.get_type::<FacebookPageRef>().getattr("parse")?.into(), (self.to_string(),)))
+    }
+}
+
+/// A validated Instagram username, lower-cased.
+#[pyclass(frozen, module = "meta_stats.handle")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InstagramHandle {
+    username: String,
+}
+
+impl fmt::Display for InstagramHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.username)
+    }
+}
+
+impl InstagramHandle {
+    /// Parses a username (`hotelgaruda` or `@hotelgaruda`) or profile URL
+    /// (`instagram.com/hotelgaruda/?hl=en`). URLs of posts, reels and stories are rejected.
+    pub fn parse(input: &str) -> Result<InstagramHandle, MetaStatsError> {
+        let trimmed = input.trim();
+        if looks_like_url(trimmed) {
+            return InstagramHandle::from_url(trimmed);
+        }
+        InstagramHandle::from_name(trimmed.strip_prefix('@').unwrap_or(trimmed))
+            .ok_or_else(|| invalid(input, "is not an Instagram username or profile URL"))
+    }
+
+    /// Parses a profile URL only; bare usernames are rejected.
+    pub fn from_url(url: &str) -> Result<InstagramHandle, MetaStatsError> {
+        let (path, _) = url_path(url, &INSTAGRAM_DOMAINS).ok_or_else(|| invalid(url, "is not an Instagram URL"))?;
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        let handle = match segments.as_slice() {
+            [] => return Err(invalid(url, "does not name a profile")),
+            [first, ..] if INSTAGRAM_RESERVED.contains(&first.to_ascii_lowercase().as_str()) => {
+                return Err(invalid(url, "links to a post or Instagram feature, not a profile"));
+            },
+            [name] => InstagramHandle::from_name(name),
+            [name, tab] if INSTAGRAM_PROFILE_TABS.contains(&tab.to_ascii_lowercase().as_str()) => InstagramHandle::from_name(name),
+            _ => return Err(invalid(url, "links to content on a profile, not the profile itself")),
+        };
+        handle.ok_or_else(|| invalid(url, "does not contain a valid username"))
+    }
+
+    /// Up to 30 letters, digits, periods and underscores, not starting with a period or
+    /// containing two in a row.
+    fn from_name(name: &str) -> Option<InstagramHandle> {
+        let valid = !name.is_empty()
+            && name.len() <= 30
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+            && !name.starts_with('.')
+            && !name.contains("..");
+        valid.then(|| InstagramHandle { username: name.to_ascii_lowercase() })
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://www.instagram.com/{}/", self.username)
+    }
+}
+
+#[pymethods]
+impl InstagramHandle {
+    /// Same as `parse`.
+    #[new]
+    fn py_new(input: &str) -> PyResult<InstagramHandle> {
+        Ok(InstagramHandle::parse(input)?)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "parse")]
+    fn py_parse(input: &str) -> PyResult<InstagramHandle> {
+        Ok(InstagramHandle::parse(input)?)
+    }
+
+    #[getter(username)]
+    fn py_username(&self) -> &str {
+        &self.username
+    }
+
+    #[getter(url)]
+    fn py_url(&self) -> String {
+        self.url()
+    }
+
+    fn __str__(&self) -> String {
+        self.username.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("InstagramHandle({})", self.username)
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        protocol::compare(self, other, op, py)
+    }
+
+    fn __hash__(&self) -> u64 {
+        protocol::hash(self)
+    }
+
+    /// Pickles as a call to `parse` with the string form.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (String,))> {
+        Ok((py.get_type::<InstagramHandle>().getattr("parse")?.into(), (self.to_string(),)))
+    }
+}
+
+fn invalid(input: &str, reason: &str) -> MetaStatsError {
+    MetaStatsError::InvalidArgument(format!("'{}' {}", input.trim(), reason))
+}
+
+/// A page argument from Python: a `FacebookPageRef`, or any string `FacebookPageRef.parse`
+/// accepts. Fetch functions parse it again, so a bad string fails there.
+#[derive(FromPyObject)]
+pub enum PageArg {
+    Ref(FacebookPageRef),
+    Text(String),
+}
+
+impl From<PageArg> for String {
+    fn from(arg: PageArg) -> String {
+        match arg {
+            PageArg::Ref(page) => page.value,
+            PageArg::Text(text) => text,
+        }
+    }
+}
+
+/// A profile argument from Python: an `InstagramHandle` or a username or profile URL.
+#[derive(FromPyObject)]
+pub enum HandleArg {
+    Handle(InstagramHandle),
+    Text(String),
+}
+
+impl From<HandleArg> for String {
+    fn from(arg: HandleArg) -> String {
+        match arg {
+            HandleArg::Handle(handle) => handle.username,
+            HandleArg::Text(text) => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(input: &str) -> Option<String> {
+        FacebookPageRef::parse(input).ok().map(|page| page.to_string())
+    }
+
+    fn handle(input: &str) -> Option<String> {
+        InstagramHandle::parse(input).ok().map(|handle| handle.to_string())
+    }
+
+    #[test]
+    fn facebook_pages() {
+        assert_eq!(page("https://www.facebook.com/ChachiOfficial/"), Some("ChachiOfficial".to_string()));
+        assert_eq!(page("m.facebook.com/ChachiOfficial/about"), Some("ChachiOfficial".to_string()));
+        assert_eq!(page("fb.com/profile.php?id=100123&sk=about"), Some("100123".to_string()));
+        assert_eq!(page("https://facebook.com/pages/Chachi/100123"), Some("100123".to_string()));
+        assert_eq!(page("https://www.facebook.com/pages/category/Musician-Band/ChachiOfficial/"), Some("ChachiOfficial".to_string()));
+        assert_eq!(page("facebook.com/pages/category/Musician-Band/Chachi-Live-100123/"), Some("100123".to_string()));
+        assert_eq!(page("@ChachiOfficial"), Some("ChachiOfficial".to_string()));
+        assert!(FacebookPageRef::parse(" 100123 ").unwrap().id().is_some());
+    }
+
+    #[test]
+    fn facebook_non_pages() {
+        assert_eq!(page("https://facebook.com/events/55"), None);
+        assert_eq!(page("https://facebook.com/ChachiOfficial/posts/123"), None);
+        assert_eq!(page("https://facebook.com/"), None);
+        assert_eq!(page("https://linktr.ee/chachi"), None);
+        assert_eq!(page("notfacebook.com/ChachiOfficial"), None);
+        assert_eq!(page("Chachi Official"), None);
+        assert_eq!(page("fb.com/profile.php"), None);
+        assert_eq!(page("https://www.facebook.com/pages/category/Musician-Band/"), None);
+    }
+
+    #[test]
+    fn facebook_short_links_are_rejected() {
+        let error = FacebookPageRef::parse("https://fb.me/ChachiOfficial").unwrap_err();
+        assert!(error.to_string().contains("short links must be expanded"), "{}", error);
+    }
+
+    #[test]
+    fn instagram_handles() {
+        assert_eq!(handle("https://www.instagram.com/HotelGaruda/?hl=en"), Some("hotelgaruda".to_string()));
+        assert_eq!(handle("instagram.com/hotelgaruda"), Some("hotelgaruda".to_string()));
+        assert_eq!(handle("@hotel.garuda_"), Some("hotel.garuda_".to_string()));
+        assert_eq!(handle("instagram.com/hotelgaruda/reels/"), Some("hotelgaruda".to_string()));
+    }
+
+    #[test]
+    fn instagram_non_profiles() {
+        assert_eq!(handle("https://instagram.com/p/C123/"), None);
+        assert_eq!(handle("https://notinstagram.com/hotelgaruda"), None);
+        assert_eq!(handle("hotel..garuda"), None);
+        assert_eq!(handle("hotel garuda"), None);
+        assert_eq!(handle(&"a".repeat(31)), None);
+        assert!(InstagramHandle::from_url("hotelgaruda").is_err());
+    }
+}
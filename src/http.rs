@@ -0,0 +1,159 @@
+use crate::cache::{self, CacheStatus};
+use crate::cassette::{self, Playback};
+use crate::config::MetaConfig;
+use crate::error::MetaStatsError;
+use crate::version;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use std::thread;
+use std::time::Duration;
+
+/// A reqwest client built from a `MetaConfig`, together with its retry policy.
+pub struct HttpClient {
+    client: Client,
+    max_retries: u32,
+    retry_backoff: Duration,
+}
+
+impl HttpClient {
+    pub fn new(config: &MetaConfig) -> Result<Self, MetaStatsError> {
+        let mut builder = Client::builder()
+            .timeout(config.timeout())
+            .connect_timeout(config.connect_timeout());
+
+        if let Some(proxy) = config.proxy() {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| MetaStatsError::Config(format!("Invalid proxy {}: {}", proxy, e)))?;
+            builder = builder.proxy(proxy);
+        }
+
+        let client = builder
+            .build()
+            .map_err(|e| MetaStatsError::Request(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(HttpClient {
+            client,
+            max_retries: config.max_retries(),
+            retry_backoff: config.retry_backoff(),
+        })
+    }
+}
+
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub text: String,
+    pub cache_status: Option<CacheStatus>,
+}
+
+impl HttpResponse {
+    fn from_cache(cached: cache::CachedResponse, cache_status: CacheStatus) -> Self {
+        HttpResponse {
+            status: StatusCode::from_u16(cached.status).unwrap_or(StatusCode::OK),
+            text: cached.body,
+            cache_status: Some(cache_status),
+        }
+    }
+}
+
+/// Sends a GET request, or replays it from the active cassette. Responses are recorded
+/// when a cassette is loaded in record mode.
+///
+/// Transport failures and 429/5xx responses are retried up to the client's `max_retries`,
+/// doubling the backoff after each attempt.
+fn send(client: &HttpClient, url: &str, headers: &[(&str, &str)], label: &str) -> Result<(StatusCode, String), MetaStatsError> {
+    let record = match cassette::playback(url)? {
+        Playback::Replayed { status, body } => {
+            return Ok((StatusCode::from_u16(status).unwrap_or(StatusCode::OK), body));
+        },
+        Playback::Live { record } => record,
+    };
+
+    let mut attempt = 0;
+    let (status, raw_text, served_version) = loop {
+        let outcome = send_once(&client.client, url, headers, label);
+        let retryable = match &outcome {
+            Ok((status, _, _)) => *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+            Err(_) => true,
+        };
+
+        if !retryable || attempt >= client.max_retries {
+            break outcome?;
+        }
+
+        thread::sleep(client.retry_backoff * 2u32.saturating_pow(attempt));
+        attempt += 1;
+    };
+
+    if let Some(served) = served_version {
+        version::check_served_version(url, &served);
+    }
+
+    if record {
+        cassette::record(url, status.as_u16(), &raw_text)?;
+    }
+
+    Ok((status, raw_text))
+}
+
+/// Sends one request, returning the status, body and the `facebook-api-version` header.
+fn send_once(client: &Client, url: &str, headers: &[(&str, &str)], label: &str) -> Result<(StatusCode, String, Option<String>), MetaStatsError> {
+    let mut request = client.get(url);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let res = request
+        .send()
+        .map_err(|e| MetaStatsError::Request(format!("Request failed for {}: {}", label, e)))?;
+
+    let status = res.status();
+    let served_version = res
+        .headers()
+        .get(version::API_VERSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let raw_text = res
+        .text()
+        .map_err(|e| MetaStatsError::Request(format!("Failed to get response text for {}: {}", label, e)))?;
+
+    Ok((status, raw_text, served_version))
+}
+
+/// Performs a GET request that must never be cached, such as one carrying the app secret.
+pub fn get_uncached(client: &HttpClient, url: &str, label: &str) -> Result<HttpResponse, MetaStatsError> {
+    let (status, text) = send(client, url, &[], label)?;
+    Ok(HttpResponse { status, text, cache_status: None })
+}
+
+/// Performs a GET request, going through the response cache when one is configured.
+///
+/// Fresh entries are returned without touching the network. Only successful responses are
+/// stored, and when `stale_while_error` is enabled an expired entry is served in place of
+/// a failed request or an error response.
+pub fn get(client: &HttpClient, url: &str, headers: &[(&str, &str)], label: &str) -> Result<HttpResponse, MetaStatsError> {
+    if !cache::is_enabled() {
+        let (status, text) = send(client, url, headers, label)?;
+        return Ok(HttpResponse { status, text, cache_status: None });
+    }
+
+    let cached = cache::lookup(url);
+    let cached = match cached {
+        Some(cached) if cached.fresh => return Ok(HttpResponse::from_cache(cached, CacheStatus::Hit)),
+        cached => cached.filter(|_| cache::stale_while_error()),
+    };
+
+    match send(client, url, headers, label) {
+        Ok((status, text)) if status.is_success() => {
+            cache::store(url, status.as_u16(), &text);
+            Ok(HttpResponse { status, text, cache_status: Some(CacheStatus::Miss) })
+        },
+        Ok((status, text)) => match cached {
+            Some(cached) => Ok(HttpResponse::from_cache(cached, CacheStatus::Stale)),
+            None => Ok(HttpResponse { status, text, cache_status: Some(CacheStatus::Miss) }),
+        },
+        Err(e) => match cached {
+            Some(cached) => Ok(HttpResponse::from_cache(cached, CacheStatus::Stale)),
+            None => Err(e),
+        },
+    }
+}
@@ -0,0 +1,391 @@
+use crate::artist::ArtistFetchFailure;
+use crate::auth;
+use crate::config::MetaConfig;
+use crate::error::MetaStatsError;
+use crate::facebook::{self, PageLinks};
+use crate::handle::{FacebookPageRef, HandleArg, InstagramHandle, PageArg};
+use crate::instagram::{self, UserInfo};
+use crate::meta::ErrorCategory;
+use crate::protocol::{model_methods, Repr};
+use crate::version::GraphVersion;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Names at least this similar (Jaro-Winkler, after normalising) count as evidence.
+const NAME_MATCH_THRESHOLD: f64 = 0.85;
+
+/// How much each kind of evidence counts on its own. Several pieces combine as independent
+/// signals: `1 - (1 - a)(1 - b)...`.
+const BUSINESS_ACCOUNT_WEIGHT: f64 = 0.95;
+const CONNECTED_ACCOUNT_WEIGHT: f64 = 0.9;
+const PROFILE_LINK_WEIGHT: f64 = 0.7;
+const PAGE_WEBSITE_WEIGHT: f64 = 0.6;
+const NAME_MATCH_WEIGHT: f64 = 0.6;
+
+/// One reason to believe a candidate is the same artist.
+#[pyclass(frozen, module = "meta_stats.identity")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MatchEvidence {
+    /// `instagram_business_account`, `connected_instagram_account`, `profile_link`,
+    /// `page_website` or `name_match`.
+    #[pyo3(get)]
+    kind: String,
+    #[pyo3(get)]
+    detail: String,
+    #[pyo3(get)]
+    weight: f64,
+}
+
+model_methods!(frozen MatchEvidence);
+
+impl Repr for MatchEvidence {
+    fn repr(&self) -> String {
+        format!("MatchEvidence({}, {:.2}: {})", self.kind, self.weight, self.detail)
+    }
+}
+
+/// A possible account for the artist on the other platform.
+#[pyclass(frozen, module = "meta_stats.identity")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdentityCandidate {
+    /// `facebook` or `instagram`.
+    #[pyo3(get)]
+    platform: String,
+    /// Page id or vanity name for Facebook, username for Instagram.
+    #[pyo3(get)]
+    account: String,
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    evidence: Vec<MatchEvidence>,
+}
+
+impl IdentityCandidate {
+    fn new(platform: &str, account: &str) -> IdentityCandidate {
+        IdentityCandidate { platform: platform.to_string(), account: account.to_string(), name: None, evidence: Vec::new() }
+    }
+
+    fn add(&mut self, kind: &str, detail: String, weight: f64) {
+        self.evidence.push(MatchEvidence { kind: kind.to_string(), detail, weight });
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Combined confidence from 0 to 1.
+    pub fn confidence(&self) -> f64 {
+        1.0 - self.evidence.iter().map(|evidence| 1.0 - evidence.weight).product::<f64>()
+    }
+}
+
+model_methods!(frozen IdentityCandidate {
+    #[getter(confidence)]
+    fn py_confidence(&self) -> f64 {
+        self.confidence()
+    }
+});
+
+impl Repr for IdentityCandidate {
+    fn repr(&self) -> String {
+        format!("IdentityCandidate({} {}, confidence={:.2})", self.platform, self.account, self.confidence())
+    }
+}
+
+/// Candidates for the artist's account on the other platform, most likely first, and the
+/// lookups that failed along the way.
+#[pyclass(frozen, module = "meta_stats.identity")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdentityResolution {
+    #[pyo3(get)]
+    platform: String,
+    #[pyo3(get)]
+    account: String,
+    #[pyo3(get)]
+    candidates: Vec<IdentityCandidate>,
+    #[pyo3(get)]
+    failures: Vec<ArtistFetchFailure>,
+}
+
+impl IdentityResolution {
+    pub fn candidates(&self) -> &[IdentityCandidate] {
+        &self.candidates
+    }
+
+    pub fn best(&self) -> Option<&IdentityCandidate> {
+        self.candidates.first()
+    }
+}
+
+model_methods!(frozen IdentityResolution {
+    #[getter(best)]
+    fn py_best(&self) -> Option<IdentityCandidate> {
+        self.best().cloned()
+    }
+});
+
+impl Repr for IdentityResolution {
+    fn repr(&self) -> String {
+        format!(
+            "IdentityResolution({} {}, {} candidates{})",
+            self.platform,
+            self.account,
+            self.candidates.len(),
+            self.best().map(|best| format!(", best={}", best.account)).unwrap_or_default()
+        )
+    }
+}
+
+/// Lower-cases, drops punctuation and filler words like "official" so that "Chachi (Official)"
+/// and "chachi" compare equal.
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !matches!(*word, "official" | "the" | "music" | "page"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two display names from 0 to 1.
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize_name(a), normalize_name(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    strsim::jaro_winkler(&a, &b)
+}
+
+fn add_name_match(candidate: &mut IdentityCandidate, facebook_name: &str, instagram_name: &str) {
+    let similarity = name_similarity(facebook_name, instagram_name);
+    if similarity >= NAME_MATCH_THRESHOLD {
+        candidate.add("name_match", format!("'{}' ~ '{}' ({:.2})", facebook_name, instagram_name, similarity), NAME_MATCH_WEIGHT * similarity);
+    }
+}
+
+/// Whether `account` (a page id or vanity name, as found in a link) refers to the page.
+fn is_same_page(account: &str, page_id: &str, links: Option<&PageLinks>) -> bool {
+    let matches = |other: &str| !other.is_empty() && account.eq_ignore_ascii_case(other);
+    matches(page_id) || links.map(|links| matches(links.id()) || links.username().map(matches).unwrap_or(false)).unwrap_or(false)
+}
+
+/// The token to look pages up with, or `None` after recording Meta's error when it rejects the
+/// app credentials.
+fn resolve_token(access_token: Option<String>, meta_version: &GraphVersion, config: &MetaConfig, failures: &mut Vec<ArtistFetchFailure>) -> Result<Option<String>, MetaStatsError> {
+    let access_token = match access_token {
+        Some(access_token) => access_token,
+        None => match auth::fetch_meta_access_token(None, None, None, None, Some(meta_version.to_string()), config)? {
+            Ok(access_token) => access_token,
+            Err(error) => {
+                failures.push(ArtistFetchFailure::meta("facebook", error.category(), error.message()));
+                return Ok(None);
+            },
+        },
+    };
+    Ok(Some(access_token))
+}
+
+fn fetch_profile(username: &str, config: &MetaConfig, failures: &mut Vec<ArtistFetchFailure>) -> Option<UserInfo> {
+    match instagram::fetch_instagram_page_info(Some(username.to_string()), config) {
+        Ok(result) => match result.into_result() {
+            Ok(page_info) => Some(page_info.user().clone()),
+            Err(error) => {
+                failures.push(ArtistFetchFailure::meta("instagram", error.category(), error.message()));
+                None
+            },
+        },
+        Err(e) => {
+            failures.push(ArtistFetchFailure::local("instagram", &e));
+            None
+        },
+    }
+}
+
+fn fetch_links(access_token: &str, page_id: &str, meta_version: &GraphVersion, config: &MetaConfig, failures: &mut Vec<ArtistFetchFailure>) -> Option<PageLinks> {
+    match facebook::fetch_facebook_page_links(access_token, page_id, meta_version, config) {
+        Ok(Ok(links)) => Some(links),
+        Ok(Err(error)) => {
+            failures.push(ArtistFetchFailure::meta("facebook", error.category(), error.message()));
+            None
+        },
+        Err(e) => {
+            failures.push(ArtistFetchFailure::local("facebook", &e));
+            None
+        },
+    }
+}
+
+/// Adds the failures of looking up one candidate. A guessed account that does not exist is
+/// expected rather than a failure.
+fn add_failures(failures: &mut Vec<ArtistFetchFailure>, lookup: Vec<ArtistFetchFailure>, guessed: bool) {
+    failures.extend(lookup.into_iter().filter(|failure| !(guessed && failure.category() == ErrorCategory::NotFound.as_str())));
+}
+
+/// Candidates with evidence, most likely first. Guessed accounts that nothing matched are dropped.
+fn sorted(mut candidates: Vec<IdentityCandidate>) -> Vec<IdentityCandidate> {
+    candidates.retain(|candidate| !candidate.evidence.is_empty());
+    candidates.sort_by(|a, b| b.confidence().total_cmp(&a.confidence()));
+    candidates
+}
+
+fn candidate_for<'a>(candidates: &'a mut Vec<IdentityCandidate>, platform: &str, account: &str) -> &'a mut IdentityCandidate {
+    match candidates.iter().position(|candidate| candidate.account.eq_ignore_ascii_case(account)) {
+        Some(index) => &mut candidates[index],
+        None => {
+            candidates.push(IdentityCandidate::new(platform, account));
+            candidates.last_mut().expect("just pushed")
+        },
+    }
+}
+
+/// Finds the Instagram account for a Facebook page from the page's linked accounts and website,
+/// then checks each candidate profile for a link back to the page and a matching name. The
+/// page's vanity name is also tried as a username, so a profile nothing links to can still be
+/// found by name; it is kept only if its name or a link back matches.
+pub fn resolve_instagram_for_page(page_id: &str, access_token: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<IdentityResolution, MetaStatsError> {
+    let page = FacebookPageRef::parse(page_id)?;
+    let page_id = page.as_str();
+    let meta_version = GraphVersion::resolve(meta_version, config)?;
+    let mut failures = Vec::new();
+    let mut candidates = Vec::new();
+
+    let access_token = match resolve_token(access_token, &meta_version, config, &mut failures)? {
+        Some(access_token) => access_token,
+        None => return Ok(IdentityResolution { platform: "facebook".to_string(), account: page_id.to_string(), candidates, failures }),
+    };
+    let links = fetch_links(&access_token, page_id, &meta_version, config, &mut failures);
+
+    if let Some(links) = &links {
+        if let Some(username) = links.instagram_business_account().and_then(|account| account.username()) {
+            candidate_for(&mut candidates, "instagram", username).add("instagram_business_account", format!("page {} manages @{}", page_id, username), BUSINESS_ACCOUNT_WEIGHT);
+        }
+        if let Some(username) = links.connected_instagram_account().and_then(|account| account.username()) {
+            candidate_for(&mut candidates, "instagram", username).add("connected_instagram_account", format!("page {} shows @{}", page_id, username), CONNECTED_ACCOUNT_WEIGHT);
+        }
+        if let Some(website) = links.website() {
+            for handle in website.split_whitespace().filter_map(|url| InstagramHandle::from_url(url).ok()) {
+                candidate_for(&mut candidates, "instagram", handle.username()).add("page_website", format!("page website links to {}", website), PAGE_WEBSITE_WEIGHT);
+            }
+        }
+    }
+    let guess = links
+        .as_ref()
+        .and_then(|links| links.username())
+        .and_then(|username| InstagramHandle::parse(username).ok())
+        .map(|handle| handle.username().to_string());
+    if let Some(username) = &guess {
+        candidate_for(&mut candidates, "instagram", username);
+    }
+
+    for candidate in candidates.iter_mut() {
+        let guessed = candidate.evidence.is_empty();
+        let mut lookup_failures = Vec::new();
+        let profile = fetch_profile(&candidate.account, config, &mut lookup_failures);
+        add_failures(&mut failures, lookup_failures, guessed);
+        let profile = match profile {
+            Some(profile) => profile,
+            None => continue,
+        };
+        candidate.name = Some(profile.full_name().to_string()).filter(|name| !name.is_empty());
+
+        let link_back = profile
+            .links()
+            .find(|url| FacebookPageRef::from_url(url).map(|page| is_same_page(page.as_str(), page_id, links.as_ref())).unwrap_or(false))
+            .map(str::to_string);
+        if let Some(url) = link_back {
+            candidate.add("profile_link", format!("@{} links to {}", candidate.account, url), PROFILE_LINK_WEIGHT);
+        }
+        if let Some(links) = &links {
+            add_name_match(candidate, links.name(), profile.full_name());
+        }
+    }
+
+    Ok(IdentityResolution { platform: "facebook".to_string(), account: page_id.to_string(), candidates: sorted(candidates), failures })
+}
+
+/// Finds the Facebook page for an Instagram profile from the profile's external URL and bio
+/// links, then checks each candidate page for a link back (linked account or website) and a
+/// matching name. The username is also tried as a page vanity name, so a page nothing links to
+/// can still be found by name; it is kept only if its name or a link back matches.
+pub fn resolve_facebook_for_instagram(username: &str, access_token: Option<String>, meta_version: Option<String>, config: &MetaConfig) -> Result<IdentityResolution, MetaStatsError> {
+    let handle = InstagramHandle::parse(username)?;
+    let username = handle.username();
+    let mut failures = Vec::new();
+    let mut candidates = Vec::new();
+
+    let profile = fetch_profile(username, config, &mut failures);
+    if let Some(profile) = &profile {
+        for url in profile.links() {
+            if let Ok(page) = FacebookPageRef::from_url(url) {
+                candidate_for(&mut candidates, "facebook", page.as_str()).add("profile_link", format!("@{} links to {}", username, url), PROFILE_LINK_WEIGHT);
+            }
+        }
+    }
+
+    if let Ok(page) = FacebookPageRef::parse(username) {
+        candidate_for(&mut candidates, "facebook", page.as_str());
+    }
+
+    if !candidates.is_empty() {
+        let meta_version = GraphVersion::resolve(meta_version, config)?;
+        if let Some(access_token) = resolve_token(access_token, &meta_version, config, &mut failures)? {
+            for candidate in candidates.iter_mut() {
+                let guessed = candidate.evidence.is_empty();
+                let mut lookup_failures = Vec::new();
+                let links = fetch_links(&access_token, &candidate.account, &meta_version, config, &mut lookup_failures);
+                add_failures(&mut failures, lookup_failures, guessed);
+                let links = match links {
+                    Some(links) => links,
+                    None => continue,
+                };
+                candidate.name = Some(links.name().to_string()).filter(|name| !name.is_empty());
+
+                let is_this_profile = |account: Option<&str>| account.map(|account| account.eq_ignore_ascii_case(username)).unwrap_or(false);
+                if is_this_profile(links.instagram_business_account().and_then(|account| account.username())) {
+                    candidate.add("instagram_business_account", format!("page {} manages @{}", candidate.account, username), BUSINESS_ACCOUNT_WEIGHT);
+                } else if is_this_profile(links.connected_instagram_account().and_then(|account| account.username())) {
+                    candidate.add("connected_instagram_account", format!("page {} shows @{}", candidate.account, username), CONNECTED_ACCOUNT_WEIGHT);
+                }
+                if let Some(website) = links.website() {
+                    if website.split_whitespace().filter_map(|url| InstagramHandle::from_url(url).ok()).any(|linked| linked.username() == username) {
+                        candidate.add("page_website", format!("page website links to {}", website), PAGE_WEBSITE_WEIGHT);
+                    }
+                }
+                if let Some(profile) = &profile {
+                    add_name_match(candidate, links.name(), profile.full_name());
+                }
+            }
+        }
+    }
+
+    Ok(IdentityResolution { platform: "instagram".to_string(), account: username.to_string(), candidates: sorted(candidates), failures })
+}
+
+#[pyfunction]
+#[pyo3(name = "resolve_instagram_for_page")]
+pub fn py_resolve_instagram_for_page(page_id: PageArg, access_token: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<IdentityResolution> {
+    Ok(resolve_instagram_for_page(&String::from(page_id), access_token, meta_version, &MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+#[pyo3(name = "resolve_facebook_for_instagram")]
+pub fn py_resolve_facebook_for_instagram(username: HandleArg, access_token: Option<String>, meta_version: Option<String>, config: Option<MetaConfig>) -> PyResult<IdentityResolution> {
+    Ok(resolve_facebook_for_instagram(&String::from(username), access_token, meta_version, &MetaConfig::or_from_env(config)?)?)
+}
+
+#[pyfunction]
+#[pyo3(name = "name_similarity")]
+pub fn py_name_similarity(a: &str, b: &str) -> f64 {
+    name_similarity(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_ignore_case_punctuation_and_filler() {
+        assert_eq!(name_similarity("Chachi (Official)", "chachi"), 1.0);
+        assert!(name_similarity("Hotel Garuda", "Hotel Garuda Music") > 0.99);
+        assert!(name_similarity("Hotel Garuda", "Kunokini") < NAME_MATCH_THRESHOLD);
+    }
+}
@@ -1,25 +1,220 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use pyo3::exceptions::PyValueError;
-use reqwest::blocking::Client;
 use rand::seq::SliceRandom;
+use crate::cache::CacheStatus;
+use crate::config::MetaConfig;
+use crate::error::MetaStatsError;
+use crate::export::{export_methods, Export, Table, Tabular};
+use crate::handle::{HandleArg, InstagramHandle};
+use crate::http::{self, HttpClient};
 use crate::meta;
+use crate::protocol::{self, model_methods, Repr};
+use crate::snapshot::{self, SnapshotStore};
+use crate::timestamp;
+use chrono::{DateTime, Utc};
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct FollowedBy {
     #[pyo3(get)]
     #[serde(default)]
     count: i32,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct EdgeCount {
+    #[pyo3(get)]
+    #[serde(default)]
+    count: i64,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MediaPageInfo {
+    #[pyo3(get)]
+    #[serde(default)]
+    has_next_page: bool,
+    #[pyo3(get)]
+    end_cursor: Option<String>,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct CaptionNode {
+    #[pyo3(get)]
+    #[serde(default)]
+    text: String,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct CaptionEdge {
+    #[pyo3(get)]
+    #[serde(default)]
+    node: CaptionNode,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct CaptionEdges {
+    #[pyo3(get)]
+    #[serde(default)]
+    edges: Vec<CaptionEdge>,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MediaNode {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    #[serde(default)]
+    shortcode: String,
+    #[pyo3(get)]
+    #[serde(default, with = "timestamp::unix_option")]
+    taken_at_timestamp: Option<DateTime<Utc>>,
+    #[pyo3(get)]
+    #[serde(default)]
+    is_video: bool,
+    #[pyo3(get)]
+    display_url: Option<String>,
+    #[pyo3(get)]
+    video_view_count: Option<i64>,
+    #[pyo3(get)]
+    #[serde(default)]
+    edge_liked_by: EdgeCount,
+    #[pyo3(get)]
+    #[serde(default)]
+    edge_media_to_comment: EdgeCount,
+    #[pyo3(get)]
+    #[serde(default)]
+    edge_media_to_caption: CaptionEdges,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MediaEdge {
+    #[pyo3(get)]
+    #[serde(default)]
+    node: MediaNode,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct TimelineMedia {
+    #[pyo3(get)]
+    #[serde(default)]
+    count: i64,
+    #[pyo3(get)]
+    #[serde(default)]
+    page_info: MediaPageInfo,
+    #[pyo3(get)]
+    #[serde(default)]
+    edges: Vec<MediaEdge>,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct FeedCaption {
+    #[pyo3(get)]
+    #[serde(default)]
+    text: String,
+}
+
+/// One post from the `api/v1/feed/user/<id>/` endpoint, which pages through a profile's full
+/// media history rather than the first twelve posts in `web_profile_info`.
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct FeedMedia {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+    #[pyo3(get)]
+    #[serde(default)]
+    code: String,
+    #[pyo3(get)]
+    #[serde(default, with = "timestamp::unix_option")]
+    taken_at: Option<DateTime<Utc>>,
+    #[pyo3(get)]
+    #[serde(default)]
+    media_type: i32,
+    #[pyo3(get)]
+    #[serde(default)]
+    like_count: i64,
+    #[pyo3(get)]
+    #[serde(default)]
+    comment_count: i64,
+    #[pyo3(get)]
+    play_count: Option<i64>,
+    #[pyo3(get)]
+    caption: Option<FeedCaption>,
+}
+
+impl FeedMedia {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn like_count(&self) -> i64 {
+        self.like_count
+    }
+
+    pub fn comment_count(&self) -> i64 {
+        self.comment_count
+    }
+
+    pub fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = vec![("likes", self.like_count as f64), ("comments", self.comment_count as f64)];
+        if let Some(plays) = self.play_count {
+            metrics.push(("plays", plays as f64));
+        }
+        metrics
+    }
+
+    pub fn taken_at(&self) -> Option<DateTime<Utc>> {
+        self.taken_at
+    }
+}
+
+#[derive(Deserialize)]
+struct FeedPage {
+    #[serde(default)]
+    items: Vec<FeedMedia>,
+    #[serde(default)]
+    more_available: bool,
+    next_max_id: Option<String>,
+    status: String,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BioLink {
+    #[pyo3(get)]
+    #[serde(default)]
+    title: String,
+    #[pyo3(get)]
+    #[serde(default)]
+    url: String,
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct UserInfo {
+    #[pyo3(get)]
+    #[serde(default)]
+    id: String,
+
     #[pyo3(get)]
     #[serde(default)]
     eimu_id: String,
 
+    #[pyo3(get)]
+    #[serde(default)]
+    username: String,
+
     #[pyo3(get)]
     #[serde(default)]
     biography: String,
@@ -40,18 +235,308 @@ pub struct UserInfo {
     #[serde(default)]
     category_name: Option<String>,
 
+    #[pyo3(get)]
+    #[serde(default)]
+    edge_owner_to_timeline_media: TimelineMedia,
+
+    #[pyo3(get)]
+    #[serde(default)]
+    external_url: Option<String>,
+
+    #[pyo3(get)]
+    #[serde(default)]
+    bio_links: Vec<BioLink>,
+
+}
+
+impl UserInfo {
+    /// Numeric user id, which stays the same when the username changes.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn followers_count(&self) -> i64 {
+        i64::from(self.edge_followed_by.count)
+    }
+
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    /// The profile's `external_url` followed by its bio links.
+    pub fn links(&self) -> impl Iterator<Item = &str> {
+        self.external_url.as_deref().into_iter().chain(self.bio_links.iter().map(|link| link.url.as_str()))
+    }
+
+    pub fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("followers_count", f64::from(self.edge_followed_by.count)),
+            ("media_count", self.edge_owner_to_timeline_media.count as f64),
+            ("highlight_reel_count", f64::from(self.highlight_reel_count)),
+        ]
+    }
+
+    pub fn timeline_media(&self) -> &TimelineMedia {
+        &self.edge_owner_to_timeline_media
+    }
+}
+
+impl MediaNode {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn taken_at(&self) -> Option<DateTime<Utc>> {
+        self.taken_at_timestamp
+    }
+
+    pub fn like_count(&self) -> i64 {
+        self.edge_liked_by.count
+    }
+
+    pub fn comment_count(&self) -> i64 {
+        self.edge_media_to_comment.count
+    }
+
+    pub fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let mut metrics = vec![("likes", self.edge_liked_by.count as f64), ("comments", self.edge_media_to_comment.count as f64)];
+        if let Some(views) = self.video_view_count {
+            metrics.push(("video_views", views as f64));
+        }
+        metrics
+    }
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+export_methods!(model TimelineMedia {
+    fn __len__(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        protocol::iter(py, &self.edges)
+    }
+
+    /// An item by position, or a list of items for a slice.
+    fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+        protocol::get_item(py, &self.edges, index)
+    }
+});
+
+impl Export for TimelineMedia {
+    fn table(&self) -> PyResult<Table> {
+        Ok(MediaNode::table(&self.nodes().cloned().collect::<Vec<_>>()))
+    }
+}
+
+impl TimelineMedia {
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &MediaNode> {
+        self.edges.iter().map(|edge| &edge.node)
+    }
+}
+
+model_methods!(
+    FollowedBy,
+    EdgeCount,
+    MediaPageInfo,
+    CaptionNode,
+    CaptionEdge,
+    MediaNode,
+    MediaEdge,
+    FeedCaption,
+    FeedMedia,
+    BioLink,
+    UserInfo,
+    InstagramPageData,
+    InstagramPageInfo,
+);
+
+impl Repr for FollowedBy {
+    fn repr(&self) -> String {
+        format!("FollowedBy({})", self.count)
+    }
+}
+
+impl Repr for EdgeCount {
+    fn repr(&self) -> String {
+        format!("EdgeCount({})", self.count)
+    }
+}
+
+impl Repr for MediaPageInfo {
+    fn repr(&self) -> String {
+        match (self.has_next_page, &self.end_cursor) {
+            (true, Some(cursor)) => format!("MediaPageInfo(more after {})", cursor),
+            _ => "MediaPageInfo(last page)".to_string(),
+        }
+    }
+}
+
+impl Repr for CaptionNode {
+    fn repr(&self) -> String {
+        format!("CaptionNode({:?})", protocol::truncate(&self.text, 40))
+    }
+}
+
+impl Repr for CaptionEdge {
+    fn repr(&self) -> String {
+        format!("CaptionEdge({:?})", protocol::truncate(&self.node.text, 40))
+    }
+}
+
+impl Repr for CaptionEdges {
+    fn repr(&self) -> String {
+        format!("CaptionEdges({})", protocol::count(self.edges.len(), "caption"))
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        protocol::rows_html("CaptionEdges", &self.edges)
+    }
+}
+
+impl Repr for MediaNode {
+    fn repr(&self) -> String {
+        format!("MediaNode({}, {}, {})", self.shortcode, protocol::count(self.edge_liked_by.count, "like"), protocol::count(self.edge_media_to_comment.count, "comment"))
+    }
+}
+
+impl Repr for MediaEdge {
+    fn repr(&self) -> String {
+        format!("MediaEdge({})", self.node.repr())
+    }
+}
+
+impl Repr for TimelineMedia {
+    fn repr(&self) -> String {
+        format!("TimelineMedia({} of {} media)", self.edges.len(), self.count)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        Ok(self.table()?.html("TimelineMedia")?)
+    }
+}
+
+impl Repr for FeedCaption {
+    fn repr(&self) -> String {
+        format!("FeedCaption({:?})", protocol::truncate(&self.text, 40))
+    }
+}
+
+impl Repr for FeedMedia {
+    fn repr(&self) -> String {
+        format!("FeedMedia({}, {}, {})", self.code, protocol::count(self.like_count, "like"), protocol::count(self.comment_count, "comment"))
+    }
+}
+
+impl Repr for BioLink {
+    fn repr(&self) -> String {
+        format!("BioLink({}, {})", self.title, self.url)
+    }
+}
+
+impl Repr for UserInfo {
+    fn repr(&self) -> String {
+        format!("UserInfo({}, {:?}, {})", self.username, self.full_name, protocol::count(self.edge_followed_by.count, "follower"))
+    }
+}
+
+impl Repr for InstagramPageData {
+    fn repr(&self) -> String {
+        format!("InstagramPageData({})", self.user.repr())
+    }
+}
+
+impl Repr for InstagramPageInfo {
+    fn repr(&self) -> String {
+        format!("InstagramPageInfo({}, {})", self.data.user.username, protocol::count(self.data.user.edge_followed_by.count, "follower"))
+    }
+}
+
+model_methods!(CaptionEdges {
+    fn __len__(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        protocol::iter(py, &self.edges)
+    }
+
+    /// An item by position, or a list of items for a slice.
+    fn __getitem__(&self, py: Python<'_>, index: &PyAny) -> PyResult<PyObject> {
+        protocol::get_item(py, &self.edges, index)
+    }
+});
+
+/// Media from `web_profile_info`, with the first caption as `caption`.
+impl Tabular for MediaNode {
+    fn table(rows: &[MediaNode]) -> Table {
+        Table::new()
+            .utf8("id", rows.iter().map(|media| Some(&media.id)))
+            .utf8("shortcode", rows.iter().map(|media| Some(&media.shortcode)))
+            .unix_time("taken_at", rows.iter().map(|media| media.taken_at_timestamp))
+            .boolean("is_video", rows.iter().map(|media| Some(media.is_video)))
+            .utf8("display_url", rows.iter().map(|media| media.display_url.as_ref()))
+            .int64("video_view_count", rows.iter().map(|media| media.video_view_count))
+            .int64("like_count", rows.iter().map(|media| Some(media.edge_liked_by.count)))
+            .int64("comment_count", rows.iter().map(|media| Some(media.edge_media_to_comment.count)))
+            .utf8("caption", rows.iter().map(|media| media.edge_media_to_caption.edges.first().map(|edge| &edge.node.text)))
+            .timestamp("taken_at_utc", rows.iter().map(|media| media.taken_at_timestamp))
+    }
+}
+
+impl Tabular for FeedMedia {
+    fn table(rows: &[FeedMedia]) -> Table {
+        Table::new()
+            .utf8("id", rows.iter().map(|media| Some(&media.id)))
+            .utf8("code", rows.iter().map(|media| Some(&media.code)))
+            .unix_time("taken_at", rows.iter().map(|media| media.taken_at))
+            .int64("media_type", rows.iter().map(|media| Some(i64::from(media.media_type))))
+            .int64("like_count", rows.iter().map(|media| Some(media.like_count)))
+            .int64("comment_count", rows.iter().map(|media| Some(media.comment_count)))
+            .int64("play_count", rows.iter().map(|media| media.play_count))
+            .utf8("caption", rows.iter().map(|media| media.caption.as_ref().map(|caption| &caption.text)))
+            .timestamp("taken_at_utc", rows.iter().map(|media| media.taken_at))
+    }
+}
+
+impl Tabular for InstagramPageInfo {
+    fn table(rows: &[InstagramPageInfo]) -> Table {
+        let users: Vec<&UserInfo> = rows.iter().map(InstagramPageInfo::user).collect();
+        Table::new()
+            .utf8("user_id", users.iter().map(|user| Some(&user.id)))
+            .utf8("username", users.iter().map(|user| Some(&user.username)))
+            .utf8("full_name", users.iter().map(|user| Some(&user.full_name)))
+            .utf8("category_name", users.iter().map(|user| user.category_name.as_ref()))
+            .utf8("external_url", users.iter().map(|user| user.external_url.as_ref()))
+            .int64("followers_count", users.iter().map(|user| Some(user.followers_count())))
+            .int64("media_count", users.iter().map(|user| Some(user.edge_owner_to_timeline_media.count)))
+            .int64("highlight_reel_count", users.iter().map(|user| Some(i64::from(user.highlight_reel_count))))
+    }
+}
+
+impl InstagramPageInfo {
+    pub fn user(&self) -> &UserInfo {
+        &self.data.user
+    }
+}
+
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct InstagramPageData {
     #[pyo3(get)]
     #[serde(default)]
     user: UserInfo,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct InstagramPageInfo {
     #[pyo3(get)]
     #[serde(default)]
@@ -62,23 +547,24 @@ pub struct InstagramPageInfo {
     status: String,
 }
 
-#[pyclass]
-#[derive(Clone)]
+#[pyclass(module = "meta_stats.instagram")]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct InstagramPageInfoResult {
     is_success: bool,
     page_info: Option<InstagramPageInfo>,
     error: Option<meta::MetaInstagramError>,
+    cache_status: Option<CacheStatus>,
 }
 
 
-#[pymethods]
-impl InstagramPageInfoResult {
+model_methods!(InstagramPageInfoResult {
     #[staticmethod]
     fn success(info: InstagramPageInfo) -> Self {
         InstagramPageInfoResult {
             is_success: true,
             page_info: Some(info),
             error: None,
+            cache_status: None,
         }
     }
 
@@ -88,6 +574,7 @@ impl InstagramPageInfoResult {
             is_success: false,
             page_info: None,
             error: Some(err),
+            cache_status: None,
         }
     }
 
@@ -95,12 +582,12 @@ impl InstagramPageInfoResult {
     fn is_success(&self) -> bool {
         self.is_success
     }
-    
+
     #[getter]
     fn is_error(&self) -> bool {
         !self.is_success
     }
-    
+
     #[getter]
     fn page_info(&self) -> Option<Py<InstagramPageInfo>> {
         match &self.page_info {
@@ -108,7 +595,7 @@ impl InstagramPageInfoResult {
             None => None,
         }
     }
-    
+
     #[getter]
     fn error_info(&self) -> Option<Py<meta::MetaInstagramError>> {
         match &self.error {
@@ -116,12 +603,50 @@ impl InstagramPageInfoResult {
             None => None,
         }
     }
-    
-    fn __repr__(&self) -> PyResult<String> {
-        if self.is_success {
-            Ok("InstagramPageInfoResult(Success)".to_string())
-        } else {
-            Ok("InstagramPageInfoResult(Error)".to_string())
+
+    #[getter]
+    fn cache_status(&self) -> Option<&'static str> {
+        self.cache_status.map(|status| status.as_str())
+    }
+});
+
+impl Repr for InstagramPageInfoResult {
+    fn repr(&self) -> String {
+        let outcome = match (&self.page_info, &self.error) {
+            (Some(value), None) => value.repr(),
+            (_, Some(error)) => error.repr(),
+            (None, None) => "empty".to_string(),
+        };
+        protocol::result_repr("InstagramPageInfoResult", outcome, self.cache_status)
+    }
+
+    fn repr_html(&self) -> PyResult<String> {
+        match (&self.page_info, &self.error) {
+            (Some(value), None) => protocol::fields_html(value),
+            (_, Some(error)) => protocol::fields_html(error),
+            (None, None) => protocol::fields_html(self),
+        }
+    }
+}
+
+impl InstagramPageInfoResult {
+    fn with_cache_status(mut self, cache_status: Option<CacheStatus>) -> Self {
+        self.cache_status = cache_status;
+        self
+    }
+
+    pub fn meta_error(&self) -> Option<&meta::MetaInstagramError> {
+        self.error.as_ref()
+    }
+
+    pub fn user(&self) -> Option<&UserInfo> {
+        self.page_info.as_ref().map(InstagramPageInfo::user)
+    }
+
+    pub fn into_result(self) -> Result<InstagramPageInfo, meta::MetaInstagramError> {
+        match (self.page_info, self.error) {
+            (Some(page_info), None) => Ok(page_info),
+            (_, error) => Err(error.unwrap_or_default()),
         }
     }
 }
@@ -150,46 +675,44 @@ const APP_IDS: [&str; 5] = [
 ];
 
 
-#[pyfunction]
-pub fn get_instagram_page_info(username: Option<String>) -> PyResult<Py<InstagramPageInfoResult>> {
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| PyValueError::new_err(format!("Failed to create HTTP client for instagram page info: {}", e)))?;
-    
+/// Browser-like headers with a randomly chosen user agent and web app id.
+fn request_headers() -> [(&'static str, &'static str); 5] {
     let user_agent = USER_AGENTS
         .choose(&mut rand::thread_rng())
         .unwrap_or(&USER_AGENTS[0]);
 
     let app_id = APP_IDS.choose(&mut rand::thread_rng()).unwrap_or(&"936619743392459");
 
+    [
+        ("Accept-Language", "en-US"),
+        ("User-Agent", *user_agent),
+        ("X-IG-App-ID", *app_id),
+        ("X-IG-Capabilities", "3brTvw=="),
+        ("X-IG-Connection-Type", "WIFI"),
+    ]
+}
+
+pub fn fetch_instagram_page_info(username: Option<String>, config: &MetaConfig) -> Result<InstagramPageInfoResult, MetaStatsError> {
+    let client = HttpClient::new(config)?;
 
     match username {
         Some(username) => {
-            let url = format!("https://i.instagram.com/api/v1/users/web_profile_info/?username={}", username);
-            let res = client.get(&url)
-                .header("Accept-Language", "en-US")
-                .header("User-Agent", *user_agent)
-                .header("X-IG-App-ID", *app_id)
-                .header("X-IG-Capabilities", "3brTvw==")
-                .header("X-IG-Connection-Type", "WIFI")
-                .send()
-                .map_err(|e| PyValueError::new_err(format!("Request failed in instagram page info: {}", e)))?;
-
-            let status = res.status();
-            let raw_text = res.text()
-                .map_err(|e| PyValueError::new_err(format!("Failed to get response text: {}", e)))?;
+            let username = InstagramHandle::parse(&username)?;
+            let url = format!("{}/api/v1/users/web_profile_info/?username={}", config.instagram_base_url(), username);
+            let headers = request_headers();
+            let response = http::get(&client, &url, &headers, "instagram page info")?;
+
+            let status = response.status;
+            let raw_text = response.text;
  
             if raw_text.contains("\"status\":\"ok\"") {
                 match serde_json::from_str::<InstagramPageInfo>(&raw_text) {
                     Ok(page_info) => {
-                        let result = InstagramPageInfoResult::success(page_info);
-                        return Python::with_gil(|py| {
-                            Py::new(py, result).map_err(|e| PyErr::from(e))
-                        });
+                        let result = InstagramPageInfoResult::success(page_info).with_cache_status(response.cache_status);
+                        return Ok(result);
                     },
                     Err(e) => {
-                        return Err(PyValueError::new_err(
+                        return Err(MetaStatsError::Parse(
                             format!("Response looks like success but failed to parse to InstagramPageInfo: {}", e)
                         ));
                     }
@@ -198,21 +721,103 @@ pub fn get_instagram_page_info(username: Option<String>) -> PyResult<Py<Instagra
             
             match serde_json::from_str::<meta::MetaInstagramError>(&raw_text) {
                 Ok(error) => {
-                    let result = InstagramPageInfoResult::error(error);
-                    return Python::with_gil(|py| {
-                        Py::new(py, result).map_err(|e| PyErr::from(e))
-                    });
+                    let result = InstagramPageInfoResult::error(error).with_cache_status(response.cache_status);
+                    Ok(result)
                 },
                 Err(e) => {
-                    return Err(PyValueError::new_err(format!(
+                    Err(MetaStatsError::Parse(format!(
                         "Failed to parse response to InstagramPageInfo as either success or error. Status: {}, Parse error: {}, Response preview: {:.200}...", 
                         status, e, raw_text
-                    )));
+                    )))
                 }
             }
         },
-        None => Err(PyValueError::new_err("username must be set to the username of the page to get info for"))?,
+        None => Err(MetaStatsError::InvalidArgument("username must be set to the username of the page to get info for".to_string()))?,
     }
 
 
-}
\ No newline at end of file
+}
+
+/// One page of feed media and the `max_id` for the page after it, if any.
+pub type FeedMediaPage = (Vec<FeedMedia>, Option<String>);
+
+/// Fetches one page of a user's media feed, continuing from `max_id`. Returns the media and
+/// the `max_id` of the following page, or `None` once the history is exhausted.
+pub fn fetch_instagram_feed_page(client: &HttpClient, user_id: &str, max_id: Option<&str>, config: &MetaConfig) -> Result<Result<FeedMediaPage, meta::MetaInstagramError>, MetaStatsError> {
+    let max_id = max_id.map(|max_id| format!("&max_id={}", max_id)).unwrap_or_default();
+    let url = format!("{}/api/v1/feed/user/{}/?count=12{}", config.instagram_base_url(), user_id, max_id);
+    let response = http::get(client, &url, &request_headers(), "instagram feed")?;
+    let raw_text = response.text;
+
+    if let Ok(page) = serde_json::from_str::<FeedPage>(&raw_text) {
+        if page.status == "ok" {
+            let next_max_id = page.next_max_id.filter(|_| page.more_available);
+            return Ok(Ok((page.items, next_max_id)));
+        }
+    }
+
+    match serde_json::from_str::<meta::MetaInstagramError>(&raw_text) {
+        Ok(error) => Ok(Err(error)),
+        Err(e) => Err(MetaStatsError::Parse(format!(
+            "Failed to parse response as either an Instagram feed or error. Status: {}, Parse error: {}, Response preview: {:.200}...",
+            response.status, e, raw_text
+        ))),
+    }
+}
+
+/// The numeric id of the user with `username`, which stays the same when they rename. With a
+/// `store`, its name history is consulted first and filled from the API otherwise. As with
+/// `facebook::resolve_page_id`, stored names are not revalidated unless `refresh` is set.
+pub fn resolve_user_id(username: &str, store: Option<&SnapshotStore>, refresh: bool, config: &MetaConfig) -> Result<Result<String, meta::MetaInstagramError>, MetaStatsError> {
+    let username = InstagramHandle::parse(username)?.to_string();
+    if let Some(id) = store.filter(|_| !refresh).map(|store| store.account_id(snapshot::INSTAGRAM, &username)).transpose()?.flatten() {
+        return Ok(Ok(id));
+    }
+
+    let user_id = fetch_instagram_page_info(Some(username.clone()), config)?.into_result().map(|page_info| page_info.user().id().to_string());
+    if let (Some(store), Ok(id)) = (store, &user_id) {
+        store.record_name(snapshot::INSTAGRAM, id, &username, None)?;
+    }
+    Ok(user_id)
+}
+
+#[pyfunction]
+pub fn get_instagram_page_info(username: Option<HandleArg>, config: Option<MetaConfig>) -> PyResult<Py<InstagramPageInfoResult>> {
+    let result = fetch_instagram_page_info(username.map(String::from), &MetaConfig::or_from_env(config)?)?;
+    Python::with_gil(|py| {
+        Py::new(py, result)
+    })
+}
+
+/// Raises `ValueError` with Instagram's message when the profile cannot be fetched. `refresh`
+/// asks Instagram even when `store` already knows the username.
+#[pyfunction]
+#[pyo3(name = "resolve_user_id")]
+pub fn py_resolve_user_id(username: HandleArg, config: Option<MetaConfig>, store: Option<PyRef<'_, SnapshotStore>>, refresh: Option<bool>) -> PyResult<String> {
+    let username = String::from(username);
+    match resolve_user_id(&username, store.as_deref(), refresh.unwrap_or(false), &MetaConfig::or_from_env(config)?)? {
+        Ok(id) => Ok(id),
+        Err(error) => Err(PyValueError::new_err(format!("Could not resolve Instagram user '{}': {}", username, error.message()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::TestCassette;
+    use crate::config::ConfigValues;
+
+    #[test]
+    fn fetches_profiles_and_rate_limits() {
+        let _cassette = TestCassette::replay("instagram");
+        let config = MetaConfig::load(None, None, Some("META_STATS_TEST_".to_string()), ConfigValues::default()).unwrap();
+
+        let user = fetch_instagram_page_info(Some("hotelgaruda".to_string()), &config).unwrap().page_info.unwrap().data.user;
+        assert_eq!(user.full_name, "Hotel Garuda");
+        assert_eq!(user.edge_followed_by.count, 15400);
+
+        let limited = fetch_instagram_page_info(Some("ratelimited".to_string()), &config).unwrap();
+        assert!(!limited.is_success);
+        assert_eq!(limited.error.unwrap().category(), meta::ErrorCategory::RateLimit);
+    }
+}
@@ -0,0 +1,78 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDateTime, PyDict, PyList, PyTuple};
+use pyo3::PyTypeInfo;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Shared bodies of the constructor and the `to_dict`, `to_json`, `from_json`, `from_dict` and
+/// `__reduce__` methods every model exposes to Python through `protocol::model_methods!`.
+pub fn to_json<T: Serialize + PyTypeInfo>(model: &T, indent: Option<usize>) -> PyResult<String> {
+    let json = match indent {
+        None => serde_json::to_string(model),
+        Some(indent) => {
+            let indent = " ".repeat(indent);
+            let mut json = Vec::new();
+            let mut serializer = serde_json::Serializer::with_formatter(&mut json, serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes()));
+            model.serialize(&mut serializer).map(|_| String::from_utf8_lossy(&json).into_owned())
+        },
+    };
+    json.map_err(|e| PyValueError::new_err(format!("Failed to serialize {}: {}", T::NAME, e)))
+}
+
+pub fn from_json<T: DeserializeOwned + PyTypeInfo>(json: &str) -> PyResult<T> {
+    serde_json::from_str(json).map_err(|e| PyValueError::new_err(format!("Invalid {} JSON: {}", T::NAME, e)))
+}
+
+pub fn to_dict<T: Serialize + PyTypeInfo>(py: Python<'_>, model: &T) -> PyResult<Py<PyDict>> {
+    let value = serde_json::to_value(model).map_err(|e| PyValueError::new_err(format!("Failed to serialize {}: {}", T::NAME, e)))?;
+    Ok(to_python(py, &value)?.downcast::<PyDict>(py)?.into())
+}
+
+/// Reads a model back from `to_dict` output, or any dict of JSON-compatible values. Nested
+/// models may be given as objects rather than dicts, and times as `datetime`s.
+pub fn from_dict<T: DeserializeOwned + PyTypeInfo>(py: Python<'_>, dict: &PyDict) -> PyResult<T> {
+    let nested = PyCFunction::new_closure(py, None, None, |args: &PyTuple, _: Option<&PyDict>| -> PyResult<PyObject> {
+        let value = args.get_item(0)?;
+        match value.is_instance_of::<PyDateTime>() {
+            true => Ok(value.call_method0("isoformat")?.into()),
+            false => Ok(value.call_method0("to_dict")?.into()),
+        }
+    })?;
+    let options = PyDict::new(py);
+    options.set_item("default", nested)?;
+    let json: String = py.import("json")?.call_method("dumps", (dict,), Some(options))?.extract()?;
+    from_json(&json)
+}
+
+/// Builds a model from constructor keyword arguments, named as in `to_dict`.
+pub fn from_fields<T: DeserializeOwned + PyTypeInfo>(py: Python<'_>, fields: Option<&PyDict>) -> PyResult<T> {
+    from_dict(py, fields.unwrap_or_else(|| PyDict::new(py)))
+}
+
+/// Pickles a model as a call to its `from_json` with its JSON form.
+pub fn reduce<T: Serialize + PyTypeInfo>(py: Python<'_>, model: &T) -> PyResult<(PyObject, (String,))> {
+    Ok((T::type_object(py).getattr("from_json")?.into(), (to_json(model, None)?,)))
+}
+
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(flag) => flag.into_py(py),
+        Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+            (Some(integer), _) => integer.into_py(py),
+            (None, Some(integer)) => integer.into_py(py),
+            (None, None) => number.as_f64().unwrap_or(f64::NAN).into_py(py),
+        },
+        Value::String(text) => text.into_py(py),
+        Value::Array(items) => PyList::new(py, items.iter().map(|item| to_python(py, item)).collect::<PyResult<Vec<_>>>()?).into(),
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, to_python(py, value)?)?;
+            }
+            dict.into()
+        },
+    })
+}
@@ -1,9 +1,34 @@
+// pyo3 0.20 expands `#[new]` into impls that newer compilers flag as non-local.
+#![allow(non_local_definitions)]
+
 use pyo3::prelude::*;
 
+pub mod artist;
 pub mod auth;
+pub mod bulk;
+pub mod cache;
+pub mod cassette;
+pub mod config;
+pub mod crawl;
+pub mod dataset;
+mod db;
+pub mod engagement;
+pub mod error;
+pub mod export;
 pub mod facebook;
+pub mod growth;
+pub mod handle;
+mod http;
+pub mod identity;
 pub mod instagram;
+mod json;
 pub mod meta;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
+mod protocol;
+pub mod snapshot;
+pub mod timestamp;
+pub mod version;
 
 /// Python module definition
 #[pymodule]
@@ -13,11 +38,41 @@ fn meta_stats(py: Python, m: &PyModule) -> PyResult<()> {
 
     auth_module.add_function(wrap_pyfunction!(auth::get_meta_client_id, auth_module)?)?;
     auth_module.add_function(wrap_pyfunction!(auth::get_meta_client_secret, auth_module)?)?;
+    auth_module.add_function(wrap_pyfunction!(auth::get_meta_version, auth_module)?)?;
     auth_module.add_function(wrap_pyfunction!(auth::get_meta_access_token, auth_module)?)?;
 
     let facebook_module = PyModule::new(py, "facebook")?;
 
-
+    facebook_module.add_class::<facebook::BasicPageInfo>()?;
+    facebook_module.add_class::<facebook::PageCategory>()?;
+    facebook_module.add_class::<facebook::PageEngagement>()?;
+    facebook_module.add_class::<facebook::CommentParent>()?;
+    facebook_module.add_class::<facebook::CommentInfo>()?;
+    facebook_module.add_class::<facebook::CommentNode>()?;
+    facebook_module.add_class::<facebook::LikeInfo>()?;
+    facebook_module.add_class::<facebook::LikeSummary>()?;
+    facebook_module.add_class::<facebook::CommentSummary>()?;
+    facebook_module.add_class::<facebook::BasicCommentsInfo>()?;
+    facebook_module.add_class::<facebook::BasicLikesInfo>()?;
+    facebook_module.add_class::<facebook::PostShares>()?;
+    facebook_module.add_class::<facebook::PostAttachment>()?;
+    facebook_module.add_class::<facebook::PostAttachments>()?;
+    facebook_module.add_class::<facebook::MessageTag>()?;
+    facebook_module.add_class::<facebook::PostInfo>()?;
+    facebook_module.add_class::<facebook::BasicPostsInfo>()?;
+    facebook_module.add_class::<facebook::PlaceLocation>()?;
+    facebook_module.add_class::<facebook::Place>()?;
+    facebook_module.add_class::<facebook::PageEvent>()?;
+    facebook_module.add_class::<facebook::VideoInsights>()?;
+    facebook_module.add_class::<facebook::PageVideo>()?;
+    facebook_module.add_class::<facebook::BasicPageInfoResult>()?;
+    facebook_module.add_class::<facebook::PageEngagementResult>()?;
+    facebook_module.add_class::<facebook::BasicPostsInfoResult>()?;
+    facebook_module.add_class::<facebook::PageEventsResult>()?;
+    facebook_module.add_class::<facebook::PageVideosResult>()?;
+    facebook_module.add_class::<facebook::PostCommentsResult>()?;
+    facebook_module.add_class::<facebook::InstagramBusinessAccount>()?;
+    facebook_module.add_class::<facebook::PageLinks>()?;
     facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_page_info, facebook_module)?)?;
     facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_page_followers, facebook_module)?)?;
     facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_page_posts, facebook_module)?)?;
@@ -25,21 +80,176 @@ fn meta_stats(py: Python, m: &PyModule) -> PyResult<()> {
     facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_next_results, facebook_module)?)?;
     facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_post_interactions, facebook_module)?)?;
     facebook_module.add_function(wrap_pyfunction!(facebook::parse_next_results_to_basic_posts_info, facebook_module)?)?;
+    facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_page_events, facebook_module)?)?;
+    facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_page_videos, facebook_module)?)?;
+    facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_page_reels, facebook_module)?)?;
+    facebook_module.add_function(wrap_pyfunction!(facebook::get_facebook_post_comments, facebook_module)?)?;
+    facebook_module.add_function(wrap_pyfunction!(facebook::py_resolve_page_id, facebook_module)?)?;
+
+    let config_module = PyModule::new(py, "config")?;
+
+    config_module.add_class::<config::MetaConfig>()?;
+    config_module.add_function(wrap_pyfunction!(config::load_config, config_module)?)?;
+
+    let version_module = PyModule::new(py, "version")?;
+
+    version_module.add_class::<version::GraphVersion>()?;
+    version_module.add_function(wrap_pyfunction!(version::supported_graph_versions, version_module)?)?;
+    version::set_warning_handler(version::python_warning);
+
+    let bulk_module = PyModule::new(py, "bulk")?;
+
+    bulk_module.add_class::<bulk::BulkFetchIterator>()?;
+    bulk_module.add_class::<bulk::BulkPageResult>()?;
+    bulk_module.add_class::<bulk::BulkProfileResult>()?;
+    bulk_module.add_function(wrap_pyfunction!(bulk::bulk_fetch_facebook_pages, bulk_module)?)?;
+    bulk_module.add_function(wrap_pyfunction!(bulk::bulk_fetch_instagram_profiles, bulk_module)?)?;
+
+    let crawl_module = PyModule::new(py, "crawl")?;
+
+    crawl_module.add_class::<crawl::CrawlJob>()?;
+    crawl_module.add_class::<crawl::CrawlProgress>()?;
+
+    let snapshot_module = PyModule::new(py, "snapshot")?;
+
+    snapshot_module.add_class::<snapshot::SnapshotStore>()?;
+    snapshot_module.add_class::<snapshot::Snapshot>()?;
+    snapshot_module.add_class::<snapshot::AccountName>()?;
+    snapshot_module.add_function(wrap_pyfunction!(snapshot::py_entity, snapshot_module)?)?;
+
+    let growth_module = PyModule::new(py, "growth")?;
+
+    growth_module.add_class::<growth::GrowthReport>()?;
+    growth_module.add_class::<growth::GrowthWindow>()?;
+    growth_module.add_function(wrap_pyfunction!(growth::py_growth_report, growth_module)?)?;
+
+    let engagement_module = PyModule::new(py, "engagement")?;
+
+    engagement_module.add_class::<engagement::EngagementSummary>()?;
+    engagement_module.add_class::<engagement::PostEngagement>()?;
+    engagement_module.add_function(wrap_pyfunction!(engagement::py_facebook_engagement, engagement_module)?)?;
+    engagement_module.add_function(wrap_pyfunction!(engagement::py_instagram_engagement, engagement_module)?)?;
+    engagement_module.add_function(wrap_pyfunction!(engagement::py_instagram_profile_engagement, engagement_module)?)?;
+
+    let artist_module = PyModule::new(py, "artist")?;
+
+    artist_module.add_class::<artist::ArtistStats>()?;
+    artist_module.add_class::<artist::PlatformReach>()?;
+    artist_module.add_class::<artist::ArtistFetchFailure>()?;
+    artist_module.add_function(wrap_pyfunction!(artist::get_artist_stats, artist_module)?)?;
+
+    let handle_module = PyModule::new(py, "handle")?;
+
+    handle_module.add_class::<handle::FacebookPageRef>()?;
+    handle_module.add_class::<handle::InstagramHandle>()?;
+
+    let identity_module = PyModule::new(py, "identity")?;
+
+    identity_module.add_class::<identity::IdentityResolution>()?;
+    identity_module.add_class::<identity::IdentityCandidate>()?;
+    identity_module.add_class::<identity::MatchEvidence>()?;
+    identity_module.add_function(wrap_pyfunction!(identity::py_resolve_instagram_for_page, identity_module)?)?;
+    identity_module.add_function(wrap_pyfunction!(identity::py_resolve_facebook_for_instagram, identity_module)?)?;
+    identity_module.add_function(wrap_pyfunction!(identity::py_name_similarity, identity_module)?)?;
+
+    let export_module = PyModule::new(py, "export")?;
+
+    export_module.add_function(wrap_pyfunction!(export::py_to_arrow, export_module)?)?;
+    export_module.add_function(wrap_pyfunction!(export::py_to_pandas, export_module)?)?;
+    export_module.add_function(wrap_pyfunction!(export::py_to_polars, export_module)?)?;
+    export_module.add_function(wrap_pyfunction!(export::columns, export_module)?)?;
+
+    let dataset_module = PyModule::new(py, "dataset")?;
+
+    dataset_module.add_class::<dataset::DatasetWriter>()?;
+    dataset_module.add_class::<dataset::DatasetPart>()?;
 
     let meta_module = PyModule::new(py, "meta")?;
 
+    meta_module.add_class::<meta::Cursor>()?;
+    meta_module.add_class::<meta::Paging>()?;
+    meta_module.add_class::<meta::MetaErrorData>()?;
+    meta_module.add_class::<meta::MetaError>()?;
+    meta_module.add_class::<meta::MetaInstagramError>()?;
+    meta_module.add_function(wrap_pyfunction!(meta::set_base_urls, meta_module)?)?;
+
+    let cassette_module = PyModule::new(py, "cassette")?;
+
+    cassette_module.add_function(wrap_pyfunction!(cassette::use_cassette, cassette_module)?)?;
+    cassette_module.add_function(wrap_pyfunction!(cassette::eject_cassette, cassette_module)?)?;
+
+    let cache_module = PyModule::new(py, "cache")?;
+
+    cache_module.add_function(wrap_pyfunction!(cache::configure_cache, cache_module)?)?;
+    cache_module.add_function(wrap_pyfunction!(cache::disable_cache, cache_module)?)?;
+    cache_module.add_function(wrap_pyfunction!(cache::clear_cache, cache_module)?)?;
+
     let instagram_module = PyModule::new(py, "instagram")?;
 
+    instagram_module.add_class::<instagram::FollowedBy>()?;
+    instagram_module.add_class::<instagram::EdgeCount>()?;
+    instagram_module.add_class::<instagram::MediaPageInfo>()?;
+    instagram_module.add_class::<instagram::CaptionNode>()?;
+    instagram_module.add_class::<instagram::CaptionEdge>()?;
+    instagram_module.add_class::<instagram::CaptionEdges>()?;
+    instagram_module.add_class::<instagram::MediaNode>()?;
+    instagram_module.add_class::<instagram::MediaEdge>()?;
+    instagram_module.add_class::<instagram::TimelineMedia>()?;
+    instagram_module.add_class::<instagram::FeedCaption>()?;
+    instagram_module.add_class::<instagram::FeedMedia>()?;
+    instagram_module.add_class::<instagram::BioLink>()?;
+    instagram_module.add_class::<instagram::UserInfo>()?;
+    instagram_module.add_class::<instagram::InstagramPageData>()?;
+    instagram_module.add_class::<instagram::InstagramPageInfo>()?;
+    instagram_module.add_class::<instagram::InstagramPageInfoResult>()?;
     instagram_module.add_function(wrap_pyfunction!(instagram::get_instagram_page_info, instagram_module)?)?;
+    instagram_module.add_function(wrap_pyfunction!(instagram::py_resolve_user_id, instagram_module)?)?;
 
     m.add_submodule(auth_module)?;
     m.add_submodule(facebook_module)?;
+    m.add_submodule(config_module)?;
     m.add_submodule(meta_module)?;
+    m.add_submodule(bulk_module)?;
+    m.add_submodule(version_module)?;
+    m.add_submodule(crawl_module)?;
+    m.add_submodule(snapshot_module)?;
+    m.add_submodule(growth_module)?;
+    m.add_submodule(engagement_module)?;
+    m.add_submodule(artist_module)?;
+    m.add_submodule(handle_module)?;
+    m.add_submodule(identity_module)?;
+    m.add_submodule(export_module)?;
+    m.add_submodule(dataset_module)?;
     m.add_submodule(instagram_module)?;
+    m.add_submodule(cache_module)?;
+    m.add_submodule(cassette_module)?;
 
     py.import("sys")?.getattr("modules")?.set_item("meta_stats.auth", auth_module)?;
     py.import("sys")?.getattr("modules")?.set_item("meta_stats.facebook", facebook_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.config", config_module)?;
     py.import("sys")?.getattr("modules")?.set_item("meta_stats.meta", meta_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.bulk", bulk_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.version", version_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.crawl", crawl_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.snapshot", snapshot_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.growth", growth_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.engagement", engagement_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.artist", artist_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.handle", handle_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.identity", identity_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.export", export_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.dataset", dataset_module)?;
     py.import("sys")?.getattr("modules")?.set_item("meta_stats.instagram", instagram_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.cache", cache_module)?;
+    py.import("sys")?.getattr("modules")?.set_item("meta_stats.cassette", cassette_module)?;
+
+    #[cfg(feature = "mock-server")]
+    {
+        let mock_module = PyModule::new(py, "mock")?;
+        mock_module.add_class::<mock_server::MockServer>()?;
+        m.add_submodule(mock_module)?;
+        py.import("sys")?.getattr("modules")?.set_item("meta_stats.mock", mock_module)?;
+    }
+
     Ok(())
 }
@@ -1,8 +1,50 @@
+use crate::protocol::{self, model_methods, Repr};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+pub const DEFAULT_GRAPH_BASE_URL: &str = "https://graph.facebook.com";
+pub const DEFAULT_INSTAGRAM_BASE_URL: &str = "https://i.instagram.com";
+
+static GRAPH_BASE_URL: RwLock<Option<String>> = RwLock::new(None);
+static INSTAGRAM_BASE_URL: RwLock<Option<String>> = RwLock::new(None);
+
+/// Base URL of the Graph API, `https://graph.facebook.com` unless overridden with `set_base_urls`.
+pub fn graph_base_url() -> String {
+    GRAPH_BASE_URL
+        .read()
+        .ok()
+        .and_then(|url| url.clone())
+        .unwrap_or_else(|| DEFAULT_GRAPH_BASE_URL.to_string())
+}
+
+/// Base URL of the Instagram web API, `https://i.instagram.com` unless overridden with `set_base_urls`.
+pub fn instagram_base_url() -> String {
+    INSTAGRAM_BASE_URL
+        .read()
+        .ok()
+        .and_then(|url| url.clone())
+        .unwrap_or_else(|| DEFAULT_INSTAGRAM_BASE_URL.to_string())
+}
+
+/// Points every request at different hosts, e.g. a mock server. `None` restores the default.
+pub fn override_base_urls(graph_base_url: Option<String>, instagram_base_url: Option<String>) {
+    if let Ok(mut url) = GRAPH_BASE_URL.write() {
+        *url = graph_base_url.map(|u| u.trim_end_matches('/').to_string());
+    }
+    if let Ok(mut url) = INSTAGRAM_BASE_URL.write() {
+        *url = instagram_base_url.map(|u| u.trim_end_matches('/').to_string());
+    }
+}
+
+#[pyfunction]
+pub fn set_base_urls(graph_base_url: Option<String>, instagram_base_url: Option<String>) -> PyResult<()> {
+    override_base_urls(graph_base_url, instagram_base_url);
+    Ok(())
+}
+
+#[pyclass(module = "meta_stats.meta")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Cursor {
     #[pyo3(get)]
     before: String,
@@ -10,8 +52,8 @@ pub struct Cursor {
     after: String,
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+#[pyclass(module = "meta_stats.meta")]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Paging {
     #[pyo3(get)]
     next: Option<String>,
@@ -19,8 +61,37 @@ pub struct Paging {
     cursors: Cursor
 }
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+impl Paging {
+    pub fn next_url(&self) -> Option<&str> {
+        self.next.as_deref()
+    }
+
+    /// The `after` cursor for the next page, or `None` on the last page. Unlike `next`, it does
+    /// not embed the access token and can be stored.
+    pub fn after_cursor(&self) -> Option<&str> {
+        self.next.as_ref().map(|_| self.cursors.after.as_str())
+    }
+}
+
+model_methods!(Cursor, Paging);
+
+impl Repr for Cursor {
+    fn repr(&self) -> String {
+        format!("Cursor(before={}, after={})", self.before, self.after)
+    }
+}
+
+impl Repr for Paging {
+    fn repr(&self) -> String {
+        match self.next {
+            Some(_) => format!("Paging(next after {})", self.cursors.after),
+            None => "Paging(last page)".to_string(),
+        }
+    }
+}
+
+#[pyclass(module = "meta_stats.meta")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct MetaErrorData {
     #[pyo3(get)]
     message: String,
@@ -29,29 +100,141 @@ pub struct MetaErrorData {
     #[pyo3(get)]
     code: i32,
     #[pyo3(get)]
+    error_subcode: Option<i32>,
+    #[pyo3(get)]
+    #[serde(default)]
     fbtrace_id: String,
     #[pyo3(get)]
+    #[serde(default)]
     is_transient: bool,
 }
+
+model_methods!(MetaErrorData);
+
+impl Repr for MetaErrorData {
+    fn repr(&self) -> String {
+        format!("MetaErrorData({} {}: {})", self.r#type, self.code, protocol::truncate(&self.message, 80))
+    }
+}
     
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+#[pyclass(module = "meta_stats.meta")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct MetaError {
     #[pyo3(get)]
     error: MetaErrorData,
 }
 
 
-#[pyclass]
-#[derive(Serialize, Deserialize, Clone)]
+#[pyclass(module = "meta_stats.meta")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct MetaInstagramError {
     #[pyo3(get)]
     message: String,
     #[pyo3(get)]
+    #[serde(default)]
     require_login: bool,
     #[pyo3(get)]
+    #[serde(default)]
     igweb_rollout: bool,
     #[pyo3(get)]
     status: String,
 }
+
+/// Broad classes of Meta errors, used to decide whether to retry, back off or give up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorCategory {
+    Auth,
+    Permission,
+    RateLimit,
+    NotFound,
+    InvalidRequest,
+    Transient,
+    Unknown,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Auth => "auth",
+            ErrorCategory::Permission => "permission",
+            ErrorCategory::RateLimit => "rate_limit",
+            ErrorCategory::NotFound => "not_found",
+            ErrorCategory::InvalidRequest => "invalid_request",
+            ErrorCategory::Transient => "transient",
+            ErrorCategory::Unknown => "unknown",
+        }
+    }
+}
+
+impl MetaError {
+    pub fn code(&self) -> i32 {
+        self.error.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.error.message
+    }
+
+    /// Classifies the error by its Graph API code, see
+    /// https://developers.facebook.com/docs/graph-api/guides/error-handling
+    pub fn category(&self) -> ErrorCategory {
+        match self.error.code {
+            101 | 102 | 190 | 463 | 467 => ErrorCategory::Auth,
+            10 | 200..=299 => ErrorCategory::Permission,
+            4 | 17 | 32 | 341 | 368 | 613 | 80000..=80014 => ErrorCategory::RateLimit,
+            803 => ErrorCategory::NotFound,
+            1 | 2 => ErrorCategory::Transient,
+            _ if self.error.is_transient => ErrorCategory::Transient,
+            100 => ErrorCategory::InvalidRequest,
+            _ => ErrorCategory::Unknown,
+        }
+    }
+}
+
+impl Repr for MetaError {
+    fn repr(&self) -> String {
+        format!("MetaError({}, {}: {})", self.category().as_str(), self.error.code, protocol::truncate(&self.error.message, 80))
+    }
+}
+
+model_methods!(MetaError {
+    #[getter(category)]
+    fn py_category(&self) -> &'static str {
+        self.category().as_str()
+    }
+});
+
+impl MetaInstagramError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Instagram asks for a login both when it is throttling anonymous requests ("Please wait
+    /// a few minutes") and when a profile genuinely needs one; the message tells them apart.
+    pub fn category(&self) -> ErrorCategory {
+        let message = self.message.to_lowercase();
+        if message.contains("wait") {
+            ErrorCategory::RateLimit
+        } else if self.require_login {
+            ErrorCategory::Auth
+        } else if message.contains("not found") {
+            ErrorCategory::NotFound
+        } else {
+            ErrorCategory::Unknown
+        }
+    }
+}
+
+impl Repr for MetaInstagramError {
+    fn repr(&self) -> String {
+        format!("MetaInstagramError({}, {}: {})", self.category().as_str(), self.status, protocol::truncate(&self.message, 80))
+    }
+}
+
+model_methods!(MetaInstagramError {
+    #[getter(category)]
+    fn py_category(&self) -> &'static str {
+        self.category().as_str()
+    }
+});
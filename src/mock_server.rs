@@ -0,0 +1,711 @@
+use crate::cassette;
+use crate::error::MetaStatsError;
+use crate::meta;
+use crate::timestamp;
+use crate::version;
+use pyo3::prelude::*;
+use reqwest::Url;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tiny_http::{Header, Response, Server};
+
+const DEFAULT_PAGE_SIZE: usize = 25;
+
+struct MockPost {
+    id: String,
+    message: String,
+    created_time: String,
+    reaction_count: usize,
+}
+
+struct MockPage {
+    id: String,
+    /// Vanity name the page can also be fetched by.
+    username: Option<String>,
+    name: String,
+    followers_count: i64,
+    fan_count: i64,
+    posts: Vec<MockPost>,
+    instagram_username: Option<String>,
+    /// Link the account as `connected_instagram_account` rather than `instagram_business_account`.
+    instagram_connected_only: bool,
+    website: Option<String>,
+}
+
+struct MockInstagramUser {
+    id: String,
+    username: String,
+    full_name: String,
+    biography: String,
+    followers_count: i64,
+    media_count: usize,
+    external_url: Option<String>,
+    bio_links: Vec<String>,
+}
+
+#[derive(Clone)]
+enum Fault {
+    Graph { status: u16, code: i32, error_type: &'static str, message: &'static str, is_transient: bool },
+    RequireLogin,
+    ServerError { status: u16 },
+}
+
+impl Fault {
+    fn parse(kind: &str, status: Option<u16>) -> Result<Self, MetaStatsError> {
+        match kind {
+            "invalid_token" => Ok(Fault::Graph { status: 400, code: 190, error_type: "OAuthException", message: "Invalid OAuth access token - Cannot parse access token", is_transient: false }),
+            "app_rate_limit" => Ok(Fault::Graph { status: 403, code: 4, error_type: "OAuthException", message: "(#4) Application request limit reached", is_transient: true }),
+            "throttled" => Ok(Fault::Graph { status: 403, code: 613, error_type: "OAuthException", message: "(#613) Calls to this api have exceeded the rate limit.", is_transient: true }),
+            "permission" => Ok(Fault::Graph { status: 403, code: 10, error_type: "OAuthException", message: "(#10) Application does not have permission for this action", is_transient: false }),
+            "invalid_client" => Ok(Fault::Graph { status: 400, code: 101, error_type: "OAuthException", message: "Error validating client secret.", is_transient: false }),
+            "require_login" => Ok(Fault::RequireLogin),
+            "server_error" => Ok(Fault::ServerError { status: status.unwrap_or(500) }),
+            other => Err(MetaStatsError::InvalidArgument(format!(
+                "kind must be one of 'invalid_token', 'app_rate_limit', 'throttled', 'permission', 'invalid_client', 'require_login' or 'server_error', got '{}'",
+                other
+            ))),
+        }
+    }
+
+    fn response(&self) -> (u16, Value) {
+        match self {
+            Fault::Graph { status, code, error_type, message, is_transient } => (*status, graph_error(*code, error_type, message, *is_transient)),
+            Fault::RequireLogin => (401, json!({
+                "message": "Please wait a few minutes before you try again.",
+                "require_login": true,
+                "igweb_rollout": false,
+                "status": "fail",
+            })),
+            Fault::ServerError { status } => (*status, json!({
+                "error": {
+                    "message": "An unexpected error has occurred. Please retry your request later.",
+                    "type": "OAuthException",
+                    "code": 2,
+                    "fbtrace_id": "MockTrace",
+                    "is_transient": true,
+                }
+            })),
+        }
+    }
+}
+
+struct InjectedFault {
+    path_contains: Option<String>,
+    remaining: u32,
+    fault: Fault,
+}
+
+#[derive(Default)]
+struct MockState {
+    pages: HashMap<String, MockPage>,
+    instagram_users: HashMap<String, MockInstagramUser>,
+    faults: Vec<InjectedFault>,
+    latency: Duration,
+    requests: Vec<String>,
+    served_version: Option<String>,
+}
+
+fn graph_error(code: i32, error_type: &str, message: &str, is_transient: bool) -> Value {
+    json!({
+        "error": {
+            "message": message,
+            "type": error_type,
+            "code": code,
+            "fbtrace_id": "MockTrace",
+            "is_transient": is_transient,
+        }
+    })
+}
+
+impl MockState {
+    fn take_fault(&mut self, path: &str) -> Option<Fault> {
+        let index = self.faults.iter().position(|f| {
+            f.remaining > 0 && f.path_contains.as_ref().map(|p| path.contains(p.as_str())).unwrap_or(true)
+        })?;
+
+        let injected = &mut self.faults[index];
+        injected.remaining -= 1;
+        let fault = injected.fault.clone();
+        if injected.remaining == 0 {
+            self.faults.remove(index);
+        }
+        Some(fault)
+    }
+
+    /// Looks a page up by id or by its current vanity name, as the Graph API does.
+    fn find_page(&self, alias: &str) -> Option<&MockPage> {
+        self.pages.get(alias).or_else(|| {
+            self.pages
+                .values()
+                .find(|page| page.username.as_deref().map(|username| username.eq_ignore_ascii_case(alias)).unwrap_or(false))
+        })
+    }
+
+    fn find_post(&self, post_id: &str) -> Option<&MockPost> {
+        self.pages.values().flat_map(|page| page.posts.iter()).find(|post| post.id == post_id)
+    }
+
+    fn respond(&mut self, base_url: &str, raw_url: &str) -> (u16, Value) {
+        let url = match Url::parse(&format!("http://mock{}", raw_url)) {
+            Ok(url) => url,
+            Err(_) => return (400, graph_error(100, "GraphMethodException", "Malformed request", false)),
+        };
+
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        match segments.as_slice() {
+            ["api", "v1", "users", "web_profile_info"] => self.instagram_profile(&params),
+            ["api", "v1", "feed", "user", id] => self.instagram_feed(id, &params),
+            [_, "oauth", "access_token"] => match (params.get("client_id"), params.get("client_secret")) {
+                (Some(_), Some(_)) => (200, json!({ "access_token": "mock-app-token", "token_type": "bearer" })),
+                _ => (400, graph_error(101, "OAuthException", "Error validating application. Invalid application ID.", false)),
+            },
+            [_, id] => self.page_node(id, &params),
+            [version, id, "posts"] => self.page_posts(base_url, version, id, &params),
+            [_, id, "reactions"] => self.post_reactions(id),
+            _ => (400, graph_error(100, "GraphMethodException", "Unknown path components", false)),
+        }
+    }
+
+    fn page_node(&self, id: &str, params: &HashMap<String, String>) -> (u16, Value) {
+        let page = match self.find_page(id) {
+            Some(page) => page,
+            None => return (404, graph_error(803, "OAuthException", "(#803) Some of the aliases you requested do not exist", false)),
+        };
+
+        let fields = params.get("fields").map(String::as_str).unwrap_or_default();
+        if fields == "id" {
+            return (200, json!({ "id": page.id }));
+        }
+        if fields.contains("instagram_business_account") {
+            let mut links = json!({ "id": page.id, "name": page.name });
+            if let Some(username) = &page.username {
+                links["username"] = json!(username);
+            }
+            if let Some(website) = &page.website {
+                links["website"] = json!(website);
+            }
+            if let Some(user) = page.instagram_username.as_ref().and_then(|username| self.instagram_users.get(username)) {
+                let field = if page.instagram_connected_only { "connected_instagram_account" } else { "instagram_business_account" };
+                links[field] = json!({ "id": user.id, "username": user.username });
+            }
+            return (200, links);
+        }
+
+        if params.contains_key("fields") {
+            (200, json!({
+                "id": page.id,
+                "name": page.name,
+                "category": "Musician/Band",
+                "category_list": [{ "id": "180164648685982", "name": "Musician/Band" }],
+                "followers_count": page.followers_count,
+                "fan_count": page.fan_count,
+                "overall_star_rating": 0.0,
+                "rating_count": 0,
+                "talking_about_count": page.fan_count / 100,
+            }))
+        } else {
+            (200, json!({ "id": page.id, "name": page.name }))
+        }
+    }
+
+    fn page_posts(&self, base_url: &str, version: &str, id: &str, params: &HashMap<String, String>) -> (u16, Value) {
+        let page = match self.find_page(id) {
+            Some(page) => page,
+            None => return (404, graph_error(803, "OAuthException", "(#803) Some of the aliases you requested do not exist", false)),
+        };
+
+        // `since` and `until` are unix seconds, both inclusive.
+        let since: i64 = params.get("since").and_then(|since| since.parse().ok()).unwrap_or(i64::MIN);
+        let until: i64 = params.get("until").and_then(|until| until.parse().ok()).unwrap_or(i64::MAX);
+        let posts: Vec<&MockPost> = page
+            .posts
+            .iter()
+            .filter(|post| timestamp::parse(&post.created_time).map(|time| (since..=until).contains(&time.timestamp())).unwrap_or(false))
+            .collect();
+
+        let start: usize = params.get("after").and_then(|after| after.parse().ok()).unwrap_or(0);
+        let limit: usize = params.get("limit").and_then(|limit| limit.parse().ok()).unwrap_or(DEFAULT_PAGE_SIZE);
+        let end = (start + limit).min(posts.len());
+        let with_summary = params.get("fields").map(|fields| fields.contains("summary(true)")).unwrap_or(false);
+
+        let data: Vec<Value> = posts[start.min(end)..end]
+            .iter()
+            .map(|post| {
+                let mut value = json!({ "id": post.id, "message": post.message, "created_time": post.created_time });
+                if with_summary {
+                    value["likes"] = json!({ "data": [], "summary": { "total_count": post.reaction_count, "can_like": true, "has_liked": false } });
+                    value["comments"] = json!({ "data": [], "summary": { "order": "ranked", "total_count": post.reaction_count / 10, "can_comment": true } });
+                }
+                value
+            })
+            .collect();
+
+        let mut paging = json!({ "cursors": { "before": start.to_string(), "after": end.to_string() } });
+        if end < posts.len() {
+            let mut next = format!("{}/{}/{}/posts?", base_url, version, id);
+            for name in ["fields", "since", "until"] {
+                if let Some(value) = params.get(name) {
+                    next.push_str(&format!("{}={}&", name, value));
+                }
+            }
+            next.push_str(&format!(
+                "access_token={}&limit={}&after={}",
+                params.get("access_token").map(String::as_str).unwrap_or_default(),
+                limit,
+                end
+            ));
+            paging["next"] = json!(next);
+        }
+
+        (200, json!({ "data": data, "paging": paging }))
+    }
+
+    fn post_reactions(&self, id: &str) -> (u16, Value) {
+        match self.find_post(id) {
+            Some(post) => {
+                let data: Vec<Value> = (0..post.reaction_count.min(DEFAULT_PAGE_SIZE))
+                    .map(|n| json!({ "id": format!("{}", 9000 + n), "name": format!("Fan {}", n + 1), "type": if n % 3 == 0 { "LOVE" } else { "LIKE" } }))
+                    .collect();
+                (200, json!({ "data": data, "paging": { "cursors": { "before": "0", "after": data.len().to_string() } } }))
+            },
+            None => (400, graph_error(100, "GraphMethodException", "Unsupported get request. Object does not exist", false)),
+        }
+    }
+
+    /// The media feed, paged with `max_id` holding the index of the next item. Media are one
+    /// day apart, newest first.
+    fn instagram_feed(&self, user_id: &str, params: &HashMap<String, String>) -> (u16, Value) {
+        let user = match self.instagram_users.values().find(|user| user.id == user_id) {
+            Some(user) => user,
+            None => return (404, json!({ "message": "User not found", "require_login": false, "igweb_rollout": false, "status": "fail" })),
+        };
+
+        let start: usize = params.get("max_id").and_then(|max_id| max_id.parse().ok()).unwrap_or(0);
+        let count: usize = params.get("count").and_then(|count| count.parse().ok()).unwrap_or(12);
+        let end = (start + count).min(user.media_count);
+
+        let items: Vec<Value> = (start..end)
+            .map(|i| json!({
+                "id": format!("{}_{}", 3_000_000 + i, user.id),
+                "code": format!("C{}", i),
+                "taken_at": 1_706_464_800 - (i as i64) * 86_400,
+                "media_type": 1,
+                "like_count": (i as i64 + 1) * 5,
+                "comment_count": i as i64 + 1,
+                "caption": { "text": format!("Media {} from {}", i + 1, user.full_name) },
+            }))
+            .collect();
+
+        let more_available = end < user.media_count;
+        (200, json!({
+            "items": items,
+            "num_results": end - start,
+            "more_available": more_available,
+            "next_max_id": if more_available { Value::String(end.to_string()) } else { Value::Null },
+            "status": "ok",
+        }))
+    }
+
+    fn instagram_profile(&self, params: &HashMap<String, String>) -> (u16, Value) {
+        let user = params.get("username").and_then(|username| self.instagram_users.get(username));
+        match user {
+            Some(user) => (200, json!({
+                "data": {
+                    "user": {
+                        "id": user.id,
+                        "username": user.username,
+                        "full_name": user.full_name,
+                        "biography": user.biography,
+                        "external_url": user.external_url,
+                        "bio_links": user.bio_links.iter().map(|url| json!({ "title": "", "url": url, "link_type": "external" })).collect::<Vec<Value>>(),
+                        "edge_followed_by": { "count": user.followers_count },
+                        "highlight_reel_count": 0,
+                        "category_name": Value::Null,
+                        "edge_owner_to_timeline_media": {
+                            "count": user.media_count,
+                            "edges": (0..user.media_count.min(12)).map(|i| json!({ "node": {
+                                "id": format!("{}_{}", 3_000_000 + i, user.id),
+                                "shortcode": format!("C{}", i),
+                                "taken_at_timestamp": 1_706_464_800 - (i as i64) * 86_400,
+                                "is_video": false,
+                                "edge_liked_by": { "count": (i as i64 + 1) * 5 },
+                                "edge_media_to_comment": { "count": i as i64 + 1 },
+                            } })).collect::<Vec<Value>>(),
+                        },
+                    }
+                },
+                "status": "ok",
+            })),
+            None => (404, json!({ "message": "User not found", "require_login": false, "igweb_rollout": false, "status": "fail" })),
+        }
+    }
+}
+
+fn serve(server: Arc<Server>, state: Arc<Mutex<MockState>>, base_url: String) {
+    for request in server.incoming_requests() {
+        let state = Arc::clone(&state);
+        let base_url = base_url.clone();
+
+        thread::spawn(move || {
+            let raw_url = request.url().to_string();
+            let requested_version = raw_url
+                .split(['/', '?'])
+                .find(|segment| !segment.is_empty())
+                .filter(|segment| segment.starts_with('v'))
+                .map(str::to_string);
+
+            let (latency, served_version, (status, body)) = match state.lock() {
+                Ok(mut state) => {
+                    state.requests.push(cassette::scrub(&raw_url));
+                    let path = raw_url.split('?').next().unwrap_or_default().to_string();
+                    let response = match state.take_fault(&path) {
+                        Some(fault) => fault.response(),
+                        None => state.respond(&base_url, &raw_url),
+                    };
+                    let served_version = state.served_version.clone().or(requested_version);
+                    (state.latency, served_version, response)
+                },
+                Err(_) => (Duration::ZERO, None, (500, json!({ "message": "mock server state poisoned" }))),
+            };
+
+            if !latency.is_zero() {
+                thread::sleep(latency);
+            }
+
+            let mut response = Response::from_string(body.to_string()).with_status_code(status);
+            if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+                response = response.with_header(header);
+            }
+            if let Some(header) = served_version.and_then(|version| Header::from_bytes(version::API_VERSION_HEADER, version).ok()) {
+                response = response.with_header(header);
+            }
+            let _ = request.respond(response);
+        });
+    }
+}
+
+/// A local stand-in for the Graph API and Instagram's `web_profile_info`, for testing code
+/// built on this library. Pages, posts and profiles are scripted with the `add_*` methods,
+/// and Meta error bodies, 5xx responses and latency can be injected.
+#[pyclass(module = "meta_stats.mock")]
+pub struct MockServer {
+    state: Arc<Mutex<MockState>>,
+    server: Arc<Server>,
+    handle: Option<JoinHandle<()>>,
+    url: String,
+}
+
+impl MockServer {
+    fn state(&self) -> Result<std::sync::MutexGuard<'_, MockState>, MetaStatsError> {
+        self.state
+            .lock()
+            .map_err(|e| MetaStatsError::Io(format!("Failed to lock mock server state: {}", e)))
+    }
+
+    fn shutdown(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[pymethods]
+impl MockServer {
+    #[staticmethod]
+    pub fn start(port: Option<u16>) -> Result<Self, MetaStatsError> {
+        let server = Server::http(("127.0.0.1", port.unwrap_or(0)))
+            .map_err(|e| MetaStatsError::Io(format!("Failed to start mock server: {}", e)))?;
+
+        let port = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.port())
+            .ok_or_else(|| MetaStatsError::Io("Mock server is not listening on an IP address".to_string()))?;
+
+        let url = format!("http://127.0.0.1:{}", port);
+        let server = Arc::new(server);
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let handle = {
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            let url = url.clone();
+            thread::spawn(move || serve(server, state, url))
+        };
+
+        Ok(MockServer {
+            state,
+            server,
+            handle: Some(handle),
+            url,
+        })
+    }
+
+    #[getter]
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Paths requested so far, with access tokens and secrets scrubbed.
+    #[getter]
+    fn requests(&self) -> Result<Vec<String>, MetaStatsError> {
+        Ok(self.state()?.requests.clone())
+    }
+
+    pub fn add_page(&self, page_id: String, name: String, followers_count: Option<i64>, fan_count: Option<i64>, post_count: Option<usize>) -> Result<(), MetaStatsError> {
+        let posts = (0..post_count.unwrap_or(0))
+            .map(|n| MockPost {
+                id: format!("{}_{}", page_id, n + 1),
+                message: format!("Post {} from {}", n + 1, name),
+                created_time: format!("2024-01-{:02}T18:00:00+0000", 28 - (n % 28)),
+                reaction_count: (n + 1) * 10,
+            })
+            .collect();
+
+        let page = MockPage {
+            id: page_id.clone(),
+            username: None,
+            name,
+            followers_count: followers_count.unwrap_or(0),
+            fan_count: fan_count.unwrap_or(0),
+            posts,
+            instagram_username: None,
+            instagram_connected_only: false,
+            website: None,
+        };
+
+        self.state()?.pages.insert(page_id, page);
+        Ok(())
+    }
+
+    /// Links a page to an Instagram account, reported as the page's `instagram_business_account`
+    /// or, with `connected_only`, its `connected_instagram_account`.
+    fn link_instagram_account(&self, page_id: String, username: String, connected_only: Option<bool>) -> Result<(), MetaStatsError> {
+        match self.state()?.pages.get_mut(&page_id) {
+            Some(page) => {
+                page.instagram_username = Some(username);
+                page.instagram_connected_only = connected_only.unwrap_or(false);
+                Ok(())
+            },
+            None => Err(MetaStatsError::InvalidArgument(format!("No mock page '{}'", page_id))),
+        }
+    }
+
+    fn set_page_website(&self, page_id: String, website: String) -> Result<(), MetaStatsError> {
+        match self.state()?.pages.get_mut(&page_id) {
+            Some(page) => {
+                page.website = Some(website);
+                Ok(())
+            },
+            None => Err(MetaStatsError::InvalidArgument(format!("No mock page '{}'", page_id))),
+        }
+    }
+
+    /// Gives the page a vanity name it can be fetched by instead of its id. Setting it again
+    /// renames the page; the old name stops resolving.
+    fn set_page_username(&self, page_id: String, username: String) -> Result<(), MetaStatsError> {
+        match self.state()?.pages.get_mut(&page_id) {
+            Some(page) => {
+                page.username = Some(username);
+                Ok(())
+            },
+            None => Err(MetaStatsError::InvalidArgument(format!("No mock page '{}'", page_id))),
+        }
+    }
+
+    /// Changes an Instagram user's username, keeping their id.
+    fn rename_instagram_user(&self, username: String, new_username: String) -> Result<(), MetaStatsError> {
+        let mut state = self.state()?;
+        match state.instagram_users.remove(&username) {
+            Some(mut user) => {
+                user.username = new_username.clone();
+                state.instagram_users.insert(new_username, user);
+                Ok(())
+            },
+            None => Err(MetaStatsError::InvalidArgument(format!("No mock Instagram user '{}'", username))),
+        }
+    }
+
+    /// Sets the profile's `external_url` and `bio_links`.
+    fn set_instagram_links(&self, username: String, external_url: Option<String>, bio_links: Option<Vec<String>>) -> Result<(), MetaStatsError> {
+        match self.state()?.instagram_users.get_mut(&username) {
+            Some(user) => {
+                user.external_url = external_url;
+                user.bio_links = bio_links.unwrap_or_default();
+                Ok(())
+            },
+            None => Err(MetaStatsError::InvalidArgument(format!("No mock Instagram user '{}'", username))),
+        }
+    }
+
+    pub fn add_instagram_user(&self, username: String, full_name: String, followers_count: Option<i64>, biography: Option<String>, media_count: Option<usize>) -> Result<(), MetaStatsError> {
+        let mut state = self.state()?;
+        let id = format!("{}", 17_000_000 + state.instagram_users.len());
+        state.instagram_users.insert(username.clone(), MockInstagramUser {
+            id,
+            username,
+            full_name,
+            biography: biography.unwrap_or_default(),
+            followers_count: followers_count.unwrap_or(0),
+            media_count: media_count.unwrap_or(0),
+            external_url: None,
+            bio_links: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Makes the next `times` requests whose path contains `path_contains` (any request when
+    /// unset) fail with the given kind: `invalid_token` (190), `app_rate_limit` (4),
+    /// `throttled` (613), `permission` (10), `invalid_client` (101, the app token request
+    /// rejecting the client secret), `require_login` or `server_error` (5xx, `status` defaults
+    /// to 500).
+    pub fn inject_error(&self, kind: String, path_contains: Option<String>, times: Option<u32>, status: Option<u16>) -> Result<(), MetaStatsError> {
+        let fault = Fault::parse(&kind, status)?;
+        self.state()?.faults.push(InjectedFault {
+            path_contains,
+            remaining: times.unwrap_or(1),
+            fault,
+        });
+        Ok(())
+    }
+
+    /// Reports `version` in the `facebook-api-version` header of every Graph response, as Meta
+    /// does after silently upgrading a retired version. `None` echoes the requested version.
+    fn set_served_version(&self, version: Option<String>) -> Result<(), MetaStatsError> {
+        self.state()?.served_version = version;
+        Ok(())
+    }
+
+    fn set_latency(&self, milliseconds: u64) -> Result<(), MetaStatsError> {
+        self.state()?.latency = Duration::from_millis(milliseconds);
+        Ok(())
+    }
+
+    /// Sends every Graph and Instagram request made by this library to the mock server.
+    fn use_for_requests(&self) -> Result<(), MetaStatsError> {
+        meta::override_base_urls(Some(self.url.clone()), Some(self.url.clone()));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), MetaStatsError> {
+        self.shutdown();
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyResult<PyRef<'_, Self>> {
+        slf.use_for_requests()?;
+        Ok(slf)
+    }
+
+    fn __exit__(&mut self, _exc_type: PyObject, _exc_value: PyObject, _traceback: PyObject) -> PyResult<bool> {
+        self.shutdown();
+        meta::override_base_urls(None, None);
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::TestCassette;
+    use crate::config::{ConfigValues, MetaConfig};
+    use crate::facebook;
+    use crate::http::HttpClient;
+    use crate::instagram;
+    use crate::version::GraphVersion;
+
+    fn config(server: &MockServer) -> MetaConfig {
+        let values = ConfigValues {
+            graph_base_url: Some(server.url()),
+            instagram_base_url: Some(server.url()),
+            version: Some("v22.0".to_string()),
+            ..ConfigValues::default()
+        };
+        MetaConfig::load(None, None, Some("META_STATS_TEST_".to_string()), values).unwrap()
+    }
+
+    fn server() -> MockServer {
+        let server = MockServer::start(None).unwrap();
+        server.add_page("ChachiOfficial".to_string(), "Chachi".to_string(), Some(52000), Some(48000), Some(30)).unwrap();
+        server.add_instagram_user("hotelgaruda".to_string(), "Hotel Garuda".to_string(), Some(15400), None, Some(14)).unwrap();
+        server
+    }
+
+    #[test]
+    fn records_then_replays_without_the_server() {
+        let server = server();
+        let config = config(&server);
+        let path = std::env::temp_dir().join(format!("meta_stats_cassette_{}.json", std::process::id()));
+        let token = Some("secret-token".to_string());
+        let page = Some("ChachiOfficial".to_string());
+
+        let recorded = {
+            let _cassette = TestCassette::load(path.clone(), true);
+            facebook::fetch_facebook_page_posts_with_summary(token.clone(), page.clone(), None, None, None, None, &config).unwrap()
+        };
+        let requests = server.requests().unwrap().len();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains("secret-token"));
+        assert!(saved.contains("access_token=REDACTED"));
+
+        let replayed = {
+            let _cassette = TestCassette::load(path.clone(), false);
+            facebook::fetch_facebook_page_posts_with_summary(token, page, None, None, None, None, &config).unwrap()
+        };
+        assert_eq!(server.requests().unwrap().len(), requests);
+        let posts = |result| serde_json::to_value(result).unwrap()["posts_info"]["data"].clone();
+        assert!(posts(&replayed) == posts(&recorded), "replayed posts differ from the recording");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn serves_post_pages_links_and_feeds() {
+        let _cassette = TestCassette::live();
+        let server = server();
+        let config = config(&server);
+        let client = HttpClient::new(&config).unwrap();
+        let version = GraphVersion::parse("v22.0").unwrap();
+
+        let (posts, after) = facebook::fetch_facebook_posts_page(&client, "token", "ChachiOfficial", &version, None, &config).unwrap().ok().unwrap();
+        assert_eq!(posts.len(), DEFAULT_PAGE_SIZE);
+        let (rest, after) = facebook::fetch_facebook_posts_page(&client, "token", "ChachiOfficial", &version, after.as_deref(), &config).unwrap().ok().unwrap();
+        assert_eq!(rest.len(), 5);
+        assert!(after.is_none());
+
+        server.link_instagram_account("ChachiOfficial".to_string(), "hotelgaruda".to_string(), None).unwrap();
+        let links = facebook::fetch_facebook_page_links("token", "ChachiOfficial", &version, &config).unwrap().ok().unwrap();
+        assert_eq!(links.name(), "Chachi");
+        assert!(links.instagram_business_account().is_some());
+
+        let user_id = instagram::resolve_user_id("hotelgaruda", None, false, &config).unwrap().ok().unwrap();
+        let (media, _) = instagram::fetch_instagram_feed_page(&client, &user_id, None, &config).unwrap().ok().unwrap();
+        assert!(!media.is_empty());
+    }
+
+    #[test]
+    fn injects_meta_errors() {
+        let _cassette = TestCassette::live();
+        let server = server();
+        let config = config(&server);
+        server.inject_error("invalid_token".to_string(), Some("ChachiOfficial".to_string()), None, None).unwrap();
+
+        let result = facebook::fetch_facebook_page_followers(Some("token".to_string()), Some("ChachiOfficial".to_string()), None, &config).unwrap();
+        assert_eq!(result.into_result().err().unwrap().category(), meta::ErrorCategory::Auth);
+        assert!(MockServer::start(None).unwrap().inject_error("teapot".to_string(), None, None, None).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::cache::CacheStatus;
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyList, PySlice};
+use pyo3::PyTypeInfo;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::os::raw::c_long;
+
+/// Rows an HTML table shows before the rest are counted in its footer.
+pub const HTML_ROWS: usize = 50;
+
+/// Shared bodies of the Python protocol methods models implement: `__repr__`, the Jupyter
+/// `_repr_html_` view, `__len__`/`__iter__`/`__getitem__` on models wrapping a list, and
+/// `__hash__` on value types.
+pub trait Repr {
+    /// One line naming the model and its key fields.
+    fn repr(&self) -> String;
+
+    /// The Jupyter view: a table of the model's fields unless the model has a better one.
+    fn repr_html(&self) -> PyResult<String>
+    where
+        Self: Serialize + PyTypeInfo,
+    {
+        fields_html(self)
+    }
+}
+
+/// `text`, cut to `limit` characters with an ellipsis, on one line.
+pub fn truncate(text: &str, limit: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(limit) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// `1 post`, `2 posts`, `3 replies`.
+pub fn count(n: impl fmt::Display, noun: &str) -> String {
+    let n = n.to_string();
+    match (n.as_str(), noun.strip_suffix('y')) {
+        ("1", _) => format!("{} {}", n, noun),
+        (_, Some(stem)) => format!("{} {}ies", n, stem),
+        (_, None) => format!("{} {}s", n, noun),
+    }
+}
+
+/// `Name(outcome)` for a result, noting the cache status when the response came through the
+/// cache.
+pub fn result_repr(name: &str, outcome: String, cache_status: Option<CacheStatus>) -> String {
+    match cache_status {
+        Some(status) => format!("{}({}, cache={})", name, outcome, status.as_str()),
+        None => format!("{}({})", name, outcome),
+    }
+}
+
+pub fn iter<T: Clone + IntoPy<PyObject>>(py: Python<'_>, items: &[T]) -> PyResult<PyObject> {
+    let list = PyList::new(py, items.iter().cloned().map(|item| item.into_py(py)));
+    Ok(list.call_method0("__iter__")?.into())
+}
+
+/// `items[index]`, with negative indexes counted from the end, or a list for a slice.
+pub fn get_item<T: Clone + IntoPy<PyObject>>(py: Python<'_>, items: &[T], index: &PyAny) -> PyResult<PyObject> {
+    if let Ok(slice) = index.downcast::<PySlice>() {
+        let indices = slice.indices(items.len() as c_long)?;
+        let picked = (0..indices.slicelength).map(|i| items[(indices.start + i * indices.step) as usize].clone().into_py(py));
+        return Ok(PyList::new(py, picked.collect::<Vec<_>>()).into());
+    }
+    let index: isize = index.extract()?;
+    let position = if index < 0 { index + items.len() as isize } else { index };
+    match usize::try_from(position).ok().and_then(|position| items.get(position)) {
+        Some(item) => Ok(item.clone().into_py(py)),
+        None => Err(PyIndexError::new_err(format!("index {} out of range for {} items", index, items.len()))),
+    }
+}
+
+pub fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes a model through its JSON form, for models holding floats that have no `Hash`. Models
+/// equal under `PartialEq` serialize the same way, so they hash the same.
+pub fn hash_json<T: Serialize>(value: &T) -> u64 {
+    hash(&serde_json::to_string(value).unwrap_or_default())
+}
+
+/// `==` and `!=` from `PartialEq`; ordering comparisons are left to Python, which raises
+/// `TypeError`.
+pub fn compare<T: PartialEq>(left: &T, right: &T, op: CompareOp, py: Python<'_>) -> PyObject {
+    match op {
+        CompareOp::Eq => (left == right).into_py(py),
+        CompareOp::Ne => (left != right).into_py(py),
+        _ => py.NotImplemented(),
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn to_value<T: Serialize + PyTypeInfo>(model: &T) -> PyResult<Value> {
+    serde_json::to_value(model).map_err(|e| PyValueError::new_err(format!("Failed to serialize {}: {}", T::NAME, e)))
+}
+
+/// Nested objects as dotted keys, lists as their length, so each field fits in one cell.
+fn flatten(prefix: &str, value: Value, cells: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                flatten(&key, value, cells);
+            }
+        },
+        Value::Array(items) => cells.push((prefix.to_string(), format!("{} items", items.len()))),
+        Value::Null => cells.push((prefix.to_string(), String::new())),
+        Value::String(text) => cells.push((prefix.to_string(), truncate(&text, 200))),
+        other => cells.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// An HTML table of `columns` and `rows`, captioned with `caption` and the row count.
+pub fn html_table(caption: &str, columns: &[String], rows: Vec<Vec<String>>) -> String {
+    let total = rows.len();
+    let noun = if total == 1 { "row" } else { "rows" };
+    let mut html = format!("<table><caption>{} ({} {})</caption><thead><tr>", escape(caption), total, noun);
+    for column in columns {
+        html += &format!("<th>{}</th>", escape(column));
+    }
+    html += "</tr></thead><tbody>";
+    for row in rows.into_iter().take(HTML_ROWS) {
+        html += "<tr>";
+        for cell in row {
+            html += &format!("<td>{}</td>", escape(&cell));
+        }
+        html += "</tr>";
+    }
+    html += "</tbody></table>";
+    if total > HTML_ROWS {
+        html += &format!("<p>… and {} more rows</p>", total - HTML_ROWS);
+    }
+    html
+}
+
+/// A two-column table of a model's fields, nested fields flattened into dotted names.
+pub fn fields_html<T: Serialize + PyTypeInfo>(model: &T) -> PyResult<String> {
+    let mut cells = Vec::new();
+    flatten("", to_value(model)?, &mut cells);
+    let mut html = format!("<table><caption>{}</caption><tbody>", T::NAME);
+    for (name, value) in cells {
+        html += &format!("<tr><th style=\"text-align:left\">{}</th><td style=\"text-align:left\">{}</td></tr>", escape(&name), escape(&value));
+    }
+    Ok(html + "</tbody></table>")
+}
+
+/// One row per item, with a column for every flattened field any item has. For lists with no
+/// columnar form; the others render their `Table`.
+pub fn rows_html<T: Serialize + PyTypeInfo>(caption: &str, items: &[T]) -> PyResult<String> {
+    let mut columns: Vec<String> = Vec::new();
+    let mut rows = Vec::new();
+    for item in items {
+        let mut cells = Vec::new();
+        flatten("", to_value(item)?, &mut cells);
+        for (name, _) in &cells {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+        rows.push(cells.into_iter().collect::<HashMap<String, String>>());
+    }
+    let rows = rows
+        .into_iter()
+        .map(|row| columns.iter().map(|column| row.get(column).cloned().unwrap_or_default()).collect())
+        .collect();
+    Ok(html_table(caption, &columns, rows))
+}
+
+/// The `#[pymethods]` block of a model: the constructor, JSON, `__repr__`, `_repr_html_` and
+/// `==` methods, followed by the model's own methods when they are given in braces, since pyo3
+/// allows one block per class. `__repr__` and `_repr_html_` come from the model's `Repr` impl.
+/// `model_methods!(frozen X)` also hashes frozen models, so they work as dict keys and in sets.
+macro_rules! model_methods {
+    (frozen $($model:ident),+ $(,)?) => {
+        $(
+            crate::protocol::model_methods!(frozen $model {});
+        )+
+    };
+    (frozen $model:ident { $($methods:tt)* }) => {
+        crate::protocol::model_methods!($model {
+            fn __hash__(&self) -> u64 {
+                crate::protocol::hash_json(self)
+            }
+
+            $($methods)*
+        });
+    };
+    ($($model:ident),+ $(,)?) => {
+        $(
+            crate::protocol::model_methods!($model {});
+        )+
+    };
+    ($model:ident { $($methods:tt)* }) => {
+        #[pymethods]
+        impl $model {
+            #[new]
+            #[pyo3(signature = (**fields))]
+            fn py_new(py: Python<'_>, fields: Option<&pyo3::types::PyDict>) -> PyResult<Self> {
+                crate::json::from_fields(py, fields)
+            }
+
+            fn to_dict(&self, py: Python<'_>) -> PyResult<Py<pyo3::types::PyDict>> {
+                crate::json::to_dict(py, self)
+            }
+
+            #[pyo3(signature = (indent=None))]
+            fn to_json(&self, indent: Option<usize>) -> PyResult<String> {
+                crate::json::to_json(self, indent)
+            }
+
+            #[staticmethod]
+            fn from_json(json: &str) -> PyResult<Self> {
+                crate::json::from_json(json)
+            }
+
+            #[staticmethod]
+            fn from_dict(py: Python<'_>, dict: &pyo3::types::PyDict) -> PyResult<Self> {
+                crate::json::from_dict(py, dict)
+            }
+
+            fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (String,))> {
+                crate::json::reduce(py, self)
+            }
+
+            fn __repr__(&self) -> String {
+                crate::protocol::Repr::repr(self)
+            }
+
+            fn _repr_html_(&self) -> PyResult<String> {
+                crate::protocol::Repr::repr_html(self)
+            }
+
+            fn __richcmp__(&self, other: &Self, op: pyo3::pyclass::CompareOp, py: Python<'_>) -> PyObject {
+                crate::protocol::compare(self, other, op, py)
+            }
+
+            $($methods)*
+        }
+    };
+}
+
+pub(crate) use model_methods;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_on_one_line() {
+        assert_eq!(truncate("New single\nout now", 40), "New single out now");
+        assert_eq!(truncate("abcdef", 3), "abc…");
+        assert_eq!(truncate("ééé", 3), "ééé");
+    }
+
+    #[test]
+    fn counts_in_singular_and_plural() {
+        assert_eq!(count(1, "post"), "1 post");
+        assert_eq!(count(0usize, "post"), "0 posts");
+        assert_eq!(count(2, "reply"), "2 replies");
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(escape("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    }
+}
@@ -0,0 +1,477 @@
+use crate::db::{self, db_error, unix_now};
+use crate::error::MetaStatsError;
+use crate::export::{Table, Tabular};
+use crate::facebook::{PageEngagement, PostInfo};
+use crate::growth::{self, GrowthReport};
+use crate::handle::{FacebookPageRef, InstagramHandle};
+use crate::instagram::{FeedMedia, InstagramPageInfo};
+use crate::protocol::{model_methods, Repr};
+use pyo3::prelude::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, MutexGuard};
+
+/// Schema migrations, applied in order by `db::open`.
+const MIGRATIONS: [&str; 2] = ["
+CREATE TABLE snapshots (
+    entity TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    captured_at REAL NOT NULL,
+    value REAL NOT NULL,
+    PRIMARY KEY (entity, metric, captured_at)
+);
+CREATE INDEX snapshots_by_time ON snapshots (entity, captured_at);
+", "
+CREATE TABLE account_names (
+    platform TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    first_seen REAL NOT NULL,
+    last_seen REAL NOT NULL,
+    PRIMARY KEY (platform, account_id, name)
+);
+CREATE INDEX account_names_by_name ON account_names (platform, name);
+"];
+
+/// Platforms in the account name history.
+pub const FACEBOOK: &str = "facebook";
+pub const INSTAGRAM: &str = "instagram";
+
+/// Entity key prefixes. An entity is `<prefix>:<id>`, e.g. `facebook_page:ChachiOfficial`.
+pub const FACEBOOK_PAGE: &str = "facebook_page";
+pub const FACEBOOK_POST: &str = "facebook_post";
+pub const INSTAGRAM_PROFILE: &str = "instagram_profile";
+pub const INSTAGRAM_MEDIA: &str = "instagram_media";
+
+pub fn entity(kind: &str, id: &str) -> String {
+    format!("{}:{}", kind, id)
+}
+
+/// The platform whose account names apply to an entity kind, for kinds keyed by account.
+fn account_platform(kind: &str) -> Option<&'static str> {
+    match kind {
+        FACEBOOK_PAGE => Some(FACEBOOK),
+        INSTAGRAM_PROFILE => Some(INSTAGRAM),
+        _ => None,
+    }
+}
+
+/// A vanity name or username an account was seen with, and when.
+#[pyclass(frozen, module = "meta_stats.snapshot")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountName {
+    /// `facebook` or `instagram`.
+    #[pyo3(get)]
+    platform: String,
+    #[pyo3(get)]
+    account_id: String,
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    first_seen: f64,
+    #[pyo3(get)]
+    last_seen: f64,
+}
+
+impl AccountName {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+model_methods!(frozen AccountName);
+
+impl Repr for AccountName {
+    fn repr(&self) -> String {
+        format!("AccountName({} {} = {:?}, {} to {})", self.platform, self.account_id, self.name, self.first_seen, self.last_seen)
+    }
+}
+
+/// One metric value of one entity at one point in time. `captured_at` is in Unix seconds.
+#[pyclass(frozen, module = "meta_stats.snapshot")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[pyo3(get)]
+    entity: String,
+    #[pyo3(get)]
+    metric: String,
+    #[pyo3(get)]
+    captured_at: f64,
+    #[pyo3(get)]
+    value: f64,
+}
+
+impl Snapshot {
+    pub fn new(entity: &str, metric: &str, captured_at: f64, value: f64) -> Snapshot {
+        Snapshot { entity: entity.to_string(), metric: metric.to_string(), captured_at, value }
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn metric(&self) -> &str {
+        &self.metric
+    }
+
+    pub fn captured_at(&self) -> f64 {
+        self.captured_at
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl Tabular for Snapshot {
+    fn table(rows: &[Snapshot]) -> Table {
+        Table::new()
+            .utf8("entity", rows.iter().map(|snapshot| Some(&snapshot.entity)))
+            .utf8("metric", rows.iter().map(|snapshot| Some(&snapshot.metric)))
+            .float64("captured_at", rows.iter().map(|snapshot| Some(snapshot.captured_at)))
+            .float64("value", rows.iter().map(|snapshot| Some(snapshot.value)))
+    }
+}
+
+model_methods!(frozen Snapshot);
+
+impl Repr for Snapshot {
+    fn repr(&self) -> String {
+        format!("Snapshot({}, {}={}, captured_at={})", self.entity, self.metric, self.value, self.captured_at)
+    }
+}
+
+/// Timestamped metric history in a local SQLite file, so follower and engagement counts that
+/// Meta only reports as current values can be tracked over time.
+#[pyclass(module = "meta_stats.snapshot")]
+pub struct SnapshotStore {
+    connection: Mutex<Connection>,
+    #[pyo3(get)]
+    path: String,
+}
+
+impl SnapshotStore {
+    pub fn open(path: &str) -> Result<SnapshotStore, MetaStatsError> {
+        let connection = db::open(path, &MIGRATIONS)?;
+        Ok(SnapshotStore { connection: Mutex::new(connection), path: path.to_string() })
+    }
+
+    fn connection(&self) -> Result<MutexGuard<'_, Connection>, MetaStatsError> {
+        self.connection
+            .lock()
+            .map_err(|e| MetaStatsError::Io(format!("Failed to lock snapshot database: {}", e)))
+    }
+
+    /// Saves every `(entity, metric, value)` row with the same timestamp, in one transaction.
+    /// Recording the same metric twice at the same instant keeps the later value.
+    pub fn record_all(&self, rows: &[(String, &str, f64)], captured_at: Option<f64>) -> Result<usize, MetaStatsError> {
+        let captured_at = captured_at.unwrap_or_else(unix_now);
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(db_error)?;
+
+        for (entity, metric, value) in rows {
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO snapshots (entity, metric, captured_at, value) VALUES (?1, ?2, ?3, ?4)",
+                    params![entity, metric, captured_at, value],
+                )
+                .map_err(db_error)?;
+        }
+
+        transaction.commit().map_err(db_error)?;
+        Ok(rows.len())
+    }
+
+    pub fn record(&self, entity: &str, metric: &str, value: f64, captured_at: Option<f64>) -> Result<usize, MetaStatsError> {
+        self.record_all(&[(entity.to_string(), metric, value)], captured_at)
+    }
+
+    /// Records a page's follower, fan, talking-about and rating counts under
+    /// `facebook_page:<id>`, using the numeric id from the response. `page_id` is the id or
+    /// vanity name the page was fetched by; a vanity name is recorded as one of the page's names,
+    /// and is only used as the key if the response has no id.
+    pub fn record_page_engagement(&self, engagement: &PageEngagement, page_id: Option<&str>, captured_at: Option<f64>) -> Result<usize, MetaStatsError> {
+        let captured_at = captured_at.unwrap_or_else(unix_now);
+        let page = page_id.map(FacebookPageRef::parse).transpose()?;
+        let id = Some(engagement.id()).filter(|id| !id.is_empty()).or(page.as_ref().map(FacebookPageRef::as_str));
+        let id = id.ok_or_else(|| MetaStatsError::InvalidArgument("page_id must be set when the engagement has no page id".to_string()))?;
+
+        if let Some(vanity) = page.as_ref().and_then(FacebookPageRef::vanity) {
+            self.record_name(FACEBOOK, id, vanity, Some(captured_at))?;
+        }
+
+        let key = entity(FACEBOOK_PAGE, id);
+        let rows: Vec<_> = engagement.metrics().into_iter().map(|(metric, value)| (key.clone(), metric, value)).collect();
+        self.record_all(&rows, Some(captured_at))
+    }
+
+    /// Records profile counts under `instagram_profile:<user id>` and the likes, comments and
+    /// views of the recent media included in the response under `instagram_media:<id>`. The
+    /// username is recorded as one of the user's names.
+    pub fn record_instagram_profile(&self, username: &str, page_info: &InstagramPageInfo, captured_at: Option<f64>) -> Result<usize, MetaStatsError> {
+        let captured_at = Some(captured_at.unwrap_or_else(unix_now));
+        let user = page_info.user();
+        let username = match user.username() {
+            "" => InstagramHandle::parse(username)?.to_string(),
+            username => username.to_ascii_lowercase(),
+        };
+        let id = match user.id() {
+            "" => username.as_str(),
+            id => {
+                self.record_name(INSTAGRAM, id, &username, captured_at)?;
+                id
+            },
+        };
+        let key = entity(INSTAGRAM_PROFILE, id);
+
+        let mut rows: Vec<_> = user.metrics().into_iter().map(|(metric, value)| (key.clone(), metric, value)).collect();
+        for node in user.timeline_media().nodes() {
+            let key = entity(INSTAGRAM_MEDIA, node.id());
+            rows.extend(node.metrics().into_iter().map(|(metric, value)| (key.clone(), metric, value)));
+        }
+
+        self.record_all(&rows, captured_at)
+    }
+
+    /// Notes that `account_id` was called `name` at `seen_at`. The first time a name is seen
+    /// for an account, snapshots recorded under the name rather than the id are moved to the id.
+    pub fn record_name(&self, platform: &str, account_id: &str, name: &str, seen_at: Option<f64>) -> Result<(), MetaStatsError> {
+        let seen_at = seen_at.unwrap_or_else(unix_now);
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(db_error)?;
+
+        let inserted = transaction
+            .execute(
+                "INSERT OR IGNORE INTO account_names (platform, account_id, name, first_seen, last_seen) VALUES (?1, ?2, ?3, ?4, ?4)",
+                params![platform, account_id, name, seen_at],
+            )
+            .map_err(db_error)?;
+
+        if inserted == 0 {
+            transaction
+                .execute(
+                    "UPDATE account_names SET first_seen = MIN(first_seen, ?4), last_seen = MAX(last_seen, ?4)
+                     WHERE platform = ?1 AND account_id = ?2 AND name = ?3",
+                    params![platform, account_id, name, seen_at],
+                )
+                .map_err(db_error)?;
+        } else if !name.eq_ignore_ascii_case(account_id) {
+            let kinds = [FACEBOOK_PAGE, INSTAGRAM_PROFILE].into_iter().filter(|kind| account_platform(kind) == Some(platform));
+            for kind in kinds {
+                transaction
+                    .execute(
+                        "UPDATE OR REPLACE snapshots SET entity = ?1 WHERE lower(entity) = lower(?2)",
+                        params![entity(kind, account_id), entity(kind, name)],
+                    )
+                    .map_err(db_error)?;
+            }
+        }
+
+        transaction.commit().map_err(db_error)
+    }
+
+    /// The account most recently seen with `name`, if any.
+    pub fn account_id(&self, platform: &str, name: &str) -> Result<Option<String>, MetaStatsError> {
+        self.connection()?
+            .query_row(
+                "SELECT account_id FROM account_names WHERE platform = ?1 AND name = ?2 ORDER BY last_seen DESC LIMIT 1",
+                params![platform, name],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)
+    }
+
+    /// Every name an account has been seen with, oldest first.
+    pub fn names(&self, platform: &str, account_id: &str) -> Result<Vec<AccountName>, MetaStatsError> {
+        let connection = self.connection()?;
+        let mut statement = connection
+            .prepare(
+                "SELECT platform, account_id, name, first_seen, last_seen FROM account_names
+                 WHERE platform = ?1 AND account_id = ?2 ORDER BY first_seen, name",
+            )
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map(params![platform, account_id], |row| {
+                Ok(AccountName { platform: row.get(0)?, account_id: row.get(1)?, name: row.get(2)?, first_seen: row.get(3)?, last_seen: row.get(4)? })
+            })
+            .map_err(db_error)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(db_error)
+    }
+
+    /// `entity` with a known page or profile name replaced by the account's id, so history can
+    /// be looked up by the name it is usually known by.
+    pub fn resolve_entity(&self, entity: &str) -> Result<String, MetaStatsError> {
+        let (kind, id) = match entity.split_once(':') {
+            Some(parts) => parts,
+            None => return Ok(entity.to_string()),
+        };
+        match account_platform(kind) {
+            Some(platform) => Ok(self.account_id(platform, id)?.map(|id| self::entity(kind, &id)).unwrap_or_else(|| entity.to_string())),
+            None => Ok(entity.to_string()),
+        }
+    }
+
+    /// Records each post's like, comment and share totals under `facebook_post:<id>`.
+    pub fn record_posts(&self, posts: &[PostInfo], captured_at: Option<f64>) -> Result<usize, MetaStatsError> {
+        let rows: Vec<_> = posts
+            .iter()
+            .flat_map(|post| {
+                let key = entity(FACEBOOK_POST, post.id());
+                post.metrics().into_iter().map(move |(metric, value)| (key.clone(), metric, value))
+            })
+            .collect();
+        self.record_all(&rows, captured_at)
+    }
+
+    /// Records each media item's like, comment and play counts under `instagram_media:<id>`.
+    pub fn record_instagram_media(&self, media: &[FeedMedia], captured_at: Option<f64>) -> Result<usize, MetaStatsError> {
+        let rows: Vec<_> = media
+            .iter()
+            .flat_map(|media| {
+                let key = entity(INSTAGRAM_MEDIA, media.id());
+                media.metrics().into_iter().map(move |(metric, value)| (key.clone(), metric, value))
+            })
+            .collect();
+        self.record_all(&rows, captured_at)
+    }
+
+    fn query(&self, sql: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<Snapshot>, MetaStatsError> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare(sql).map_err(db_error)?;
+        let rows = statement
+            .query_map(params, |row| {
+                Ok(Snapshot { entity: row.get(0)?, metric: row.get(1)?, captured_at: row.get(2)?, value: row.get(3)? })
+            })
+            .map_err(db_error)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(db_error)
+    }
+
+    /// Values of one metric, oldest first, optionally limited to `since <= captured_at <= until`.
+    /// Pages and profiles can be given by name or id.
+    pub fn history(&self, entity: &str, metric: &str, since: Option<f64>, until: Option<f64>) -> Result<Vec<Snapshot>, MetaStatsError> {
+        let entity = self.resolve_entity(entity)?;
+        self.query(
+            "SELECT entity, metric, captured_at, value FROM snapshots
+             WHERE entity = ?1 AND metric = ?2 AND captured_at >= ?3 AND captured_at <= ?4
+             ORDER BY captured_at",
+            &[&entity, &metric, &since.unwrap_or(f64::MIN), &until.unwrap_or(f64::MAX)],
+        )
+    }
+
+    /// Growth figures for one metric, computed over its full history.
+    pub fn growth_report(&self, entity: &str, metric: &str, as_of: Option<f64>, platform_size: Option<f64>) -> Result<Option<GrowthReport>, MetaStatsError> {
+        Ok(growth::growth_report(&self.history(entity, metric, None, as_of)?, as_of, platform_size))
+    }
+
+    /// The most recent value of every metric recorded for `entity`, by metric name.
+    pub fn latest(&self, entity: &str) -> Result<Vec<Snapshot>, MetaStatsError> {
+        let entity = self.resolve_entity(entity)?;
+        self.query(
+            "SELECT entity, metric, MAX(captured_at), value FROM snapshots WHERE entity = ?1 GROUP BY metric ORDER BY metric",
+            &[&entity],
+        )
+    }
+
+    /// Every entity with at least one snapshot, optionally only those of one kind.
+    pub fn entities(&self, kind: Option<&str>) -> Result<Vec<String>, MetaStatsError> {
+        let connection = self.connection()?;
+        let pattern = kind.map(|kind| format!("{}:%", kind)).unwrap_or_else(|| "%".to_string());
+        let mut statement = connection
+            .prepare("SELECT DISTINCT entity FROM snapshots WHERE entity LIKE ?1 ORDER BY entity")
+            .map_err(db_error)?;
+        let rows = statement.query_map([pattern], |row| row.get(0)).map_err(db_error)?;
+        rows.collect::<Result<Vec<String>, _>>().map_err(db_error)
+    }
+}
+
+#[pymethods]
+impl SnapshotStore {
+    /// Opens the store at `path`, creating it or migrating it to the current schema as needed.
+    #[staticmethod]
+    #[pyo3(name = "open")]
+    fn py_open(path: String) -> PyResult<SnapshotStore> {
+        Ok(SnapshotStore::open(&path)?)
+    }
+
+    #[pyo3(name = "record")]
+    fn py_record(&self, entity: &str, metric: &str, value: f64, captured_at: Option<f64>) -> PyResult<usize> {
+        Ok(self.record(entity, metric, value, captured_at)?)
+    }
+
+    #[pyo3(name = "record_page_engagement")]
+    fn py_record_page_engagement(&self, engagement: PageEngagement, page_id: Option<&str>, captured_at: Option<f64>) -> PyResult<usize> {
+        Ok(self.record_page_engagement(&engagement, page_id, captured_at)?)
+    }
+
+    #[pyo3(name = "record_instagram_profile")]
+    fn py_record_instagram_profile(&self, username: &str, page_info: InstagramPageInfo, captured_at: Option<f64>) -> PyResult<usize> {
+        Ok(self.record_instagram_profile(username, &page_info, captured_at)?)
+    }
+
+    #[pyo3(name = "record_posts")]
+    fn py_record_posts(&self, posts: Vec<PostInfo>, captured_at: Option<f64>) -> PyResult<usize> {
+        Ok(self.record_posts(&posts, captured_at)?)
+    }
+
+    #[pyo3(name = "record_instagram_media")]
+    fn py_record_instagram_media(&self, media: Vec<FeedMedia>, captured_at: Option<f64>) -> PyResult<usize> {
+        Ok(self.record_instagram_media(&media, captured_at)?)
+    }
+
+    #[pyo3(name = "record_name")]
+    fn py_record_name(&self, platform: &str, account_id: &str, name: &str, seen_at: Option<f64>) -> PyResult<()> {
+        Ok(self.record_name(platform, account_id, name, seen_at)?)
+    }
+
+    #[pyo3(name = "account_id")]
+    fn py_account_id(&self, platform: &str, name: &str) -> PyResult<Option<String>> {
+        Ok(self.account_id(platform, name)?)
+    }
+
+    #[pyo3(name = "names")]
+    fn py_names(&self, platform: &str, account_id: &str) -> PyResult<Vec<AccountName>> {
+        Ok(self.names(platform, account_id)?)
+    }
+
+    #[pyo3(name = "resolve_entity")]
+    fn py_resolve_entity(&self, entity: &str) -> PyResult<String> {
+        Ok(self.resolve_entity(entity)?)
+    }
+
+    #[pyo3(name = "history")]
+    fn py_history(&self, entity: &str, metric: &str, since: Option<f64>, until: Option<f64>) -> PyResult<Vec<Snapshot>> {
+        Ok(self.history(entity, metric, since, until)?)
+    }
+
+    #[pyo3(name = "growth_report")]
+    fn py_growth_report(&self, entity: &str, metric: &str, as_of: Option<f64>, platform_size: Option<f64>) -> PyResult<Option<GrowthReport>> {
+        Ok(self.growth_report(entity, metric, as_of, platform_size)?)
+    }
+
+    #[pyo3(name = "latest")]
+    fn py_latest(&self, entity: &str) -> PyResult<Vec<Snapshot>> {
+        Ok(self.latest(entity)?)
+    }
+
+    #[pyo3(name = "entities")]
+    fn py_entities(&self, kind: Option<&str>) -> PyResult<Vec<String>> {
+        Ok(self.entities(kind)?)
+    }
+
+    #[getter]
+    fn schema_version(&self) -> usize {
+        MIGRATIONS.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SnapshotStore({:?})", self.path)
+    }
+}
+
+/// `facebook_page:<id>` style key for use with `history` and `latest`.
+#[pyfunction]
+#[pyo3(name = "entity")]
+pub fn py_entity(kind: &str, id: &str) -> String {
+    entity(kind, id)
+}
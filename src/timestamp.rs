@@ -0,0 +1,150 @@
+use crate::error::MetaStatsError;
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDateTime;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// How the Graph API writes times, e.g. `2024-03-01T18:00:00+0000`.
+const GRAPH_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%z";
+
+/// Parses a Graph API time. RFC 3339 (`+00:00` or `Z`) is accepted too, which is what Python's
+/// `datetime.isoformat()` produces when a model is built from a `datetime`.
+pub fn parse(text: &str) -> Result<DateTime<Utc>, MetaStatsError> {
+    DateTime::parse_from_str(text, GRAPH_FORMAT)
+        .or_else(|_| DateTime::parse_from_rfc3339(text))
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| MetaStatsError::Parse(format!("'{}' is not a timestamp: {}", text, e)))
+}
+
+/// `time` as the Graph API writes it, so serialized models read back unchanged.
+pub fn format(time: &DateTime<Utc>) -> String {
+    time.format(GRAPH_FORMAT).to_string()
+}
+
+pub fn from_unix(seconds: i64) -> Result<DateTime<Utc>, MetaStatsError> {
+    DateTime::from_timestamp(seconds, 0).ok_or_else(|| MetaStatsError::Parse(format!("{} is out of range for a unix timestamp", seconds)))
+}
+
+/// Serde for Graph API times, as `#[serde(with = "timestamp::graph")]`.
+pub mod graph {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(time: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format(time))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        parse(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// `graph` for optional fields. Needs `#[serde(default)]` as well, for when the field is missing.
+pub mod graph_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(time: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_str(&format(time)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| parse(&text))
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde for Instagram's unix `taken_at` seconds. A time string is read too, for models built
+/// from a `datetime`.
+pub mod unix {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum Unix {
+        Seconds(i64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(time: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(time.timestamp())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        match Unix::deserialize(deserializer)? {
+            Unix::Seconds(seconds) => from_unix(seconds),
+            Unix::Text(text) => parse(&text),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+/// `unix` for optional fields. Needs `#[serde(default)]` as well, for when the field is missing.
+pub mod unix_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(time: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_i64(time.timestamp()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+        Option::<unix::Unix>::deserialize(deserializer)?
+            .map(|time| match time {
+                unix::Unix::Seconds(seconds) => from_unix(seconds),
+                unix::Unix::Text(text) => parse(&text),
+            })
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A time argument from Python: a timezone-aware `datetime`, in any zone. Naive datetimes are
+/// rejected rather than guessed at.
+#[derive(Clone, Copy)]
+pub struct TimeArg(pub DateTime<Utc>);
+
+impl<'source> FromPyObject<'source> for TimeArg {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let datetime: &PyDateTime = ob.downcast()?;
+        if datetime.getattr("tzinfo")?.is_none() || datetime.call_method0("utcoffset")?.is_none() {
+            return Err(PyValueError::new_err(format!(
+                "{} has no time zone; pass an aware datetime such as datetime(2024, 3, 1, tzinfo=timezone.utc)",
+                datetime.str()?
+            )));
+        }
+        let utc = ob.py().import("datetime")?.getattr("timezone")?.getattr("utc")?;
+        Ok(TimeArg(datetime.call_method1("astimezone", (utc,))?.extract()?))
+    }
+}
+
+impl From<TimeArg> for DateTime<Utc> {
+    fn from(arg: TimeArg) -> DateTime<Utc> {
+        arg.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_graph_and_rfc_3339_times() {
+        let time = parse("2024-03-08T21:00:00-0600").unwrap();
+        assert_eq!(format(&time), "2024-03-09T03:00:00+0000");
+        assert_eq!(parse("2024-03-09T03:00:00+00:00").unwrap(), time);
+        assert_eq!(parse("2024-03-09T03:00:00Z").unwrap(), time);
+        assert!(parse("2024-03-09").is_err());
+    }
+
+    #[test]
+    fn reads_unix_seconds() {
+        assert_eq!(format(&from_unix(1_706_464_800).unwrap()), "2024-01-28T18:00:00+0000");
+    }
+}
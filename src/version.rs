@@ -0,0 +1,272 @@
+use crate::config::MetaConfig;
+use crate::error::MetaStatsError;
+use crate::protocol;
+use chrono::{NaiveDate, Utc};
+use pyo3::exceptions::PyDeprecationWarning;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Mutex, RwLock};
+
+/// Response header in which the Graph API reports the version that actually served a request.
+pub const API_VERSION_HEADER: &str = "facebook-api-version";
+
+/// Warn this many days before a version's announced sunset.
+const SUNSET_WARNING_DAYS: i64 = 90;
+
+/// A release or sunset date from Meta's changelog.
+const fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("valid calendar date")
+}
+
+struct Release {
+    major: u32,
+    released: NaiveDate,
+    /// The date Meta announced the version stops working, once it has been announced.
+    expires: Option<NaiveDate>,
+}
+
+/// Graph API versions this library knows about, oldest first, from Meta's changelog.
+/// Anything older than the first entry has long been retired.
+const RELEASES: [Release; 9] = [
+    Release { major: 16, released: date(2023, 2, 2), expires: Some(date(2025, 5, 14)) },
+    Release { major: 17, released: date(2023, 5, 23), expires: Some(date(2025, 9, 12)) },
+    Release { major: 18, released: date(2023, 9, 12), expires: Some(date(2026, 1, 26)) },
+    Release { major: 19, released: date(2024, 1, 23), expires: Some(date(2026, 5, 21)) },
+    Release { major: 20, released: date(2024, 5, 21), expires: Some(date(2026, 9, 24)) },
+    Release { major: 21, released: date(2024, 10, 2), expires: None },
+    Release { major: 22, released: date(2025, 1, 21), expires: None },
+    Release { major: 23, released: date(2025, 5, 29), expires: None },
+    Release { major: 24, released: date(2025, 10, 8), expires: None },
+];
+
+static WARNING_HANDLER: RwLock<Option<fn(&str)>> = RwLock::new(None);
+static WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Routes version warnings somewhere other than stderr. The Python module sends them to
+/// `warnings.warn` as `DeprecationWarning`s.
+pub fn set_warning_handler(handler: fn(&str)) {
+    if let Ok(mut current) = WARNING_HANDLER.write() {
+        *current = Some(handler);
+    }
+}
+
+/// Emits `message` through the warning handler, at most once per process.
+fn warn(message: String) {
+    let first_time = match WARNED.lock() {
+        Ok(mut warned) => warned.get_or_insert_with(HashSet::new).insert(message.clone()),
+        Err(_) => true,
+    };
+
+    if !first_time {
+        return;
+    }
+
+    match WARNING_HANDLER.read().ok().and_then(|handler| *handler) {
+        Some(handler) => handler(&message),
+        None => eprintln!("warning: {}", message),
+    }
+}
+
+pub fn python_warning(message: &str) {
+    Python::with_gil(|py| {
+        if let Err(e) = PyErr::warn(py, py.get_type::<PyDeprecationWarning>(), message, 1) {
+            e.write_unraisable(py, None);
+        }
+    });
+}
+
+/// A validated Graph API version such as `v22.0`.
+#[pyclass(frozen, module = "meta_stats.version")]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GraphVersion {
+    major: u32,
+    minor: u32,
+}
+
+impl fmt::Display for GraphVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}", self.major, self.minor)
+    }
+}
+
+impl GraphVersion {
+    /// Parses `v22.0`, `22.0`, `v22` or `22`. Anything else is rejected rather than being
+    /// passed through to the URL.
+    pub fn parse(version: &str) -> Result<GraphVersion, MetaStatsError> {
+        let invalid = || {
+            MetaStatsError::InvalidArgument(format!(
+                "'{}' is not a Graph API version; expected something like 'v22.0'",
+                version
+            ))
+        };
+
+        let trimmed = version.trim();
+        let number = trimmed.strip_prefix('v').or_else(|| trimmed.strip_prefix('V')).unwrap_or(trimmed);
+        let (major, minor) = match number.split_once('.') {
+            Some((major, minor)) => (major, minor),
+            None => (number, "0"),
+        };
+
+        let digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        if !digits(major) || !digits(minor) {
+            return Err(invalid());
+        }
+
+        Ok(GraphVersion {
+            major: major.parse().map_err(|_| invalid())?,
+            minor: minor.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    /// The version for a request: the explicit `meta_version` argument, else the config's pinned
+    /// version. Warns when that version is retired or close to its sunset.
+    pub fn resolve(meta_version: Option<String>, config: &MetaConfig) -> Result<GraphVersion, MetaStatsError> {
+        let version = match meta_version {
+            Some(meta_version) => GraphVersion::parse(&meta_version)?,
+            None => GraphVersion::parse(&config.version()?)?,
+        };
+
+        version.warn_if_deprecated();
+        Ok(version)
+    }
+
+    fn release(&self) -> Option<&'static Release> {
+        RELEASES.iter().find(|release| release.major == self.major)
+    }
+
+    fn expires(&self) -> Option<NaiveDate> {
+        self.release().and_then(|release| release.expires)
+    }
+
+    /// Days until the announced sunset, negative once it has passed. `None` when no date has
+    /// been announced yet.
+    pub fn days_until_expiry(&self) -> Option<i64> {
+        self.expires().map(|expires| (expires - Utc::now().date_naive()).num_days())
+    }
+
+    /// Older than every version in the release table, or past its announced sunset.
+    pub fn is_retired(&self) -> bool {
+        self.major < RELEASES[0].major || self.days_until_expiry().map(|days| days < 0).unwrap_or(false)
+    }
+
+    fn warn_if_deprecated(&self) {
+        if self.major < RELEASES[0].major {
+            warn(format!(
+                "Graph API {} has been retired; Meta will serve requests with its oldest available version instead",
+                self
+            ));
+            return;
+        }
+
+        if let (Some(expires), Some(days)) = (self.expires(), self.days_until_expiry()) {
+            if days < 0 {
+                warn(format!(
+                    "Graph API {} was retired on {}; Meta will serve requests with its oldest available version instead",
+                    self, expires
+                ));
+            } else if days <= SUNSET_WARNING_DAYS {
+                warn(format!("Graph API {} will be retired on {} ({} days from now)", self, expires, days));
+            }
+        }
+    }
+}
+
+/// Warns when the `facebook-api-version` header shows Meta answered a request with a
+/// different version than the one in its URL, which happens once a pinned version is retired.
+pub fn check_served_version(url: &str, served: &str) {
+    let requested = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.path_segments().and_then(|mut segments| segments.next().map(str::to_string)))
+        .filter(|segment| segment.starts_with('v'))
+        .and_then(|segment| GraphVersion::parse(&segment).ok());
+
+    if let (Some(requested), Ok(served)) = (requested, GraphVersion::parse(served)) {
+        if requested != served {
+            warn(format!(
+                "Requested Graph API {} but Meta served {}; the pinned version has been upgraded",
+                requested, served
+            ));
+        }
+    }
+}
+
+#[pymethods]
+impl GraphVersion {
+    /// Same as `parse`.
+    #[new]
+    fn py_new(version: &str) -> PyResult<GraphVersion> {
+        Ok(GraphVersion::parse(version)?)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "parse")]
+    fn py_parse(version: &str) -> PyResult<GraphVersion> {
+        Ok(GraphVersion::parse(version)?)
+    }
+
+    #[getter]
+    fn major(&self) -> u32 {
+        self.major
+    }
+
+    #[getter]
+    fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Release date as `YYYY-MM-DD`, if the version is in the release table.
+    #[getter]
+    fn released(&self) -> Option<String> {
+        self.release().map(|release| release.released.to_string())
+    }
+
+    /// Announced sunset date as `YYYY-MM-DD`.
+    #[getter(expires)]
+    fn py_expires(&self) -> Option<String> {
+        self.expires().map(|expires| expires.to_string())
+    }
+
+    #[getter(days_until_expiry)]
+    fn py_days_until_expiry(&self) -> Option<i64> {
+        self.days_until_expiry()
+    }
+
+    #[getter(is_retired)]
+    fn py_is_retired(&self) -> bool {
+        self.is_retired()
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("GraphVersion({})", self)
+    }
+
+    /// Versions order by major, then minor number.
+    fn __richcmp__(&self, other: &Self, op: CompareOp) -> bool {
+        op.matches(self.cmp(other))
+    }
+
+    fn __hash__(&self) -> u64 {
+        protocol::hash(self)
+    }
+
+    /// Pickles as a call to `parse` with the string form.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(PyObject, (String,))> {
+        Ok((py.get_type::<GraphVersion>().getattr("parse")?.into(), (self.to_string(),)))
+    }
+}
+
+/// Every version in the release table that has not been retired, oldest first.
+#[pyfunction]
+pub fn supported_graph_versions() -> Vec<GraphVersion> {
+    RELEASES
+        .iter()
+        .map(|release| GraphVersion { major: release.major, minor: 0 })
+        .filter(|version| !version.is_retired())
+        .collect()
+}
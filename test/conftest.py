@@ -0,0 +1,18 @@
+from pathlib import Path
+
+import pytest
+
+import meta_stats
+
+CASSETTES = Path(__file__).parent / "cassettes"
+TOKEN = "test-token"
+VERSION = "v22.0"
+
+
+@pytest.fixture
+def cassette():
+    def load(name):
+        meta_stats.cassette.use_cassette(str(CASSETTES / f"{name}.json"), "replay")
+
+    yield load
+    meta_stats.cassette.eject_cassette()
@@ -0,0 +1,105 @@
+import json
+import time
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+pytestmark = pytest.mark.skipif(not hasattr(meta_stats, "mock"), reason="built without the mock-server feature")
+
+
+@pytest.fixture
+def server():
+    with meta_stats.mock.MockServer.start() as server:
+        server.add_page("ChachiOfficial", "Chachi", 52000, 48000, 30)
+        yield server
+
+
+@pytest.fixture
+def cache_dir(tmp_path):
+    yield tmp_path
+    meta_stats.cache.disable_cache()
+
+
+def age_entries(directory, seconds):
+    """Moves every cached entry `seconds` into the past."""
+    for path in directory.glob("*.json"):
+        entry = json.loads(path.read_text())
+        entry["stored_at"] = int(time.time()) - seconds
+        path.write_text(json.dumps(entry))
+
+
+def page_info(token=TOKEN, config=None):
+    return meta_stats.facebook.get_facebook_page_info(token, "ChachiOfficial", VERSION, config)
+
+
+def test_miss_then_hit(server, cache_dir):
+    meta_stats.cache.configure_cache(str(cache_dir))
+
+    first = page_info()
+    assert first.cache_status == "miss"
+    requests = len(server.requests)
+
+    second = page_info()
+    assert second.cache_status == "hit"
+    assert second.page_info.name == "Chachi"
+    assert len(server.requests) == requests
+
+
+def test_entries_expire_after_the_default_ttl(server, cache_dir):
+    meta_stats.cache.configure_cache(str(cache_dir))
+    page_info()
+
+    age_entries(cache_dir, 3599)
+    assert page_info().cache_status == "hit"
+
+    age_entries(cache_dir, 3601)
+    assert page_info().cache_status == "miss"
+
+
+def test_endpoint_ttls_override_the_default(server, cache_dir):
+    meta_stats.cache.configure_cache(str(cache_dir), 3600, {"node": 60})
+    page_info()
+
+    age_entries(cache_dir, 120)
+    assert page_info().cache_status == "miss"
+
+
+def test_entries_are_scoped_per_access_token(server, cache_dir):
+    meta_stats.cache.configure_cache(str(cache_dir))
+
+    assert page_info("first-token").cache_status == "miss"
+    assert page_info("second-token").cache_status == "miss"
+    assert page_info("first-token").cache_status == "hit"
+    assert all("first-token" not in path.read_text() for path in cache_dir.glob("*.json"))
+
+
+def test_entries_are_scoped_per_port(server, cache_dir):
+    meta_stats.cache.configure_cache(str(cache_dir))
+    page_info()
+
+    with meta_stats.mock.MockServer.start() as other:
+        other.add_page("ChachiOfficial", "Chachi (staging)", 10, 10, 1)
+        page = page_info()
+    assert page.cache_status == "miss"
+    assert page.page_info.name == "Chachi (staging)"
+
+
+def test_stale_entries_are_served_on_server_errors(server, cache_dir):
+    config = meta_stats.config.load_config(graph_base_url=server.url, version=VERSION, max_retries=0)
+    meta_stats.cache.configure_cache(str(cache_dir), stale_while_error=True)
+    page_info(config=config)
+    age_entries(cache_dir, 7200)
+
+    server.inject_error("server_error", None, 1, 503)
+    stale = page_info(config=config)
+    assert stale.is_success
+    assert stale.cache_status == "stale"
+    assert stale.page_info.name == "Chachi"
+
+    meta_stats.cache.configure_cache(str(cache_dir), stale_while_error=False)
+    server.inject_error("server_error", None, 1, 503)
+    failed = page_info(config=config)
+    assert failed.is_error
+    assert failed.cache_status == "miss"
@@ -0,0 +1,84 @@
+import pytest
+
+import meta_stats
+from conftest import CASSETTES
+
+CONFIG = """
+version = "v22.0"
+timeout_seconds = 20
+
+[profiles.staging]
+client_id = "staging-id"
+client_secret = "staging-secret"
+graph_base_url = "http://localhost:8080/"
+
+[profiles.production]
+client_id = "production-id"
+client_secret = "production-secret"
+version = "v23.0"
+max_retries = 3
+"""
+
+
+@pytest.fixture
+def config_file(tmp_path):
+    path = tmp_path / "meta_stats.toml"
+    path.write_text(CONFIG)
+    return str(path)
+
+
+def test_profiles_share_top_level_values(config_file):
+    staging = meta_stats.config.load_config(config_file, "staging", "TEST_CONFIG_UNSET_")
+    assert staging.client_id == "staging-id"
+    assert staging.has_client_secret
+    assert staging.version == "v22.0"
+    assert staging.timeout_seconds == 20
+    assert staging.graph_base_url == "http://localhost:8080"
+
+    production = meta_stats.config.load_config(config_file, "production", "TEST_CONFIG_UNSET_")
+    assert production.client_id == "production-id"
+    assert production.version == "v23.0"
+    assert production.max_retries == 3
+    assert production.graph_base_url is None
+
+
+def test_environment_overrides_file_and_arguments_override_environment(config_file, monkeypatch):
+    monkeypatch.setenv("STAGING_META_CLIENT_ID", "env-id")
+    monkeypatch.setenv("STAGING_META_VERSION", "v21.0")
+
+    config = meta_stats.config.load_config(config_file, "staging", "STAGING_META_")
+    assert config.client_id == "env-id"
+    assert config.version == "v21.0"
+
+    config = meta_stats.config.load_config(config_file, "staging", "STAGING_META_", version="v24.0")
+    assert config.version == "v24.0"
+    assert meta_stats.auth.get_meta_version(config) == "v24.0"
+
+
+def test_profile_and_file_from_environment(config_file, monkeypatch):
+    monkeypatch.setenv("PROFILED_CONFIG", config_file)
+    monkeypatch.setenv("PROFILED_PROFILE", "production")
+
+    config = meta_stats.config.load_config(env_prefix="PROFILED_")
+    assert config.profile == "production"
+    assert meta_stats.auth.get_meta_client_id(config) == "production-id"
+
+
+def test_missing_profile_and_settings_are_reported(config_file):
+    with pytest.raises(ValueError, match="Profile 'qa' not found"):
+        meta_stats.config.load_config(config_file, "qa", "TEST_CONFIG_UNSET_")
+
+    config = meta_stats.config.load_config(None, None, "TEST_CONFIG_UNSET_")
+    with pytest.raises(ValueError, match="TEST_CONFIG_UNSET_CLIENT_SECRET not set"):
+        meta_stats.auth.get_meta_client_secret(config)
+
+
+def test_config_is_used_for_requests():
+    config = meta_stats.config.load_config(None, None, "TEST_CONFIG_UNSET_", "client-id", "client-secret", "v22.0")
+    meta_stats.cassette.use_cassette(str(CASSETTES / "facebook.json"), "replay")
+    try:
+        followers = meta_stats.facebook.get_facebook_page_followers("test-token", "ChachiOfficial", None, config)
+    finally:
+        meta_stats.cassette.eject_cassette()
+
+    assert followers.is_success
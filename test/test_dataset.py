@@ -0,0 +1,62 @@
+import csv
+from pathlib import Path
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+DAY = 86400
+
+
+def read_csv(path):
+    with open(path, newline="") as file:
+        return list(csv.DictReader(file))
+
+
+def test_writes_partitioned_csv_parts(cassette, tmp_path):
+    cassette("facebook")
+    writer = meta_stats.dataset.DatasetWriter.open(str(tmp_path), "csv")
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info.data
+
+    part = writer.write_posts("ChachiOfficial", posts, 20000 * DAY)
+    assert part.rows == len(posts)
+    assert Path(part.path).parent == tmp_path / "posts" / "platform=facebook" / "date=2024-10-04"
+
+    rows = read_csv(part.path)
+    assert list(rows[0])[:4] == ["captured_at", "page_id", "id", "created_time"]
+    assert rows[0]["page_id"] == "ChachiOfficial"
+    assert rows[0]["like_count"] == "120"
+
+
+def test_writes_append_new_parts(cassette, tmp_path):
+    cassette("facebook")
+    writer = meta_stats.dataset.DatasetWriter.open(str(tmp_path), "csv")
+    comments = meta_stats.facebook.get_facebook_post_comments(TOKEN, "100_1", VERSION, None, "stream").comments
+
+    first = writer.write_comments("100_1", comments, DAY)
+    second = writer.write_comments("100_1", comments, DAY)
+    assert first.path != second.path
+
+    partition = Path(first.path).parent
+    assert sorted(path.name for path in partition.iterdir()) == sorted([Path(first.path).name, Path(second.path).name])
+    assert [row["depth"] for row in read_csv(first.path)][:2] == ["0", "1"]
+    assert writer.write_comments("100_1", [], DAY) is None
+
+
+def test_bulk_fetch_writes_successes_and_returns_failures(cassette, tmp_path):
+    cassette("facebook")
+    writer = meta_stats.dataset.DatasetWriter.open(str(tmp_path))
+    fetch = meta_stats.bulk.bulk_fetch_facebook_pages(["ChachiOfficial", "NotRecorded"], None, 2, TOKEN, VERSION)
+
+    parts, failures = fetch.write_dataset(writer)
+    assert [part.dataset for part in parts] == ["page_engagement"]
+    assert parts[0].rows == 1
+    assert parts[0].path.endswith(".parquet")
+    assert Path(parts[0].path).read_bytes()[:4] == b"PAR1"
+    assert [failure.page_id for failure in failures] == ["NotRecorded"]
+
+
+def test_rejects_unknown_formats(tmp_path):
+    with pytest.raises(ValueError, match="Unknown dataset format"):
+        meta_stats.dataset.DatasetWriter.open(str(tmp_path), "xlsx")
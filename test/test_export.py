@@ -0,0 +1,135 @@
+import ctypes
+import importlib.util
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+
+class ArrowSchema(ctypes.Structure):
+    pass
+
+
+ArrowSchema._fields_ = [
+    ("format", ctypes.c_char_p),
+    ("name", ctypes.c_char_p),
+    ("metadata", ctypes.c_char_p),
+    ("flags", ctypes.c_int64),
+    ("n_children", ctypes.c_int64),
+    ("children", ctypes.POINTER(ctypes.POINTER(ArrowSchema))),
+]
+
+
+class ArrowArray(ctypes.Structure):
+    _fields_ = [("length", ctypes.c_int64), ("null_count", ctypes.c_int64), ("offset", ctypes.c_int64)]
+
+
+def capsule_struct(capsule, name, struct):
+    get_pointer = ctypes.pythonapi.PyCapsule_GetPointer
+    get_pointer.restype = ctypes.c_void_p
+    get_pointer.argtypes = [ctypes.py_object, ctypes.c_char_p]
+    return struct.from_address(get_pointer(capsule, name))
+
+
+def test_posts_export_through_the_arrow_c_interface(cassette):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info
+
+    schema_capsule, array_capsule = posts.__arrow_c_array__()
+    schema = capsule_struct(schema_capsule, b"arrow_schema", ArrowSchema)
+    array = capsule_struct(array_capsule, b"arrow_array", ArrowArray)
+
+    assert schema.format == b"+s"
+    names = [schema.children[i].contents.name.decode() for i in range(schema.n_children)]
+    assert names[:7] == ["id", "created_time", "message", "story", "like_count", "comment_count", "share_count"]
+    assert array.length == len(posts.data)
+
+
+def test_nested_collections_are_flattened(cassette):
+    cassette("facebook")
+    comments = meta_stats.facebook.get_facebook_post_comments(TOKEN, "100_1", VERSION, None, "stream").comments
+    assert meta_stats.export.columns(comments)[:3] == ["id", "parent_id", "depth"]
+
+    videos = meta_stats.facebook.get_facebook_page_videos(TOKEN, "ChachiOfficial", VERSION, True)
+    assert "total_video_views" in meta_stats.export.columns(videos.videos)
+    _, array_capsule = videos.__arrow_c_array__()
+    assert capsule_struct(array_capsule, b"arrow_array", ArrowArray).length == len(videos.videos)
+
+
+def test_failed_results_cannot_be_exported(cassette):
+    cassette("facebook")
+    missing = meta_stats.facebook.get_facebook_page_events(TOKEN, "missingpage", VERSION, "upcoming")
+    assert missing.is_error
+
+    with pytest.raises(ValueError, match="Cannot export a failed result"):
+        missing.__arrow_c_array__()
+
+
+def test_bulk_results_drain_into_one_table(cassette):
+    cassette("facebook")
+    fetch = meta_stats.bulk.bulk_fetch_facebook_pages(["ChachiOfficial", "NotRecorded"], None, 2, TOKEN, VERSION)
+
+    schema_capsule, array_capsule = fetch.__arrow_c_array__()
+    schema = capsule_struct(schema_capsule, b"arrow_schema", ArrowSchema)
+    names = [schema.children[i].contents.name.decode() for i in range(schema.n_children)]
+    assert names[:5] == ["index", "page_id", "is_success", "error_category", "error_message"]
+    assert "followers_count" in names
+    assert capsule_struct(array_capsule, b"arrow_array", ArrowArray).length == 2
+    assert list(fetch) == []
+
+
+def test_snapshots_and_engagement_export(tmp_path):
+    store = meta_stats.snapshot.SnapshotStore.open(str(tmp_path / "snapshots.sqlite"))
+    store.record("facebook_page:100", "followers_count", 1000, 86400)
+    history = store.history("facebook_page:100", "followers_count")
+    assert meta_stats.export.columns(history) == ["entity", "metric", "captured_at", "value"]
+
+    summary = meta_stats.engagement.facebook_engagement([], 100)
+    _, array_capsule = summary.__arrow_c_array__()
+    assert capsule_struct(array_capsule, b"arrow_array", ArrowArray).length == 0
+
+
+def test_empty_lists_need_a_kind(tmp_path):
+    with pytest.raises(ValueError, match="pass kind"):
+        meta_stats.export.columns([])
+    assert meta_stats.export.columns([], "snapshots") == ["entity", "metric", "captured_at", "value"]
+    assert meta_stats.export.columns([], kind="posts")[:2] == ["id", "created_time"]
+
+    store = meta_stats.snapshot.SnapshotStore.open(str(tmp_path / "snapshots.sqlite"))
+    store.record("facebook_page:100", "followers_count", 1000, 86400)
+    history = store.history("facebook_page:100", "followers_count")
+    assert meta_stats.export.columns(history, "snapshots")[0] == "entity"
+    with pytest.raises(ValueError, match="Expected posts"):
+        meta_stats.export.columns(history, "posts")
+    with pytest.raises(ValueError, match="kind must be one of"):
+        meta_stats.export.columns([], "reels")
+
+
+@pytest.mark.skipif(importlib.util.find_spec("pandas") is not None, reason="pandas is installed")
+def test_missing_dataframe_library_is_reported(cassette):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts(TOKEN, "ChachiOfficial", VERSION).posts_info
+
+    with pytest.raises(ImportError, match="pip install"):
+        posts.to_pandas()
+
+
+@pytest.mark.skipif(importlib.util.find_spec("pandas") is None or importlib.util.find_spec("pyarrow") is None, reason="needs pandas and pyarrow")
+def test_to_pandas(cassette):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info
+
+    frame = posts.to_pandas()
+    assert list(frame["id"]) == [post.id for post in posts.data]
+    assert frame["like_count"][0] == 120
+
+
+@pytest.mark.skipif(importlib.util.find_spec("polars") is None or importlib.util.find_spec("pyarrow") is None, reason="needs polars and pyarrow")
+def test_to_polars(cassette):
+    cassette("facebook")
+    events = meta_stats.facebook.get_facebook_page_events(TOKEN, "ChachiOfficial", VERSION, "upcoming")
+
+    frame = events.to_polars()
+    assert frame["city"][0] == "Austin"
+    assert frame["is_canceled"][1]
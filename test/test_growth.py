@@ -0,0 +1,48 @@
+import pytest
+
+import meta_stats
+
+DAY = 86400
+PAGE = "facebook_page:ChachiOfficial"
+
+
+@pytest.fixture
+def store():
+    store = meta_stats.snapshot.SnapshotStore.open(":memory:")
+    for day in range(91):
+        store.record(PAGE, "followers_count", 10000 + 100 * day, day * DAY)
+    return store
+
+
+def test_reports_windows_and_percent_growth(store):
+    report = store.growth_report(PAGE, "followers_count")
+    assert report.current == 19000
+    assert report.growth_7d.absolute == 700
+    assert report.growth_30d.start_value == 16000
+    assert report.growth_90d.percent == pytest.approx(90.0)
+    assert report.growth_90d.per_day == pytest.approx(100.0)
+    assert report.acceleration == pytest.approx(0.0)
+    assert report.rolling_average[-1] == (90 * DAY, 18700)
+
+
+def test_short_history_omits_longer_windows(store):
+    report = store.growth_report(PAGE, "followers_count", 20 * DAY)
+    assert report.as_of == 20 * DAY
+    assert report.growth_7d is not None
+    assert report.growth_30d is None
+    assert report.normalized_growth_30d is None
+    assert store.growth_report(PAGE, "missing") is None
+
+
+def test_normalizes_against_platform_size(store):
+    facebook = store.growth_report(PAGE, "followers_count")
+    assert facebook.normalized_growth_30d == pytest.approx(3000 / 3070)
+    override = store.growth_report(PAGE, "followers_count", None, 1e6)
+    assert override.normalized_growth_30d == pytest.approx(3000)
+
+
+def test_report_from_history_list(store):
+    history = store.history(PAGE, "followers_count", 60 * DAY)
+    report = meta_stats.growth.growth_report(list(reversed(history)))
+    assert report.growth_30d.absolute == 3000
+    assert report.growth_90d is None
@@ -0,0 +1,54 @@
+import pytest
+
+import meta_stats
+
+FacebookPageRef = meta_stats.handle.FacebookPageRef
+InstagramHandle = meta_stats.handle.InstagramHandle
+
+
+def test_parses_facebook_pages():
+    for text in ["ChachiOfficial", "@ChachiOfficial", "https://www.facebook.com/ChachiOfficial/", "m.facebook.com/ChachiOfficial/about"]:
+        page = FacebookPageRef.parse(text)
+        assert page.vanity == "ChachiOfficial"
+        assert page.id is None
+
+    page = FacebookPageRef.parse("fb.com/profile.php?id=100123")
+    assert page.is_numeric
+    assert page.id == "100123"
+    assert page.url == "https://www.facebook.com/profile.php?id=100123"
+
+
+def test_parses_instagram_handles():
+    for text in ["hotelgaruda", "@HotelGaruda", "instagram.com/hotelgaruda/?hl=en", "https://www.instagram.com/hotelgaruda/reels/"]:
+        assert InstagramHandle.parse(text).username == "hotelgaruda"
+
+
+def test_rejects_non_profile_urls():
+    for text in ["https://www.facebook.com/events/55", "facebook.com/ChachiOfficial/posts/123", "https://linktr.ee/chachi", "Chachi Official"]:
+        with pytest.raises(ValueError):
+            FacebookPageRef.parse(text)
+
+    with pytest.raises(ValueError, match="short links must be expanded"):
+        FacebookPageRef.parse("fb.me/ChachiOfficial")
+
+    for text in ["https://www.instagram.com/p/C123/", "instagram.com/explore/tags/music", "hotel..garuda", "facebook.com/hotelgaruda"]:
+        with pytest.raises(ValueError):
+            InstagramHandle.parse(text)
+
+
+def test_fetch_functions_accept_urls_and_refs(cassette):
+    cassette("facebook")
+    page = meta_stats.facebook.get_facebook_page_info("test-token", "https://www.facebook.com/ChachiOfficial/", "v22.0")
+    assert page.is_success
+    assert meta_stats.facebook.get_facebook_page_info("test-token", FacebookPageRef.parse("@ChachiOfficial"), "v22.0").is_success
+
+    cassette("instagram")
+    assert meta_stats.instagram.get_instagram_page_info("https://instagram.com/HotelGaruda?hl=en").is_success
+    assert meta_stats.instagram.get_instagram_page_info(InstagramHandle.parse("@hotelgaruda")).is_success
+
+
+def test_invalid_input_is_rejected_before_the_request():
+    with pytest.raises(ValueError, match="not a page"):
+        meta_stats.facebook.get_facebook_page_info("test-token", "https://www.facebook.com/ChachiOfficial/posts/123", "v22.0")
+    with pytest.raises(ValueError, match="not an Instagram username"):
+        meta_stats.instagram.get_instagram_page_info("hotel garuda")
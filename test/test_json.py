@@ -0,0 +1,87 @@
+import json
+import pickle
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+
+def test_results_round_trip_through_json(cassette):
+    cassette("facebook")
+    result = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+
+    text = result.to_json()
+    assert json.loads(text)["page_engagement"]["followers_count"] == 52000
+
+    copy = meta_stats.facebook.PageEngagementResult.from_json(text)
+    assert copy.is_success
+    assert copy.page_engagement.followers_count == 52000
+    assert copy.page_engagement.category_list[0].name == "Musician/Band"
+    assert copy.to_json() == text
+    assert result.to_json(indent=2).startswith("{\n  ")
+
+
+def test_models_round_trip_through_dicts(cassette):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info
+
+    post = posts.data[0]
+    data = post.to_dict()
+    assert data["id"] == post.id
+    assert meta_stats.facebook.PostInfo.from_dict(data).to_dict() == data
+    assert meta_stats.facebook.BasicPostsInfo.from_dict(posts.to_dict()).data[0].id == post.id
+
+
+def test_models_compare_equal_after_a_round_trip(cassette):
+    cassette("facebook")
+    result = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+
+    copy = meta_stats.facebook.PageEngagementResult.from_json(result.to_json())
+    assert copy == result
+    assert copy.page_engagement == result.page_engagement
+    assert meta_stats.facebook.PageCategory(id="1", name="Band") != meta_stats.facebook.PageCategory(id="2", name="Band")
+    assert copy != "not a result"
+    with pytest.raises(TypeError):
+        hash(copy)
+
+    name = meta_stats.snapshot.AccountName(platform="facebook", account_id="100", name="ChachiOfficial", first_seen=1.0, last_seen=2.0)
+    same = meta_stats.snapshot.AccountName.from_json(name.to_json())
+    assert same == name
+    assert len({name, same}) == 1
+
+
+def test_failed_results_keep_their_error(cassette):
+    cassette("instagram")
+    limited = meta_stats.instagram.get_instagram_page_info("ratelimited")
+
+    copy = meta_stats.instagram.InstagramPageInfoResult.from_json(limited.to_json())
+    assert copy.is_error
+    assert copy.error_info.require_login
+
+
+def test_models_pickle(cassette):
+    cassette("instagram")
+    result = meta_stats.instagram.get_instagram_page_info("hotelgaruda")
+
+    copy = pickle.loads(pickle.dumps(result))
+    assert copy.page_info.data.user.full_name == "Hotel Garuda"
+    assert copy.to_json() == result.to_json()
+
+    user = pickle.loads(pickle.dumps(result.page_info.data.user))
+    assert user.edge_followed_by.count == 15400
+
+
+def test_values_pickle_through_parse():
+    page = pickle.loads(pickle.dumps(meta_stats.handle.FacebookPageRef.parse("https://www.facebook.com/profile.php?id=100123")))
+    assert page.id == "100123"
+
+    handle = pickle.loads(pickle.dumps(meta_stats.handle.InstagramHandle.parse("@hotelgaruda")))
+    assert handle.username == "hotelgaruda"
+
+    assert str(pickle.loads(pickle.dumps(meta_stats.version.GraphVersion.parse("v22.0")))) == "v22.0"
+
+
+def test_invalid_json_is_rejected():
+    with pytest.raises(ValueError, match="Invalid PostInfo JSON"):
+        meta_stats.facebook.PostInfo.from_json('{"id": 1}')
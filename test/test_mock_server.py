@@ -0,0 +1,377 @@
+from datetime import datetime, timezone
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+pytestmark = pytest.mark.skipif(not hasattr(meta_stats, "mock"), reason="built without the mock-server feature")
+
+
+@pytest.fixture
+def server():
+    with meta_stats.mock.MockServer.start() as server:
+        server.add_page("ChachiOfficial", "Chachi", 52000, 48000, 30)
+        server.add_instagram_user("hotelgaruda", "Hotel Garuda", 15400)
+        yield server
+
+
+def test_serves_token_page_and_profile(server):
+    token = meta_stats.auth.get_meta_access_token(None, "client-id", "client-secret", None, VERSION)
+    assert token == "mock-app-token"
+
+    page = meta_stats.facebook.get_facebook_page_info(token, "ChachiOfficial", VERSION)
+    assert page.page_info.name == "Chachi"
+
+    followers = meta_stats.facebook.get_facebook_page_followers(token, "ChachiOfficial", VERSION)
+    assert followers.page_engagement.followers_count == 52000
+
+    profile = meta_stats.instagram.get_instagram_page_info("hotelgaruda")
+    assert profile.page_info.data.user.edge_followed_by.count == 15400
+
+
+def test_pages_posts_with_cursors(server):
+    first = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION)
+    assert len(first.posts_info.data) == 25
+    assert first.posts_info.data[0].likes.summary.total_count == 10
+
+    raw = meta_stats.facebook.get_facebook_next_results(first.posts_info.paging.next)
+    second = meta_stats.facebook.parse_next_results_to_basic_posts_info(raw)
+    assert len(second.posts_info.data) == 5
+    assert second.posts_info.paging.next is None
+
+    reactions = meta_stats.facebook.get_facebook_post_interactions(TOKEN, "ChachiOfficial_1", VERSION)
+    assert "LOVE" in reactions
+    assert all("test-token" not in path for path in server.requests)
+
+
+def test_filters_posts_by_created_time(server):
+    server.add_page("Dated", "Dated", 100, 100, 10)
+    since = datetime(2024, 1, 20, tzinfo=timezone.utc)
+    until = datetime(2024, 1, 25, 18, tzinfo=timezone.utc)
+
+    posts = meta_stats.facebook.get_facebook_page_posts(TOKEN, "Dated", VERSION, since=since).posts_info.data
+    assert len(posts) == 9
+    assert all(post.created_time >= since for post in posts)
+
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "Dated", VERSION, since=since, until=until).posts_info.data
+    assert [post.created_time.day for post in posts] == [25, 24, 23, 22, 21, 20]
+
+
+def test_cassette_records_then_replays(server, tmp_path):
+    path = str(tmp_path / "posts.json")
+    meta_stats.cassette.use_cassette(path, "record")
+    try:
+        recorded = meta_stats.facebook.get_facebook_page_posts_with_summary("secret-token", "ChachiOfficial", VERSION)
+    finally:
+        meta_stats.cassette.eject_cassette()
+    assert "secret-token" not in open(path).read()
+
+    requests = len(server.requests)
+    meta_stats.cassette.use_cassette(path, "replay")
+    try:
+        replayed = meta_stats.facebook.get_facebook_page_posts_with_summary("secret-token", "ChachiOfficial", VERSION)
+    finally:
+        meta_stats.cassette.eject_cassette()
+    assert len(server.requests) == requests
+    assert [post.id for post in replayed.posts_info] == [post.id for post in recorded.posts_info]
+
+
+def test_injects_meta_errors(server):
+    server.inject_error("invalid_token", "/ChachiOfficial")
+    expired = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+    assert expired.is_error
+    assert expired.error_info.error.code == 190
+
+    server.inject_error("throttled", None, 2)
+    for _ in range(2):
+        throttled = meta_stats.facebook.get_facebook_page_info(TOKEN, "ChachiOfficial", VERSION)
+        assert throttled.error_info.error.code == 613
+    assert meta_stats.facebook.get_facebook_page_info(TOKEN, "ChachiOfficial", VERSION).is_success
+
+    server.inject_error("require_login", "web_profile_info")
+    blocked = meta_stats.instagram.get_instagram_page_info("hotelgaruda")
+    assert blocked.is_error
+    assert blocked.error_info.require_login
+
+    server.inject_error("server_error", None, 1, 503)
+    unavailable = meta_stats.facebook.get_facebook_page_info(TOKEN, "ChachiOfficial", VERSION)
+    assert unavailable.error_info.error.is_transient
+
+
+def test_config_retries_server_errors(server):
+    config = meta_stats.config.load_config(graph_base_url=server.url, version=VERSION, max_retries=2, retry_backoff_ms=1)
+    server.inject_error("server_error", None, 2, 503)
+
+    page = meta_stats.facebook.get_facebook_page_info(TOKEN, "ChachiOfficial", None, config)
+    assert page.is_success
+
+
+def test_warns_when_meta_serves_a_different_version(server):
+    server.set_served_version("v24.0")
+
+    with pytest.warns(DeprecationWarning, match="Requested Graph API v23.0 but Meta served v24.0"):
+        page = meta_stats.facebook.get_facebook_page_info(TOKEN, "ChachiOfficial", "v23.0")
+    assert page.is_success
+
+
+def test_bulk_fetch_streams_pages_and_profiles(server):
+    page_ids = [f"page{i}" for i in range(20)]
+    for page_id in page_ids:
+        server.add_page(page_id, page_id.title(), 1000, 900, 0)
+    server.set_latency(50)
+
+    results = meta_stats.bulk.bulk_fetch_facebook_pages(page_ids + ["missing"], ["followers_count", "fan_count"], 10, TOKEN, VERSION)
+    assert results.total == 21
+
+    seen = {result.index: result for result in results}
+    assert sorted(seen) == list(range(21))
+    assert all(seen[i].is_success and seen[i].result.page_engagement.followers_count == 1000 for i in range(20))
+    assert seen[20].is_error
+
+    profiles = list(meta_stats.bulk.bulk_fetch_instagram_profiles(["hotelgaruda"], 4))
+    assert profiles[0].username == "hotelgaruda"
+    assert profiles[0].result.page_info.data.user.edge_followed_by.count == 15400
+
+
+def test_bulk_fetch_shares_rate_limit_backoff(server):
+    config = meta_stats.config.load_config(graph_base_url=server.url, version=VERSION, max_retries=2, retry_backoff_ms=10)
+    server.inject_error("app_rate_limit", None, 1)
+
+    results = list(meta_stats.bulk.bulk_fetch_facebook_pages(["ChachiOfficial"] * 5, None, 5, TOKEN, None, config))
+    assert all(result.is_success for result in results)
+
+
+def test_crawl_job_resumes_after_interruption(server, tmp_path):
+    path = str(tmp_path / "crawl.sqlite")
+    job = meta_stats.crawl.CrawlJob.open(path)
+    assert job.add_facebook_pages(["ChachiOfficial", "missing"]) == 2
+    assert job.add_facebook_pages(["ChachiOfficial"]) == 0
+
+    first = job.run(TOKEN, VERSION, 1)
+    assert first.stopped_reason == "max_pages"
+    assert first.items_fetched == 25
+    assert not first.is_complete
+
+    server.inject_error("invalid_token", "/ChachiOfficial/posts")
+    expired = job.run(TOKEN, VERSION)
+    assert expired.stopped_reason == "auth"
+    assert expired.items_fetched == 25
+
+    updates = []
+    resumed = meta_stats.crawl.CrawlJob.open(path).run(TOKEN, VERSION, None, updates.append)
+    assert resumed.is_complete
+    assert resumed.stopped_reason is None
+    assert (resumed.targets_done, resumed.targets_failed) == (1, 1)
+    assert updates[-1].eta_seconds == 0
+    assert any("after=25" in request for request in server.requests)
+
+    posts = job.facebook_posts("ChachiOfficial")
+    assert len(posts) == 30
+    assert len({post.id for post in posts}) == 30
+    assert all("test-token" not in line for line in open(path, "rb").read().decode("latin-1").splitlines())
+
+
+def test_crawl_job_records_runs_that_fail(server, tmp_path):
+    job = meta_stats.crawl.CrawlJob.open(str(tmp_path / "crawl.sqlite"))
+    job.add_facebook_pages(["ChachiOfficial"])
+
+    config = meta_stats.config.load_config(env_prefix="META_STATS_TEST_", client_id="client-id", client_secret="wrong-secret", graph_base_url=server.url, version=VERSION)
+    server.inject_error("invalid_client", "oauth")
+    rejected = job.run(config=config)
+    assert rejected.stopped_reason == "auth"
+    assert rejected.targets_pending == 1
+    assert "Error validating client secret" in rejected.last_error
+
+    config = meta_stats.config.load_config(env_prefix="META_STATS_TEST_", graph_base_url=server.url, version=VERSION)
+    with pytest.raises(ValueError, match="CLIENT_ID not set"):
+        job.run(config=config)
+    failed = job.progress()
+    assert failed.stopped_reason == "error"
+    assert job.progress().elapsed_seconds == failed.elapsed_seconds
+
+
+def test_crawl_job_fetches_instagram_media(server, tmp_path):
+    server.add_instagram_user("kunokini", "Kunokini", 800, None, 30)
+    job = meta_stats.crawl.CrawlJob.open(str(tmp_path / "crawl.sqlite"))
+    job.add_instagram_users(["kunokini", "hotelgaruda"])
+
+    stopped = []
+    progress = job.run(progress_callback=lambda progress: stopped.append(progress) or len(stopped) < 2)
+    assert progress.stopped_reason == "cancelled"
+    assert progress.items_fetched == 24
+
+    server.rename_instagram_user("kunokini", "kunokini_official")
+    progress = job.run()
+    assert progress.is_complete
+    media = job.instagram_media("kunokini")
+    assert len(media) == 30
+    assert media[0].taken_at > media[-1].taken_at
+    by_id = job.instagram_media(meta_stats.instagram.resolve_user_id("kunokini_official"))
+    assert [item.id for item in by_id] == [item.id for item in media]
+    assert job.instagram_media("hotelgaruda") == []
+
+
+def test_snapshot_store_records_fetched_metrics(server, tmp_path):
+    store = meta_stats.snapshot.SnapshotStore.open(str(tmp_path / "snapshots.sqlite"))
+
+    followers = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+    assert store.record_page_engagement(followers.page_engagement, "ChachiOfficial", 100.0) == 5
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION)
+    store.record_posts(posts.posts_info.data, 100.0)
+    profile = meta_stats.instagram.get_instagram_page_info("hotelgaruda")
+    store.record_instagram_profile("hotelgaruda", profile.page_info, 100.0)
+
+    page = {snapshot.metric: snapshot.value for snapshot in store.latest("facebook_page:ChachiOfficial")}
+    assert page["followers_count"] == 52000
+    assert store.latest("facebook_post:ChachiOfficial_1")[0].metric == "comments"
+    assert store.history("instagram_profile:hotelgaruda", "followers_count")[0].value == 15400
+    assert len(store.entities("facebook_post")) == 25
+
+
+def test_renamed_accounts_keep_their_history(server, tmp_path):
+    store = meta_stats.snapshot.SnapshotStore.open(str(tmp_path / "snapshots.sqlite"))
+    server.add_page("100200", "Garuda", 8000, 7000)
+    server.set_page_username("100200", "GarudaBand")
+
+    assert meta_stats.facebook.resolve_page_id("https://facebook.com/GarudaBand", TOKEN, VERSION) == "100200"
+    assert meta_stats.facebook.resolve_page_id("100200") == "100200"
+    followers = meta_stats.facebook.get_facebook_page_followers(TOKEN, "GarudaBand", VERSION)
+    store.record_page_engagement(followers.page_engagement, "GarudaBand", 100.0)
+
+    server.set_page_username("100200", "GarudaOfficial")
+    with pytest.raises(ValueError, match="Could not resolve page 'GarudaBand'"):
+        meta_stats.facebook.resolve_page_id("GarudaBand", TOKEN, VERSION)
+    assert meta_stats.facebook.resolve_page_id("GarudaBand", TOKEN, VERSION, store=store) == "100200"
+    assert meta_stats.facebook.resolve_page_id("GarudaOfficial", TOKEN, VERSION, store=store) == "100200"
+    assert store.account_id("facebook", "GarudaOfficial") == "100200"
+    followers = meta_stats.facebook.get_facebook_page_followers(TOKEN, "GarudaOfficial", VERSION)
+    store.record_page_engagement(followers.page_engagement, "GarudaOfficial", 200.0)
+
+    assert store.entities("facebook_page") == ["facebook_page:100200"]
+    assert len(store.history("facebook_page:GarudaBand", "followers_count")) == 2
+    assert [name.name for name in store.names("facebook", "100200")] == ["GarudaBand", "GarudaOfficial"]
+    assert any("/GarudaOfficial?fields=id&" in request for request in server.requests)
+
+    server.add_page("300400", "Garuda Tribute", 50, 50)
+    server.set_page_username("300400", "GarudaBand")
+    assert meta_stats.facebook.resolve_page_id("GarudaBand", TOKEN, VERSION, store=store) == "100200"
+    assert meta_stats.facebook.resolve_page_id("GarudaBand", TOKEN, VERSION, store=store, refresh=True) == "300400"
+    assert meta_stats.facebook.resolve_page_id("GarudaBand", TOKEN, VERSION, store=store) == "300400"
+
+    user_id = meta_stats.instagram.resolve_user_id("@hotelgaruda")
+    store.record_instagram_profile("hotelgaruda", meta_stats.instagram.get_instagram_page_info("hotelgaruda").page_info, 100.0)
+    server.rename_instagram_user("hotelgaruda", "garudahotel")
+    assert meta_stats.instagram.resolve_user_id("garudahotel") == user_id
+    store.record_instagram_profile("garudahotel", meta_stats.instagram.get_instagram_page_info("garudahotel").page_info, 200.0)
+
+    assert store.resolve_entity("instagram_profile:hotelgaruda") == f"instagram_profile:{user_id}"
+    assert meta_stats.instagram.resolve_user_id("hotelgaruda", store=store) == user_id
+    with pytest.raises(ValueError, match="Could not resolve Instagram user 'hotelgaruda'"):
+        meta_stats.instagram.resolve_user_id("hotelgaruda", store=store, refresh=True)
+    assert len(store.history("instagram_profile:garudahotel", "followers_count")) == 2
+
+
+def test_engagement_rates_and_viral_posts(server):
+    server.add_page("Viral", "Viral", 1000, 900, 12)
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "Viral", VERSION).posts_info.data
+
+    summary = meta_stats.engagement.facebook_engagement(posts, 1000, None, None, "Viral")
+    assert summary.post_count == 12
+    assert summary.posts[0].interactions == summary.posts[0].likes + summary.posts[0].comments
+    assert summary.outliers == []
+
+    viral = meta_stats.engagement.facebook_engagement(posts, 1000, 10, {"Viral_5": 5000})
+    assert viral.post_count == 10
+    assert [post.post_id for post in viral.outliers] == ["Viral_5"]
+    assert viral.median_rate < viral.mean_rate
+
+    server.add_instagram_user("kunokini", "Kunokini", 1000, None, 30)
+    profile = meta_stats.instagram.get_instagram_page_info("kunokini").page_info
+    recent = meta_stats.engagement.instagram_profile_engagement(profile, 5, "kunokini")
+    assert recent.platform == "instagram"
+    assert recent.followers == 1000
+    assert [post.interactions for post in recent.posts] == [6, 12, 18, 24, 30]
+    assert recent.median_rate == pytest.approx(1.8)
+
+
+def test_artist_stats_follows_linked_instagram_account(server):
+    server.link_instagram_account("ChachiOfficial", "hotelgaruda")
+
+    artist = meta_stats.artist.get_artist_stats("ChachiOfficial", None, TOKEN, VERSION)
+    assert artist.is_complete
+    assert artist.instagram_linked
+    assert artist.instagram_username == "hotelgaruda"
+    assert artist.name == "Hotel Garuda"
+    assert artist.total_reach == 52000 + 15400
+    assert artist.followers("instagram") == 15400
+    assert artist.facebook.fan_count == 48000
+    assert all(reach.fetched_at >= artist.fetched_at for reach in artist.platforms)
+
+
+def test_artist_stats_reports_partial_failures(server):
+    server.inject_error("require_login", "web_profile_info")
+
+    artist = meta_stats.artist.get_artist_stats("ChachiOfficial", "hotelgaruda", TOKEN, VERSION)
+    assert artist.is_partial
+    assert artist.total_reach == 52000
+    assert artist.instagram is None
+    assert [(failure.platform, failure.category) for failure in artist.failures] == [("instagram", "rate_limit")]
+
+    unlinked = meta_stats.artist.get_artist_stats("ChachiOfficial", None, TOKEN, VERSION)
+    assert unlinked.instagram_username is None
+    assert unlinked.is_complete
+
+    with pytest.raises(ValueError):
+        meta_stats.artist.get_artist_stats()
+
+
+def test_resolves_instagram_account_for_page(server):
+    server.add_instagram_user("chachi", "Chachi", 9000)
+    server.link_instagram_account("ChachiOfficial", "chachi")
+    server.set_instagram_links("chachi", "https://www.facebook.com/ChachiOfficial")
+    server.set_page_website("ChachiOfficial", "https://chachi.example https://instagram.com/hotelgaruda")
+
+    resolution = meta_stats.identity.resolve_instagram_for_page("ChachiOfficial", TOKEN, VERSION)
+    assert [candidate.account for candidate in resolution.candidates] == ["chachi", "hotelgaruda"]
+    best = resolution.best
+    assert best.name == "Chachi"
+    assert {evidence.kind for evidence in best.evidence} == {"instagram_business_account", "profile_link", "name_match"}
+    assert best.confidence > 0.99
+    assert resolution.candidates[1].confidence == pytest.approx(0.6)
+
+
+def test_resolves_facebook_page_for_instagram_profile(server):
+    server.add_page("hotelgarudaband", "Hotel Garuda", 3000, 2800)
+    server.link_instagram_account("hotelgarudaband", "hotelgaruda", True)
+    server.set_instagram_links("hotelgaruda", None, ["https://linktr.ee/hg", "https://fb.com/hotelgarudaband", "https://facebook.com/missingpage"])
+
+    resolution = meta_stats.identity.resolve_facebook_for_instagram("hotelgaruda", TOKEN, VERSION)
+    best = resolution.best
+    assert best.account == "hotelgarudaband"
+    assert [evidence.kind for evidence in best.evidence] == ["profile_link", "connected_instagram_account", "name_match"]
+    assert resolution.candidates[1].account == "missingpage"
+    assert resolution.candidates[1].confidence == pytest.approx(0.7)
+    assert [failure.category for failure in resolution.failures] == ["not_found"]
+
+    assert meta_stats.identity.resolve_facebook_for_instagram("kunokini_missing", TOKEN, VERSION).candidates == []
+    assert meta_stats.identity.name_similarity("Hotel Garuda (Official)", "hotel garuda") == 1.0
+
+
+def test_resolves_accounts_without_links_by_name(server):
+    server.add_page("200300", "Kunokini", 900, 850)
+    server.set_page_username("200300", "kunokini")
+    server.add_instagram_user("kunokini", "Kunokini (Official)", 800)
+
+    page = meta_stats.identity.resolve_facebook_for_instagram("kunokini", TOKEN, VERSION)
+    assert [candidate.account for candidate in page.candidates] == ["kunokini"]
+    assert [evidence.kind for evidence in page.best.evidence] == ["name_match"]
+    assert page.best.name == "Kunokini"
+    assert page.failures == []
+
+    profile = meta_stats.identity.resolve_instagram_for_page("200300", TOKEN, VERSION)
+    assert [candidate.account for candidate in profile.candidates] == ["kunokini"]
+    assert profile.best.confidence == pytest.approx(0.6)
+
+    server.add_instagram_user("otherband", "Other Band", 100)
+    assert meta_stats.identity.resolve_facebook_for_instagram("otherband", TOKEN, VERSION).candidates == []
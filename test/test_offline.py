@@ -0,0 +1,230 @@
+import json
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+
+def test_auth_reads_environment(monkeypatch):
+    monkeypatch.setenv("META_CLIENT_ID", "client-id")
+    monkeypatch.setenv("META_CLIENT_SECRET", "client-secret")
+    monkeypatch.setenv("META_VERSION", VERSION)
+
+    assert meta_stats.auth.get_meta_client_id() == "client-id"
+    assert meta_stats.auth.get_meta_client_secret() == "client-secret"
+    assert meta_stats.auth.get_meta_version() == VERSION
+
+
+def test_get_meta_access_token(cassette):
+    cassette("auth")
+
+    token = meta_stats.auth.get_meta_access_token(None, "client-id", "client-secret", "client_credentials", "22.0")
+    assert token == "REDACTED"
+
+
+def test_get_facebook_page_info(cassette):
+    cassette("facebook")
+
+    result = meta_stats.facebook.get_facebook_page_info(TOKEN, "ChachiOfficial", VERSION)
+    assert result.is_success
+    assert result.page_info.name == "Chachi"
+    assert result.page_info.id == "100"
+
+    missing = meta_stats.facebook.get_facebook_page_info(TOKEN, "missingpage", VERSION)
+    assert missing.is_error
+    assert missing.error_info.error.code == 803
+
+
+def test_get_facebook_page_followers(cassette):
+    cassette("facebook")
+
+    result = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+    assert result.is_success
+    engagement = result.page_engagement
+    assert engagement.followers_count == 52000
+    assert engagement.fan_count == 48000
+    assert engagement.category_list[0].name == "Musician/Band"
+
+
+def test_get_facebook_page_posts_and_next_results(cassette):
+    cassette("facebook")
+
+    result = meta_stats.facebook.get_facebook_page_posts(TOKEN, "ChachiOfficial", VERSION)
+    assert result.is_success
+    posts = result.posts_info
+    assert [post.id for post in posts.data] == ["100_1", "100_2"]
+    assert posts.data[1].story == "Chachi updated their cover photo."
+
+    raw = meta_stats.facebook.get_facebook_next_results(posts.paging.next)
+    next_page = meta_stats.facebook.parse_next_results_to_basic_posts_info(raw)
+    assert next_page.is_success
+    assert next_page.posts_info.data[0].message == "Tour dates announced"
+    assert next_page.posts_info.paging.next is None
+
+
+def test_get_facebook_page_posts_with_summary(cassette):
+    cassette("facebook")
+
+    result = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION)
+    assert result.is_success
+    post = result.posts_info.data[0]
+    assert post.likes.summary.total_count == 120
+    assert post.comments.summary.total_count == 14
+    assert post.comments.data[0].message == "See you there"
+
+
+def test_extended_post_fields(cassette):
+    cassette("facebook")
+
+    result = meta_stats.facebook.get_facebook_page_posts(TOKEN, "ChachiOfficial", VERSION, True)
+    assert result.is_success
+    post, cover = result.posts_info.data
+    assert post.permalink_url == "https://www.facebook.com/ChachiOfficial/posts/1"
+    assert post.shares.count == 37
+    assert (post.status_type, post.is_published) == ("added_photos", True)
+    assert post.place.location.city == "Austin"
+    assert [(tag.name, tag.type, tag.offset, tag.length) for tag in post.message_tags] == [("Hotel Garuda", "page", 35, 12)]
+
+    album = post.attachments.data[0]
+    assert (album.media_type, album.title) == ("album", "Friday")
+    assert [photo.url for photo in album.subattachments] == ["https://www.facebook.com/photo/?fbid=11", "https://www.facebook.com/photo/?fbid=12"]
+    assert album.subattachments.data[0].subattachments is None
+
+    assert cover.shares is None
+    assert cover.attachments is None
+    assert cover.message_tags is None
+
+
+def test_get_facebook_post_interactions(cassette):
+    cassette("facebook")
+
+    raw = meta_stats.facebook.get_facebook_post_interactions(TOKEN, "100_1", VERSION)
+    reactions = json.loads(raw)
+    assert [reaction["type"] for reaction in reactions["data"]] == ["LIKE", "LOVE"]
+
+
+def test_get_facebook_page_events(cassette):
+    cassette("facebook")
+
+    result = meta_stats.facebook.get_facebook_page_events(TOKEN, "ChachiOfficial", VERSION, "upcoming")
+    assert result.is_success
+    events = result.events
+    assert len(events) == 2
+    assert events[0].place.location.city == "Austin"
+    assert (events[0].attending_count, events[0].interested_count, events[0].maybe_count) == (210, 1300, 45)
+    assert events[1].is_canceled
+
+    past = meta_stats.facebook.get_facebook_page_events(TOKEN, "ChachiOfficial", VERSION, "past").events
+    assert [(event.id, event.attending_count, event.interested_count) for event in past] == [("299", 540, 2100)]
+
+    missing = meta_stats.facebook.get_facebook_page_events(TOKEN, "missingpage", VERSION, "upcoming")
+    assert missing.is_error
+    assert missing.error_info.category == "not_found"
+
+    with pytest.raises(ValueError, match="time_filter must be either 'upcoming' or 'past', got 'someday'"):
+        meta_stats.facebook.get_facebook_page_events(TOKEN, "ChachiOfficial", VERSION, "someday")
+
+
+def test_get_facebook_page_videos_and_reels(cassette):
+    cassette("facebook")
+
+    videos = meta_stats.facebook.get_facebook_page_videos(TOKEN, "ChachiOfficial", VERSION, True)
+    assert videos.is_success
+    video = videos.videos[0]
+    assert video.title == "Live set"
+    assert (video.views, video.length) == (8800, 3605.4)
+    assert video.insights.total_video_views == 9100
+    assert video.insights.total_video_complete_views == 640
+    assert video.insights.total_video_avg_time_watched == 41000
+    assert video.insights.total_video_impressions is None
+
+    reels = meta_stats.facebook.get_facebook_page_reels(TOKEN, "ChachiOfficial", VERSION)
+    assert reels.is_success
+    assert reels.videos[0].insights is None
+
+
+def test_video_insights_errors(cassette):
+    cassette("facebook")
+
+    # Missing read_insights only leaves the insights unset; the reel itself is still returned.
+    reels = meta_stats.facebook.get_facebook_page_reels(TOKEN, "ChachiOfficial", VERSION, True)
+    assert reels.is_success
+    assert reels.videos[0].description == "Soundcheck"
+    assert reels.videos[0].insights is None
+
+    videos = meta_stats.facebook.get_facebook_page_videos(TOKEN, "busypage", VERSION, True)
+    assert videos.is_error
+    assert videos.error_info.category == "rate_limit"
+    assert videos.error_info.error.code == 4
+
+
+def test_get_facebook_post_comments(cassette):
+    cassette("facebook")
+
+    for comment_filter in ["stream", "toplevel"]:
+        result = meta_stats.facebook.get_facebook_post_comments(TOKEN, "100_1", VERSION, None, comment_filter)
+        assert result.is_success
+        threads = result.comments
+        assert [node.comment.id for node in threads] == ["100_1_1", "100_1_3"]
+        assert threads[0].replies[0].comment.message == "Me too!"
+        assert threads[0].replies[0].comment.parent.id == "100_1_1"
+        assert (threads[0].comment.like_count, threads[0].comment.comment_count) == (3, 1)
+        assert threads[1].replies == []
+
+
+def test_comment_stream_builds_nested_threads(cassette):
+    cassette("facebook")
+
+    threads = meta_stats.facebook.get_facebook_post_comments(TOKEN, "100_2", VERSION, "reverse_chronological", "stream").comments
+    # 100_2_5 replies to a comment missing from the listing, so it starts its own thread.
+    assert [node.comment.id for node in threads] == ["100_2_5", "100_2_1"]
+    root = threads[1]
+    assert [reply.comment.id for reply in root.replies] == ["100_2_3", "100_2_2"]
+    assert [reply.comment.id for reply in root.replies[1].replies] == ["100_2_4"]
+    assert root.replies[1].replies[0].replies == []
+
+    with pytest.raises(ValueError, match="filter must be either 'stream' or 'toplevel'"):
+        meta_stats.facebook.get_facebook_post_comments(TOKEN, "100_2", VERSION, None, "all")
+    with pytest.raises(ValueError, match="order must be either 'chronological' or 'reverse_chronological'"):
+        meta_stats.facebook.get_facebook_post_comments(TOKEN, "100_2", VERSION, "newest")
+
+
+def test_get_instagram_page_info(cassette):
+    cassette("instagram")
+
+    result = meta_stats.instagram.get_instagram_page_info("hotelgaruda")
+    assert result.is_success
+    user = result.page_info.data.user
+    assert user.full_name == "Hotel Garuda"
+    assert user.edge_followed_by.count == 15400
+
+    limited = meta_stats.instagram.get_instagram_page_info("ratelimited")
+    assert limited.is_error
+    assert limited.error_info.require_login
+
+
+def test_replay_rejects_unrecorded_requests(cassette):
+    cassette("facebook")
+
+    with pytest.raises(ValueError, match="No recorded interaction"):
+        meta_stats.facebook.get_facebook_page_info(TOKEN, "someoneelse", VERSION)
+
+
+def test_bulk_fetch_facebook_pages(cassette):
+    cassette("facebook")
+
+    results = list(meta_stats.bulk.bulk_fetch_facebook_pages(["ChachiOfficial", "NotRecorded"], None, 2, TOKEN, VERSION))
+    assert len(results) == 2
+
+    by_id = {result.page_id: result for result in results}
+    assert by_id["ChachiOfficial"].is_success
+    assert by_id["ChachiOfficial"].index == 0
+    assert by_id["ChachiOfficial"].result.page_engagement.followers_count > 0
+    assert by_id["NotRecorded"].is_error
+    assert by_id["NotRecorded"].error_category == "request"
+
+
+def test_bulk_fetch_rejects_unknown_fields():
+    with pytest.raises(ValueError, match="'likes' is not a page engagement field"):
+        meta_stats.bulk.bulk_fetch_facebook_pages(["ChachiOfficial"], ["likes"], 1, TOKEN, VERSION)
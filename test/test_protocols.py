@@ -0,0 +1,96 @@
+import pytest
+
+import meta_stats
+from meta_stats.handle import FacebookPageRef, InstagramHandle
+from meta_stats.version import GraphVersion
+from conftest import TOKEN, VERSION
+
+
+def test_list_models_behave_as_sequences():
+    posts = meta_stats.facebook.BasicPostsInfo(
+        data=[meta_stats.facebook.PostInfo(id=str(i), created_time="2024-03-01T18:00:00+0000") for i in range(3)],
+        paging=meta_stats.meta.Paging(cursors=meta_stats.meta.Cursor(before="a", after="b")),
+    )
+
+    assert len(posts) == 3
+    assert [post.id for post in posts] == ["0", "1", "2"]
+    assert posts[0].id == "0"
+    assert posts[-1].id == "2"
+    assert [post.id for post in posts[::2]] == ["0", "2"]
+    assert posts[5:] == []
+    with pytest.raises(IndexError):
+        posts[3]
+    with pytest.raises(IndexError):
+        posts[-4]
+
+
+def test_nested_lists_behave_as_sequences(cassette):
+    cassette("facebook")
+    post = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info[0]
+
+    assert len(post.comments) == len(post.comments.data)
+    assert [comment.id for comment in post.comments] == [comment.id for comment in post.comments.data]
+    assert len(post.likes) == 1
+
+
+def test_reprs_show_key_fields(cassette):
+    cassette("facebook")
+    result = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+
+    assert repr(result) == "PageEngagementResult(PageEngagement(100, 52000 followers, 48000 fans))"
+    assert repr(result.page_engagement.category_list[0]) == "PageCategory(Musician/Band)"
+
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION)
+    assert repr(posts) == "BasicPostsInfoResult(BasicPostsInfo(1 post))"
+    assert repr(posts.posts_info[0]) == 'PostInfo(100_1, 2024-03-01T18:00:00Z, "Live at the Garuda this Friday!")'
+    assert repr(posts.posts_info[0].comments) == "BasicCommentsInfo(1 of 14 comments)"
+
+
+def test_failed_result_reprs_show_the_error(cassette):
+    cassette("instagram")
+    limited = meta_stats.instagram.get_instagram_page_info("ratelimited")
+
+    assert repr(limited).startswith("InstagramPageInfoResult(MetaInstagramError(rate_limit, fail: Please wait")
+
+
+def test_html_views(cassette):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION)
+
+    html = posts._repr_html_()
+    assert html.startswith("<table><caption>BasicPostsInfoResult (1 row)</caption>")
+    assert "<th>message</th>" in html
+    assert "Live at the Garuda this Friday!" in html
+
+    page = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION).page_engagement
+    html = page._repr_html_()
+    assert "<caption>PageEngagement</caption>" in html
+    assert "category_list" in html
+    assert "52000" in html
+
+
+def test_html_views_escape_and_truncate():
+    comments = meta_stats.facebook.BasicCommentsInfo(
+        data=[meta_stats.facebook.CommentInfo(id=str(i), message="<b>hi</b>") for i in range(60)],
+    )
+
+    html = comments._repr_html_()
+    assert "&lt;b&gt;hi&lt;/b&gt;" in html
+    assert "<b>" not in html
+    assert html.count("<tr>") == 51
+    assert "… and 10 more rows" in html
+
+
+def test_handles_compare_and_hash_by_value():
+    assert FacebookPageRef("ChachiOfficial") == FacebookPageRef("https://www.facebook.com/ChachiOfficial/about")
+    assert FacebookPageRef("ChachiOfficial") != FacebookPageRef("100")
+    assert len({InstagramHandle("hotelgaruda"), InstagramHandle("@HotelGaruda"), InstagramHandle("instagram.com/hotelgaruda/")}) == 1
+    assert InstagramHandle("hotelgaruda") != "hotelgaruda"
+
+
+def test_graph_versions_order():
+    assert GraphVersion("v21") < GraphVersion("22.0") <= GraphVersion("v22")
+    assert sorted([GraphVersion("v22"), GraphVersion("v19"), GraphVersion("v21")]) == [GraphVersion("v19"), GraphVersion("v21"), GraphVersion("v22")]
+    assert {GraphVersion("v22"): "current"}[GraphVersion("22")] == "current"
+    with pytest.raises(TypeError):
+        GraphVersion("v22") < "v23"
@@ -0,0 +1,71 @@
+import sqlite3
+
+import pytest
+
+import meta_stats
+
+DAY = 86400
+PAGE = meta_stats.snapshot.entity("facebook_page", "ChachiOfficial")
+
+
+@pytest.fixture
+def store(tmp_path):
+    return meta_stats.snapshot.SnapshotStore.open(str(tmp_path / "snapshots.sqlite"))
+
+
+def test_history_is_ordered_and_filtered(store):
+    for day, followers in [(3, 1300), (1, 1000), (2, 1100)]:
+        store.record(PAGE, "followers_count", followers, day * DAY)
+    store.record(PAGE, "fan_count", 900, 2 * DAY)
+
+    history = store.history(PAGE, "followers_count")
+    assert [snapshot.value for snapshot in history] == [1000, 1100, 1300]
+    assert [snapshot.value for snapshot in store.history(PAGE, "followers_count", 2 * DAY)] == [1100, 1300]
+    assert [snapshot.value for snapshot in store.history(PAGE, "followers_count", None, 2 * DAY)] == [1000, 1100]
+    assert store.history(PAGE, "missing") == []
+
+
+def test_latest_returns_newest_value_per_metric(store):
+    store.record(PAGE, "followers_count", 1000, DAY)
+    store.record(PAGE, "followers_count", 1200, 3 * DAY)
+    store.record(PAGE, "fan_count", 900, 2 * DAY)
+    store.record("instagram_profile:hotelgaruda", "followers_count", 15400)
+
+    latest = {snapshot.metric: snapshot for snapshot in store.latest(PAGE)}
+    assert latest["followers_count"].value == 1200
+    assert latest["followers_count"].captured_at == 3 * DAY
+    assert latest["fan_count"].value == 900
+    assert store.entities() == ["facebook_page:ChachiOfficial", "instagram_profile:hotelgaruda"]
+    assert store.entities("instagram_profile") == ["instagram_profile:hotelgaruda"]
+
+
+def test_reopening_keeps_data_and_schema_version(tmp_path):
+    path = str(tmp_path / "snapshots.sqlite")
+    meta_stats.snapshot.SnapshotStore.open(path).record(PAGE, "followers_count", 1000, DAY)
+
+    reopened = meta_stats.snapshot.SnapshotStore.open(path)
+    assert reopened.history(PAGE, "followers_count")[0].value == 1000
+    assert sqlite3.connect(path).execute("PRAGMA user_version").fetchone()[0] == reopened.schema_version
+
+
+def test_refuses_newer_schema(tmp_path):
+    path = str(tmp_path / "snapshots.sqlite")
+    connection = sqlite3.connect(path)
+    connection.execute("PRAGMA user_version = 99")
+    connection.close()
+
+    with pytest.raises(ValueError, match="newer than this library supports"):
+        meta_stats.snapshot.SnapshotStore.open(path)
+
+
+def test_names_move_history_to_the_account_id(store):
+    store.record(PAGE, "followers_count", 1000, DAY)
+    store.record_name("facebook", "100", "ChachiOfficial", 2 * DAY)
+    store.record("facebook_page:100", "followers_count", 1100, 2 * DAY)
+    store.record_name("facebook", "100", "ChachiMusic", 3 * DAY)
+
+    assert store.entities() == ["facebook_page:100"]
+    assert store.account_id("facebook", "chachiofficial") == "100"
+    assert [name.name for name in store.names("facebook", "100")] == ["ChachiOfficial", "ChachiMusic"]
+    for name in [PAGE, "facebook_page:ChachiMusic", "facebook_page:100"]:
+        assert [snapshot.value for snapshot in store.history(name, "followers_count")] == [1000, 1100]
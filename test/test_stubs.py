@@ -0,0 +1,68 @@
+import ast
+import subprocess
+import sys
+import types
+from pathlib import Path
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+ROOT = Path(__file__).parent.parent
+STUBS = ROOT / "python" / "meta_stats"
+
+
+def stub_members(name):
+    """Top-level names of a stub, each mapped to its class members, or `None` for a function."""
+    tree = ast.parse((STUBS / f"{name}.pyi").read_text())
+    members = {}
+    for node in tree.body:
+        if isinstance(node, ast.ClassDef):
+            members[node.name] = {item.name for item in node.body if isinstance(item, ast.FunctionDef)}
+        elif isinstance(node, ast.FunctionDef):
+            members[node.name] = None
+    return members
+
+
+def test_stubs_are_up_to_date():
+    result = subprocess.run([sys.executable, str(ROOT / "scripts" / "generate_stubs.py"), "--check"], capture_output=True, text=True)
+    assert result.returncode == 0, result.stderr
+    assert (STUBS / "py.typed").exists()
+
+
+def test_stubs_cover_every_exported_name():
+    # The package re-exports the compiled `meta_stats.meta_stats` extension, which has no stub of its own.
+    modules = [value for value in vars(meta_stats).values() if isinstance(value, types.ModuleType) and value.__name__ != "meta_stats.meta_stats"]
+    assert len(modules) > 10
+
+    for module in modules:
+        name = module.__name__.rpartition(".")[2]
+        stubs = stub_members(name)
+        exported = {attr for attr in dir(module) if not attr.startswith("_")}
+        assert exported == set(stubs), name
+
+        for attr in exported:
+            value = getattr(module, attr)
+            if isinstance(value, type):
+                assert value.__module__ == f"meta_stats.{name}"
+                public = {member for member in dir(value) if not member.startswith("_")}
+                assert public <= stubs[attr], f"{name}.{attr}: {public - stubs[attr]}"
+
+
+def test_models_are_exported_and_constructible(cassette):
+    cassette("facebook")
+    result = meta_stats.facebook.get_facebook_page_followers(TOKEN, "ChachiOfficial", VERSION)
+    assert isinstance(result, meta_stats.facebook.PageEngagementResult)
+    assert isinstance(result.page_engagement, meta_stats.facebook.PageEngagement)
+
+    category = meta_stats.facebook.PageCategory(id="1", name="Musician/Band")
+    page = meta_stats.facebook.PageEngagement(id="100", followers_count=10, category_list=[category])
+    assert page.followers_count == 10
+    assert page.category_list[0].name == "Musician/Band"
+
+    with pytest.raises(ValueError, match="Invalid Cursor JSON"):
+        meta_stats.meta.Cursor(before="a")
+
+    assert meta_stats.handle.FacebookPageRef("https://www.facebook.com/profile.php?id=100123").id == "100123"
+    assert meta_stats.version.GraphVersion("v22.0").major == 22
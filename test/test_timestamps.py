@@ -0,0 +1,80 @@
+import csv
+import json
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+import meta_stats
+from conftest import TOKEN, VERSION
+
+
+def test_graph_times_are_aware_datetimes(cassette):
+    cassette("facebook")
+    post = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info[0]
+
+    assert post.created_time == datetime(2024, 3, 1, 18, tzinfo=timezone.utc)
+    assert post.created_time.tzinfo is not None
+    assert post.comments[0].created_time == datetime(2024, 3, 1, 19, tzinfo=timezone.utc)
+
+
+def test_offsets_are_converted_to_utc(cassette):
+    cassette("facebook")
+    event = meta_stats.facebook.get_facebook_page_events(TOKEN, "ChachiOfficial", VERSION, "upcoming").events[0]
+
+    assert event.start_time == datetime(2024, 3, 9, 3, tzinfo=timezone.utc)
+    assert event.start_time == datetime(2024, 3, 8, 21, tzinfo=timezone(timedelta(hours=-6)))
+
+
+def test_json_keeps_the_graph_format():
+    post = meta_stats.facebook.PostInfo(id="1", created_time=datetime(2024, 3, 1, 12, tzinfo=timezone(timedelta(hours=-6))))
+
+    assert post.created_time == datetime(2024, 3, 1, 18, tzinfo=timezone.utc)
+    assert json.loads(post.to_json())["created_time"] == "2024-03-01T18:00:00+0000"
+    assert post.to_dict()["created_time"] == "2024-03-01T18:00:00+0000"
+    assert meta_stats.facebook.PostInfo.from_json(post.to_json()).created_time == post.created_time
+
+
+def test_since_and_until_need_aware_datetimes(cassette):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts
+
+    with pytest.raises(ValueError, match="no time zone"):
+        posts(TOKEN, "ChachiOfficial", VERSION, since=datetime(2024, 3, 1))
+    with pytest.raises(ValueError, match="since"):
+        posts(TOKEN, "ChachiOfficial", VERSION, since=datetime(2024, 3, 2, tzinfo=timezone.utc), until=datetime(2024, 3, 1, tzinfo=timezone.utc))
+    with pytest.raises(TypeError):
+        posts(TOKEN, "ChachiOfficial", VERSION, since="2024-03-01")
+
+
+def test_exports_timestamp_columns(cassette, tmp_path):
+    cassette("facebook")
+    posts = meta_stats.facebook.get_facebook_page_posts_with_summary(TOKEN, "ChachiOfficial", VERSION).posts_info.data
+    writer = meta_stats.dataset.DatasetWriter.open(str(tmp_path), "csv")
+
+    part = writer.write_posts("ChachiOfficial", posts, 0)
+    with open(part.path, newline="") as file:
+        row = next(csv.DictReader(file))
+    assert row["created_time"] == "2024-03-01T18:00:00+0000"
+    assert row["created_time_utc"] == "2024-03-01T18:00:00Z"
+    columns = list(row)
+    assert columns.index("created_time") == 3
+    assert columns[-1] == "created_time_utc"
+
+
+def test_missing_times_stay_none():
+    post = meta_stats.facebook.PostInfo.from_json('{"id": "1"}')
+    assert post.created_time is None
+    assert json.loads(post.to_json())["created_time"] is None
+
+    media = meta_stats.instagram.FeedMedia.from_json('{"id": "1"}')
+    assert media.taken_at is None
+    assert meta_stats.instagram.FeedMedia.from_json('{"id": "1", "taken_at": 1709316000}').taken_at == datetime(2024, 3, 1, 18, tzinfo=timezone.utc)
+
+
+def test_since_and_until_are_keyword_only(cassette):
+    cassette("facebook")
+    config = meta_stats.config.load_config()
+
+    with pytest.raises(TypeError):
+        meta_stats.facebook.get_facebook_page_posts(TOKEN, "ChachiOfficial", VERSION, None, datetime(2024, 3, 1, tzinfo=timezone.utc))
+    assert meta_stats.facebook.get_facebook_page_posts(TOKEN, "ChachiOfficial", VERSION, None, config).is_success
@@ -0,0 +1,45 @@
+import pytest
+
+import meta_stats
+from conftest import CASSETTES
+
+GraphVersion = meta_stats.version.GraphVersion
+
+
+def test_parses_and_normalizes_versions():
+    assert str(GraphVersion.parse("22.0")) == "v22.0"
+    assert str(GraphVersion.parse("v22")) == "v22.0"
+    assert GraphVersion.parse("v22.0").major == 22
+    assert GraphVersion.parse("v22.0").released == "2025-01-21"
+
+    for bad in ["latest", "v22.0x", "", "vv22"]:
+        with pytest.raises(ValueError, match="not a Graph API version"):
+            GraphVersion.parse(bad)
+
+
+def test_knows_retired_versions():
+    assert GraphVersion.parse("v12.0").is_retired
+    assert GraphVersion.parse("v19.0").is_retired
+    assert GraphVersion.parse("v19.0").expires == "2026-05-21"
+    assert GraphVersion.parse("v19.0").days_until_expiry < 0
+    assert all(not version.is_retired for version in meta_stats.version.supported_graph_versions())
+
+
+def test_malformed_version_is_rejected_before_the_request():
+    with pytest.raises(ValueError, match="'22,0' is not a Graph API version"):
+        meta_stats.facebook.get_facebook_page_info("test-token", "ChachiOfficial", "22,0")
+
+
+def test_retired_version_warns():
+    meta_stats.cassette.use_cassette(str(CASSETTES / "facebook.json"), "replay")
+    try:
+        with pytest.warns(DeprecationWarning, match="v18.0 was retired on 2026-01-26"):
+            with pytest.raises(ValueError, match="No recorded interaction"):
+                meta_stats.facebook.get_facebook_page_info("test-token", "ChachiOfficial", "v18.0")
+    finally:
+        meta_stats.cassette.eject_cassette()
+
+
+def test_config_rejects_malformed_version():
+    with pytest.raises(ValueError, match="not a Graph API version"):
+        meta_stats.config.load_config(None, None, "TEST_VERSION_UNSET_", version="twenty-two")
@@ -0,0 +1,107 @@
+//! Runs the `meta-stats` binary against the mock server and checks its exit codes and output formats.
+
+use meta_stats::mock_server::MockServer;
+use serde_json::Value;
+use std::process::{Command, Output};
+
+fn server() -> MockServer {
+    let server = MockServer::start(None).unwrap();
+    server.add_page("ChachiOfficial".to_string(), "Chachi".to_string(), Some(52000), Some(48000), Some(3)).unwrap();
+    server
+}
+
+fn meta_stats(server: &MockServer, args: &[&str]) -> Output {
+    command(server).args(["--token", "mock-token"]).args(args).output().unwrap()
+}
+
+/// The binary pointed at the mock server, requesting an app token since no `--token` is given.
+fn command(server: &MockServer) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_meta-stats"));
+    command
+        .args(["--api-version", "v22.0", "--graph-base-url", &server.url(), "--instagram-base-url", &server.url()])
+        .env("META_CLIENT_ID", "mock-client")
+        .env("META_CLIENT_SECRET", "mock-secret")
+        .env("META_MAX_RETRIES", "0")
+        .env_remove("META_CONFIG")
+        .env_remove("META_PROFILE");
+    command
+}
+
+fn exit_code(output: &Output) -> i32 {
+    output.status.code().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn exit_codes_follow_the_error_category() {
+    let server = server();
+    let cases = [
+        ("invalid_token", None, 4),
+        ("permission", None, 5),
+        ("app_rate_limit", None, 6),
+        ("throttled", None, 6),
+        ("server_error", Some(503), 9),
+    ];
+
+    for (kind, status, expected) in cases {
+        server.inject_error(kind.to_string(), Some("ChachiOfficial".to_string()), None, status).unwrap();
+        let output = meta_stats(&server, &["page", "followers", "ChachiOfficial"]);
+        assert_eq!(exit_code(&output), expected, "{}: {}", kind, String::from_utf8_lossy(&output.stderr));
+        assert!(output.stdout.is_empty());
+    }
+
+    let output = meta_stats(&server, &["page", "info", "NoSuchPage"]);
+    assert_eq!(exit_code(&output), 7);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not_found"));
+
+    for args in [&["token"][..], &["page", "info", "ChachiOfficial"][..]] {
+        server.inject_error("invalid_client".to_string(), Some("oauth".to_string()), None, None).unwrap();
+        let output = command(&server).args(args).output().unwrap();
+        assert_eq!(exit_code(&output), 4, "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Error validating client secret"));
+    }
+}
+
+#[test]
+fn prints_json() {
+    let server = server();
+
+    let output = meta_stats(&server, &["--format", "json", "page", "info", "ChachiOfficial"]);
+    assert_eq!(exit_code(&output), 0);
+    let page: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(page["name"], "Chachi");
+
+    let output = meta_stats(&server, &["--format", "json", "page", "posts", "ChachiOfficial"]);
+    assert_eq!(exit_code(&output), 0);
+    let posts: Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(posts.as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn prints_csv() {
+    let server = server();
+
+    let output = meta_stats(&server, &["--format", "csv", "page", "posts", "ChachiOfficial"]);
+    assert_eq!(exit_code(&output), 0);
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "id,created_time,message,story,likes,comments");
+    assert_eq!(lines.len(), 4);
+    assert!(lines[1].starts_with("ChachiOfficial_1,"));
+}
+
+#[test]
+fn prints_a_table() {
+    let server = server();
+
+    let output = meta_stats(&server, &["page", "posts", "ChachiOfficial"]);
+    assert_eq!(exit_code(&output), 0);
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("id  "));
+    assert!(lines[1].chars().all(|c| c == '-' || c == ' '));
+    assert_eq!(lines.len(), 5);
+}